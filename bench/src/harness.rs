@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anchor_spl::token::spl_token;
+use solana_program_test::{find_file, read_file, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use std::collections::BTreeMap;
+
+/// Compute limit requested for every measured transaction (runtime maximum)
+pub const MAX_TX_COMPUTE_UNITS: u32 = 1_400_000;
+
+pub struct BenchContext {
+    pub ctx: ProgramTestContext,
+    /// Upgrade authority of the deployed program
+    pub upgrade_authority: Keypair,
+    /// Highest compute units observed per label
+    pub results: BTreeMap<String, u64>,
+}
+
+impl BenchContext {
+    /// Start a bank with the compiled program loaded from `target/deploy/ipt.so`.
+    /// BPF is required: native execution does not meter compute units.
+    pub async fn start() -> Self {
+        let upgrade_authority = Keypair::new();
+
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+        program_test.set_compute_max_units(MAX_TX_COMPUTE_UNITS as u64);
+        add_upgradeable_program(&mut program_test, &upgrade_authority.pubkey());
+        program_test.add_account(
+            upgrade_authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000,
+                ..Account::default()
+            },
+        );
+
+        Self {
+            ctx: program_test.start_with_context().await,
+            upgrade_authority,
+            results: BTreeMap::new(),
+        }
+    }
+
+    pub fn payer(&self) -> Keypair {
+        Keypair::from_bytes(&self.ctx.payer.to_bytes()).unwrap()
+    }
+
+    /// Send a transaction without recording its cost
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<u64, BanksClientError> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+
+        let mut all_instructions =
+            vec![ComputeBudgetInstruction::set_compute_unit_limit(MAX_TX_COMPUTE_UNITS)];
+        all_instructions.extend_from_slice(instructions);
+
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            &all_instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        let outcome = self
+            .ctx
+            .banks_client
+            .process_transaction_with_metadata(tx)
+            .await?;
+        let metadata = outcome.metadata.unwrap_or_else(|| panic!("missing metadata"));
+
+        if let Err(err) = outcome.result {
+            for line in &metadata.log_messages {
+                eprintln!("    {}", line);
+            }
+            return Err(err.into());
+        }
+
+        Ok(metadata.compute_units_consumed)
+    }
+
+    /// Send a transaction and record its compute units under `label`
+    pub async fn measure(&mut self, label: &str, instructions: &[Instruction], signers: &[&Keypair]) {
+        let units = self
+            .send(instructions, signers)
+            .await
+            .unwrap_or_else(|err| panic!("{} failed: {}", label, err));
+
+        let entry = self.results.entry(label.to_string()).or_insert(0);
+        *entry = (*entry).max(units);
+    }
+
+    pub async fn fund(&mut self, recipient: &Pubkey, lamports: u64) {
+        let payer = self.payer().pubkey();
+        self.send(&[system_instruction::transfer(&payer, recipient, lamports)], &[])
+            .await
+            .unwrap();
+    }
+
+    pub async fn create_mint(&mut self, authority: &Pubkey, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer().pubkey();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+
+        self.send(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    authority,
+                    None,
+                    decimals,
+                )
+                .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+
+        mint.pubkey()
+    }
+
+    pub async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let account = Keypair::new();
+        let payer = self.payer().pubkey();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+
+        self.send(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &account.pubkey(),
+                    rent.minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_account(
+                    &spl_token::ID,
+                    &account.pubkey(),
+                    mint,
+                    owner,
+                )
+                .unwrap(),
+            ],
+            &[&account],
+        )
+        .await
+        .unwrap();
+
+        account.pubkey()
+    }
+
+    pub async fn mint_to(&mut self, mint: &Pubkey, to: &Pubkey, authority: &Keypair, amount: u64) {
+        self.send(
+            &[spl_token::instruction::mint_to(
+                &spl_token::ID,
+                mint,
+                to,
+                &authority.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap()],
+            &[authority],
+        )
+        .await
+        .unwrap();
+    }
+
+    pub async fn token_balance(&mut self, account: &Pubkey) -> u64 {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(*account)
+            .await
+            .unwrap()
+            .unwrap();
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    /// Current bank time, for instructions that take an observation timestamp
+    pub async fn unix_timestamp(&mut self) -> i64 {
+        self.ctx
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+    }
+
+    /// Move the bank clock forward by `secs`
+    pub async fn warp_clock(&mut self, secs: i64) {
+        let mut clock = self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp += secs;
+        self.ctx.set_sysvar(&clock);
+    }
+}
+
+/// Deploy the program through the upgradeable loader so instructions gated on
+/// the upgrade authority (e.g. init_protocol_config) can be exercised.
+fn add_upgradeable_program(program_test: &mut ProgramTest, upgrade_authority: &Pubkey) {
+    let elf = read_file(find_file("ipt.so").expect("ipt.so not found - run `anchor build` first"));
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[ipt::ID.as_ref()], &bpf_loader_upgradeable::id());
+
+    let program_data = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address,
+    })
+    .unwrap();
+
+    let mut programdata_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*upgrade_authority),
+    })
+    .unwrap();
+    programdata_data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+    programdata_data.extend_from_slice(&elf);
+
+    program_test.add_account(
+        ipt::ID,
+        Account {
+            lamports: 1_000_000_000,
+            data: program_data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+    program_test.add_account(
+        programdata_address,
+        Account {
+            lamports: 1_000_000_000,
+            data: programdata_data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[ipt::ID.as_ref()], &bpf_loader_upgradeable::id()).0
+}
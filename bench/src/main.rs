@@ -0,0 +1,1163 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compute-unit benchmarks for every refi-ipt instruction.
+//!
+//! Requires the program to be built first (`anchor build`), then:
+//!
+//!   cargo run -p ipt-bench            # compare against cu-baseline.txt
+//!   cargo run -p ipt-bench -- --record  # overwrite cu-baseline.txt
+//!
+//! Exits non-zero when an instruction exceeds its hard ceiling or regresses
+//! more than `TOLERANCE_PCT` above the recorded baseline, and when the
+//! baseline is missing or has no entry for an instruction. The batch size cap
+//! in `batch_execute_withdraw` is derived from the worst-case batch numbers.
+
+mod harness;
+
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use harness::{BenchContext, MAX_TX_COMPUTE_UNITS};
+use ipt::states::{AdminAction, FeeBucket, PauseFlag, PoolConfig, ReserveMovementReason};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    sysvar,
+};
+use std::{collections::BTreeMap, fs, path::PathBuf, process::ExitCode};
+
+/// Allowed growth over the recorded baseline before the run fails
+const TOLERANCE_PCT: u64 = 5;
+
+/// Default per-instruction budget clients get without a compute budget request
+const DEFAULT_INSTRUCTION_CEILING: u64 = 200_000;
+
+/// Batches must leave headroom below the per-transaction maximum
+const BATCH_CEILING: u64 = MAX_TX_COMPUTE_UNITS as u64 * 9 / 10;
+
+/// Maximum batch size accepted by batch_execute_withdraw
+const MAX_BATCH_SIZE: usize = 10;
+
+const DECIMALS: u8 = 6;
+const USER_DEPOSIT: u64 = 1_000 * 10u64.pow(DECIMALS as u32);
+
+struct Pool {
+    protocol_config: Pubkey,
+    usdc_mint: Pubkey,
+    pool_id: u64,
+    pool: Pubkey,
+    ipt_mint: Pubkey,
+    usdc_reserve: Pubkey,
+}
+
+impl Pool {
+    fn derive(protocol_config: Pubkey, usdc_mint: Pubkey, pool_id: u64) -> Self {
+        let (pool, _) = Pubkey::find_program_address(
+            &[
+                ipt::states::Pool::SEED_PREFIX,
+                usdc_mint.as_ref(),
+                pool_id.to_le_bytes().as_ref(),
+            ],
+            &ipt::ID,
+        );
+        let (ipt_mint, _) = Pubkey::find_program_address(&[b"ipt_mint", pool.as_ref()], &ipt::ID);
+        let (usdc_reserve, _) =
+            Pubkey::find_program_address(&[b"usdc_reserve", pool.as_ref()], &ipt::ID);
+
+        Self {
+            protocol_config,
+            usdc_mint,
+            pool_id,
+            pool,
+            ipt_mint,
+            usdc_reserve,
+        }
+    }
+}
+
+struct User {
+    keypair: Keypair,
+    usdc_account: Pubkey,
+    ipt_account: Pubkey,
+}
+
+fn ceiling(label: &str) -> u64 {
+    if label.starts_with("batch_execute_withdraw") {
+        BATCH_CEILING
+    } else {
+        DEFAULT_INSTRUCTION_CEILING
+    }
+}
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("cu-baseline.txt")
+}
+
+fn read_baseline() -> std::io::Result<BTreeMap<String, u64>> {
+    Ok(fs::read_to_string(baseline_path())?
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (label, units) = line.rsplit_once(' ')?;
+            Some((label.trim().to_string(), units.trim().parse().ok()?))
+        })
+        .collect())
+}
+
+fn write_baseline(results: &BTreeMap<String, u64>) {
+    let mut contents = String::from("# Compute units per instruction, recorded by `cargo run -p ipt-bench -- --record`\n");
+    for (label, units) in results {
+        contents.push_str(&format!("{} {}\n", label, units));
+    }
+    fs::write(baseline_path(), contents).unwrap();
+}
+
+fn pool_config(admin: &Pubkey, oracle: &Pubkey, fee_collector: &Pubkey) -> PoolConfig {
+    PoolConfig {
+        admin_authority: *admin,
+        oracle_authority: *oracle,
+        fee_collector: *fee_collector,
+        treasurer_authority: *admin,
+        deposit_fee_bps: 50,
+        withdrawal_fee_bps: 100,
+        management_fee_bps: 50,
+        initial_exchange_rate: 1_000_000,
+        max_total_supply: 0,
+        max_queue_size: ipt::states::Pool::MAX_QUEUE_SIZE as u32,
+        timelock_delay: 0,
+        rate_floor: 0,
+        rate_ceiling: 0,
+        max_heartbeat_secs: 0,
+        dead_man_switch_secs: 0,
+        min_rate_update_interval_secs: 0,
+        max_redemption_bps_per_epoch: 0,
+        queue_pressure_depth: 0,
+        queue_pressure_bps: 0,
+        rebasing: false,
+        epoch_duration_secs: 86_400,
+        segregate_roles: false,
+        isolated_exits: true,
+        queue_age_weight: 0,
+        queue_size_weight: 0,
+        max_batch_share_bps: 0,
+        max_skip_attempts: 3,
+        min_execution_delay_secs: 0,
+        cancellation_fee_bps: 10,
+        cancellation_grace_secs: 86_400,
+        commit_reveal_threshold: 0,
+        reveal_delay_slots: 0,
+        approval_threshold: 0,
+        compliance_authority: Pubkey::default(),
+        approval_expiry_secs: 0,
+        large_withdrawal_threshold: 0,
+        withdrawal_cosigner: Pubkey::default(),
+        max_buyback_usdc: 0,
+        fee_sweep_threshold: 0,
+        fee_recipients: vec![],
+        oracle_overlap_secs: 0,
+        promo_start_at: 0,
+        promo_end_at: 0,
+        promo_bonus_bps: 0,
+        promo_waives_deposit_fee: false,
+        early_supporter_slots: 0,
+        early_supporter_discount_bps: 0,
+        referral_fee_share_bps: 0,
+        allowlist_only: false,
+        recovery_authority: Pubkey::default(),
+        dormancy_period_secs: 0,
+        escheat_custody: Pubkey::default(),
+        reserve_drift_tolerance_bps: 0,
+        wind_down_waterfall: vec![],
+        pull_payouts: false,
+        guardian_authority: Pubkey::default(),
+        queue_alert_depth: 0,
+        queue_alert_usdc: 0,
+        tier_terms: vec![],
+        max_queue_entries_per_day: 0,
+        refund_window_secs: 0,
+        slot_stable_quotes: false,
+        sweep_wrapper_program: Pubkey::default(),
+        sweep_wrapped_mint: Pubkey::default(),
+        liquidity_buffer_usdc: 0,
+    }
+}
+
+fn init_pool_ix(pool: &Pool, admin: &Pubkey, config: &PoolConfig) -> Instruction {
+    Instruction {
+        program_id: ipt::ID,
+        accounts: ipt::accounts::InitializePool {
+            payer: *admin,
+            protocol_config: pool.protocol_config,
+            usdc_mint: pool.usdc_mint,
+            pool: pool.pool,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: ipt::instruction::InitPool {
+            pool_id: pool.pool_id,
+            config: config.clone(),
+        }
+        .data(),
+    }
+}
+
+fn init_pool_step2_ix(pool: &Pool, admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ipt::ID,
+        accounts: ipt::accounts::InitializePoolStep2 {
+            admin: *admin,
+            pool: pool.pool,
+            pool_authority: pool.pool,
+            usdc_mint: pool.usdc_mint,
+            ipt_mint: pool.ipt_mint,
+            usdc_reserve: pool.usdc_reserve,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: ipt::instruction::InitPoolStep2 { ipt_decimals: DECIMALS }.data(),
+    }
+}
+
+fn user_deposit_ix(pool: &Pool, user: &User, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ipt::ID,
+        accounts: ipt::accounts::UserDeposit {
+            user: user.keypair.pubkey(),
+            pool: pool.pool,
+            protocol_config: pool.protocol_config,
+            pool_authority: pool.pool,
+            user_usdc_account: user.usdc_account,
+            user_ipt_account: user.ipt_account,
+            pool_usdc_reserve: pool.usdc_reserve,
+            ipt_mint: pool.ipt_mint,
+            position: None,
+            referral: None,
+            partner_account: None,
+            access_entry: None,
+            cost_basis: None,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: ipt::instruction::UserDeposit {
+            net_usdc_amount: amount,
+            min_ipt_amount: 0,
+        }
+        .data(),
+    }
+}
+
+fn user_withdraw_ix(pool: &Pool, user: &User, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ipt::ID,
+        accounts: ipt::accounts::UserWithdraw {
+            user: user.keypair.pubkey(),
+            pool: pool.pool,
+            protocol_config: pool.protocol_config,
+            pool_authority: pool.pool,
+            user_usdc_account: user.usdc_account,
+            user_ipt_account: user.ipt_account,
+            pool_usdc_reserve: pool.usdc_reserve,
+            ipt_mint: pool.ipt_mint,
+            token_program: spl_token::ID,
+            instructions: sysvar::instructions::ID,
+            position: None,
+            cost_basis: None,
+            access_entry: None,
+        }
+        .to_account_metas(None),
+        data: ipt::instruction::UserWithdraw {
+            net_ipt_amount: amount,
+            min_usdc_amount: 0,
+        }
+        .data(),
+    }
+}
+
+fn admin_deposit_ix(pool: &Pool, treasurer: &Pubkey, treasurer_usdc: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ipt::ID,
+        accounts: ipt::accounts::AdminDepositUsdc {
+            treasurer: *treasurer,
+            pool: pool.pool,
+            treasurer_usdc_account: *treasurer_usdc,
+            pool_usdc_reserve: pool.usdc_reserve,
+            token_program: spl_token::ID,
+            memo_program: None,
+        }
+        .to_account_metas(None),
+        data: ipt::instruction::AdminDepositUsdc {
+            amount,
+            reason: ReserveMovementReason::Rebalance,
+            memo: None,
+        }.data(),
+    }
+}
+
+fn admin_withdraw_ix(pool: &Pool, treasurer: &Pubkey, treasurer_usdc: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ipt::ID,
+        accounts: ipt::accounts::AdminWithdrawUsdc {
+            treasurer: *treasurer,
+            pool: pool.pool,
+            pool_authority: pool.pool,
+            treasurer_usdc_account: *treasurer_usdc,
+            pool_usdc_reserve: pool.usdc_reserve,
+            token_program: spl_token::ID,
+            cosigner: None,
+            memo_program: None,
+        }
+        .to_account_metas(None),
+        data: ipt::instruction::AdminWithdrawUsdc {
+            amount,
+            reason: ReserveMovementReason::Rebalance,
+            memo: None,
+        }.data(),
+    }
+}
+
+fn batch_execute_ix(pool: &Pool, executor: &Pubkey, users: &[&User], amounts: Vec<u64>) -> Instruction {
+    let mut accounts = ipt::accounts::BatchExecuteWithdraw {
+        executor: *executor,
+        pool: pool.pool,
+        protocol_config: pool.protocol_config,
+        pool_authority: pool.pool,
+        pool_usdc_reserve: pool.usdc_reserve,
+        ipt_mint: pool.ipt_mint,
+        token_program: spl_token::ID,
+        instructions: sysvar::instructions::ID,
+    }
+    .to_account_metas(None);
+
+    for user in users {
+        accounts.push(AccountMeta::new(user.ipt_account, false));
+        accounts.push(AccountMeta::new(user.usdc_account, false));
+    }
+
+    Instruction {
+        program_id: ipt::ID,
+        accounts,
+        data: ipt::instruction::BatchExecuteWithdraw { amounts }.data(),
+    }
+}
+
+fn crank_withdraw_queue_ix(pool: &Pool, executor: &Pubkey) -> Instruction {
+    Instruction {
+        data: ipt::instruction::CrankWithdrawQueue {}.data(),
+        ..batch_execute_ix(pool, executor, &[], Vec::new())
+    }
+}
+
+fn protocol_pause_ix(pool: &Pool, protocol_admin: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: ipt::ID,
+        accounts: ipt::accounts::ProtocolPause {
+            protocol_admin: *protocol_admin,
+            protocol_config: pool.protocol_config,
+        }
+        .to_account_metas(None),
+        data: ipt::instruction::ProtocolPause { paused }.data(),
+    }
+}
+
+async fn queued_amount(bench: &mut BenchContext, pool: &Pool, user: &User) -> u64 {
+    let account = bench
+        .ctx
+        .banks_client
+        .get_account(pool.pool)
+        .await
+        .unwrap()
+        .unwrap();
+    let pool_state =
+        <ipt::states::Pool as anchor_lang::AccountDeserialize>::try_deserialize(&mut account.data.as_slice())
+            .unwrap();
+
+    pool_state
+        .queued()
+        .iter()
+        .find(|w| w.user == user.keypair.pubkey())
+        .map(|w| w.amount)
+        .unwrap_or(0)
+}
+
+async fn run(bench: &mut BenchContext) {
+    let admin = bench.payer();
+    let oracle = Keypair::new();
+    let fee_collector = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    // ---------------- Protocol initialization ----------------
+    let (protocol_config, _) =
+        Pubkey::find_program_address(&[ipt::states::ProtocolConfig::SEED_PREFIX], &ipt::ID);
+    let upgrade_authority = Keypair::from_bytes(&bench.upgrade_authority.to_bytes()).unwrap();
+
+    bench
+        .measure(
+            "init_protocol_config",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::InitProtocolConfig {
+                    authority: upgrade_authority.pubkey(),
+                    protocol_config,
+                    program: ipt::ID,
+                    program_data: harness::program_data_address(),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::InitProtocolConfig {
+                    protocol_admin: admin.pubkey(),
+                    max_deposit_fee_bps: 1_000,
+                    max_withdrawal_fee_bps: 1_000,
+                    max_management_fee_bps: 1_000,
+                }
+                .data(),
+            }],
+            &[&upgrade_authority],
+        )
+        .await;
+
+    // ---------------- Pool initialization ----------------
+    let usdc_mint = bench.create_mint(&mint_authority.pubkey(), DECIMALS).await;
+    let pool = Pool::derive(protocol_config, usdc_mint, 0);
+    let config = pool_config(&admin.pubkey(), &oracle.pubkey(), &fee_collector.pubkey());
+
+    bench
+        .measure("init_pool", &[init_pool_ix(&pool, &admin.pubkey(), &config)], &[])
+        .await;
+    bench
+        .measure("init_pool_step2", &[init_pool_step2_ix(&pool, &admin.pubkey())], &[])
+        .await;
+
+    // Sibling pool on the same mint, target of migrate_position
+    let successor = Pool::derive(protocol_config, usdc_mint, 1);
+    bench
+        .measure(
+            "clone_pool",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::ClonePool {
+                    admin: admin.pubkey(),
+                    source_pool: pool.pool,
+                    protocol_config: pool.protocol_config,
+                    usdc_mint: successor.usdc_mint,
+                    pool: successor.pool,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::ClonePool {
+                    new_pool_id: successor.pool_id,
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+    bench
+        .send(&[init_pool_step2_ix(&successor, &admin.pubkey())], &[])
+        .await
+        .unwrap();
+
+    // ---------------- Participants ----------------
+    let admin_usdc = bench.create_token_account(&pool.usdc_mint, &admin.pubkey()).await;
+    bench
+        .mint_to(&pool.usdc_mint, &admin_usdc, &mint_authority, 100 * USER_DEPOSIT)
+        .await;
+    let fee_collector_usdc = bench
+        .create_token_account(&pool.usdc_mint, &fee_collector.pubkey())
+        .await;
+    bench.fund(&oracle.pubkey(), 1_000_000_000).await;
+    bench.fund(&fee_collector.pubkey(), 1_000_000_000).await;
+
+    // One extra user so a full-size batch remains after the single-entry batch
+    let mut users = Vec::with_capacity(MAX_BATCH_SIZE + 1);
+    for _ in 0..=MAX_BATCH_SIZE {
+        let keypair = Keypair::new();
+        bench.fund(&keypair.pubkey(), 1_000_000_000).await;
+        let usdc_account = bench.create_token_account(&pool.usdc_mint, &keypair.pubkey()).await;
+        let ipt_account = bench.create_token_account(&pool.ipt_mint, &keypair.pubkey()).await;
+        bench
+            .mint_to(&pool.usdc_mint, &usdc_account, &mint_authority, 2 * USER_DEPOSIT)
+            .await;
+        users.push(User {
+            keypair,
+            usdc_account,
+            ipt_account,
+        });
+    }
+
+    // ---------------- User flows ----------------
+    for user in &users {
+        bench
+            .measure("user_deposit", &[user_deposit_ix(&pool, user, USER_DEPOSIT)], &[&user.keypair])
+            .await;
+    }
+
+    let migrating = &users[MAX_BATCH_SIZE];
+    let successor_ipt = bench
+        .create_token_account(&successor.ipt_mint, &migrating.keypair.pubkey())
+        .await;
+    let migrate_amount = bench.token_balance(&migrating.ipt_account).await / 2;
+    bench
+        .measure(
+            "migrate_position",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::MigratePosition {
+                    user: migrating.keypair.pubkey(),
+                    source_pool: pool.pool,
+                    destination_pool: successor.pool,
+                    protocol_config: pool.protocol_config,
+                    source_pool_authority: pool.pool,
+                    destination_pool_authority: successor.pool,
+                    source_user_ipt_account: migrating.ipt_account,
+                    destination_user_ipt_account: successor_ipt,
+                    source_usdc_reserve: pool.usdc_reserve,
+                    destination_usdc_reserve: successor.usdc_reserve,
+                    source_ipt_mint: pool.ipt_mint,
+                    destination_ipt_mint: successor.ipt_mint,
+                    destination_access_entry: None,
+                    source_position: None,
+                    destination_position: None,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::MigratePosition {
+                    net_ipt_amount: migrate_amount,
+                    min_ipt_out: 0,
+                }
+                .data(),
+            }],
+            &[&migrating.keypair],
+        )
+        .await;
+
+    let observed_at = bench.unix_timestamp().await;
+    bench
+        .measure(
+            "update_exchange_rate",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::UpdateExchangeRate {
+                    oracle: oracle.pubkey(),
+                    pool: pool.pool,
+                    nav_summary: None,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::UpdateExchangeRate {
+                    new_rate: 1_010_000,
+                    observed_at,
+                    accrued_yield: 0,
+                    evidence: None,
+                }
+                .data(),
+            }],
+            &[&oracle],
+        )
+        .await;
+
+    let first = &users[0];
+    let request_amount = bench.token_balance(&first.ipt_account).await / 4;
+    bench
+        .measure(
+            "user_withdrawal_request",
+            &[
+                spl_token::instruction::approve(
+                    &spl_token::ID,
+                    &first.ipt_account,
+                    &pool.pool,
+                    &first.keypair.pubkey(),
+                    &[],
+                    request_amount,
+                )
+                .unwrap(),
+                Instruction {
+                    program_id: ipt::ID,
+                    accounts: ipt::accounts::UserWithdrawalRequest {
+                        user: first.keypair.pubkey(),
+                        pool: pool.pool,
+                        protocol_config: pool.protocol_config,
+                        pool_authority: pool.pool,
+                        user_ipt_account: first.ipt_account,
+                        ipt_mint: pool.ipt_mint,
+                        access_entry: None,
+                    }
+                    .to_account_metas(None),
+                    data: ipt::instruction::UserWithdrawalRequest {
+                        net_ipt_amount: request_amount,
+                        min_usdc_amount: 0,
+                    }
+                    .data(),
+                },
+            ],
+            &[&first.keypair],
+        )
+        .await;
+
+    bench
+        .measure(
+            "user_withdraw (instant)",
+            &[user_withdraw_ix(&pool, first, request_amount)],
+            &[&first.keypair],
+        )
+        .await;
+
+    // ---------------- Treasury flows ----------------
+    bench
+        .measure(
+            "admin_deposit_usdc",
+            &[admin_deposit_ix(&pool, &admin.pubkey(), &admin_usdc, USER_DEPOSIT)],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "fee_collector_withdraw",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::FeeCollectorWithdraw {
+                    fee_collector: fee_collector.pubkey(),
+                    pool: pool.pool,
+                    pool_authority: pool.pool,
+                    fee_collector_usdc_account: fee_collector_usdc,
+                    pool_usdc_reserve: pool.usdc_reserve,
+                    token_program: spl_token::ID,
+                    memo_program: None,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::FeeCollectorWithdraw {
+                    amount: 1,
+                    bucket: FeeBucket::Deposit,
+                    memo: None,
+                }
+                .data(),
+            }],
+            &[&fee_collector],
+        )
+        .await;
+
+    // ---------------- Dividends ----------------
+    // Single-holder tree: the root is the leaf and the proof is empty
+    let (distribution, _) = Pubkey::find_program_address(
+        &[
+            ipt::states::Distribution::SEED_PREFIX,
+            pool.pool.as_ref(),
+            0u64.to_le_bytes().as_ref(),
+        ],
+        &ipt::ID,
+    );
+    let (payout_vault, _) = Pubkey::find_program_address(
+        &[ipt::states::Distribution::VAULT_SEED_PREFIX, distribution.as_ref()],
+        &ipt::ID,
+    );
+    let holder = &users[0];
+    let dividend = USER_DEPOSIT / 100;
+    bench
+        .measure(
+            "create_distribution",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::CreateDistribution {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                    pool_authority: pool.pool,
+                    distribution,
+                    payout_vault,
+                    usdc_mint: pool.usdc_mint,
+                    admin_usdc_account: admin_usdc,
+                    token_program: spl_token::ID,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::CreateDistribution {
+                    merkle_root: ipt::utils::MerkleUtils::dividend_leaf(
+                        &distribution,
+                        &holder.keypair.pubkey(),
+                        dividend,
+                    ),
+                    total_amount: dividend,
+                    snapshot_slot: 0,
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    let (claim_receipt, _) = Pubkey::find_program_address(
+        &[
+            ipt::states::ClaimReceipt::SEED_PREFIX,
+            distribution.as_ref(),
+            holder.keypair.pubkey().as_ref(),
+        ],
+        &ipt::ID,
+    );
+    bench
+        .measure(
+            "claim_dividend",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::ClaimDividend {
+                    claimant: holder.keypair.pubkey(),
+                    pool: pool.pool,
+                    pool_authority: pool.pool,
+                    distribution,
+                    claim_receipt,
+                    payout_vault,
+                    claimant_usdc_account: holder.usdc_account,
+                    token_program: spl_token::ID,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::ClaimDividend {
+                    amount: dividend,
+                    proof: vec![],
+                }
+                .data(),
+            }],
+            &[&holder.keypair],
+        )
+        .await;
+
+    bench
+        .measure(
+            "admin_update_config",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::AdminUpdateConfig {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                    protocol_config: pool.protocol_config,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::AdminUpdateConfig {
+                    new_config: PoolConfig {
+                        max_buyback_usdc: 0,
+                        ..config.clone()
+                    },
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "check_heartbeat",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::CheckHeartbeat {
+                    caller: admin.pubkey(),
+                    pool: pool.pool,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::CheckHeartbeat {}.data(),
+            }],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "set_oracle_publisher",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::SetOraclePublisher {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::SetOraclePublisher {
+                    new_publisher: Pubkey::default(),
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "set_pause_flag",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::SetPauseFlag {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::SetPauseFlag {
+                    flag: PauseFlag::RateUpdates,
+                    paused: false,
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    let observed_at = bench.unix_timestamp().await;
+    bench
+        .measure(
+            "admin_update_rate_and_state",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::AdminUpdateRateAndState {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::AdminUpdateRateAndState {
+                    new_rate: 1_012_000,
+                    observed_at,
+                    pause_flags: 0,
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "set_max_total_supply",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::SetMaxTotalSupply {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::SetMaxTotalSupply {
+                    new_max_total_supply: 0,
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "expand_queue",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::ExpandQueue {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::ExpandQueue {
+                    new_max_queue_size: ipt::states::Pool::MAX_QUEUE_SIZE as u32 + 10,
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    // ---------------- Migration (timelocked admin mint) ----------------
+    let migration_amount = 10 * 10u64.pow(DECIMALS as u32);
+    let operation = Pubkey::find_program_address(
+        &[
+            ipt::states::TimelockOperation::SEED_PREFIX,
+            pool.pool.as_ref(),
+            0u64.to_le_bytes().as_ref(),
+        ],
+        &ipt::ID,
+    )
+    .0;
+
+    bench
+        .measure(
+            "begin_migration",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::BeginMigration {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::BeginMigration {
+                    mint_cap: migration_amount,
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "schedule_admin_action",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::ScheduleAdminAction {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                    timelock_operation: operation,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::ScheduleAdminAction {
+                    action: AdminAction::MintIpt {
+                        beneficiary: users[0].keypair.pubkey(),
+                        amount: migration_amount,
+                        reason: "legacy ledger import".to_string(),
+                    },
+                }
+                .data(),
+            }],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "admin_mint_ipt",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::AdminMintIpt {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                    timelock_operation: operation,
+                    pool_authority: pool.pool,
+                    beneficiary_ipt_account: users[0].ipt_account,
+                    ipt_mint: pool.ipt_mint,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::AdminMintIpt {}.data(),
+            }],
+            &[],
+        )
+        .await;
+
+    bench
+        .measure(
+            "end_migration",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::EndMigration {
+                    admin: admin.pubkey(),
+                    pool: pool.pool,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::EndMigration {}.data(),
+            }],
+            &[],
+        )
+        .await;
+
+    // Drain reserves so every withdrawal below lands in the queue
+    let reserves = bench.token_balance(&pool.usdc_reserve).await;
+    bench
+        .measure(
+            "admin_withdraw_usdc",
+            &[admin_withdraw_ix(&pool, &admin.pubkey(), &admin_usdc, reserves)],
+            &[],
+        )
+        .await;
+
+    // ---------------- Queue flows ----------------
+    for user in &users {
+        let balance = bench.token_balance(&user.ipt_account).await;
+        bench
+            .measure(
+                "user_withdraw (queued)",
+                &[user_withdraw_ix(&pool, user, balance)],
+                &[&user.keypair],
+            )
+            .await;
+    }
+
+    let last = users.last().unwrap();
+    bench
+        .measure(
+            "cancel_withdrawal_request",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::CancelWithdrawalRequest {
+                    user: last.keypair.pubkey(),
+                    pool: pool.pool,
+                    protocol_config: pool.protocol_config,
+                    user_ipt_account: last.ipt_account,
+                    ipt_mint: pool.ipt_mint,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::CancelWithdrawalRequest {}.data(),
+            }],
+            &[&last.keypair],
+        )
+        .await;
+    let balance = bench.token_balance(&last.ipt_account).await;
+    bench
+        .send(&[user_withdraw_ix(&pool, last, balance)], &[&last.keypair])
+        .await
+        .unwrap();
+
+    let first = &users[0];
+    let cancel_amount = queued_amount(bench, &pool, first).await / 2;
+    bench
+        .measure(
+            "shrink_withdrawal_request",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::ShrinkWithdrawalRequest {
+                    user: first.keypair.pubkey(),
+                    pool: pool.pool,
+                    protocol_config: pool.protocol_config,
+                    user_ipt_account: first.ipt_account,
+                    ipt_mint: pool.ipt_mint,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::ShrinkWithdrawalRequest { cancel_amount }.data(),
+            }],
+            &[&first.keypair],
+        )
+        .await;
+
+    // Refill reserves so every queued withdrawal can be paid
+    bench
+        .send(
+            &[admin_deposit_ix(&pool, &admin.pubkey(), &admin_usdc, 50 * USER_DEPOSIT)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    bench
+        .measure(
+            "protocol_pause (pause + resume)",
+            &[
+                protocol_pause_ix(&pool, &admin.pubkey(), true),
+                protocol_pause_ix(&pool, &admin.pubkey(), false),
+            ],
+            &[],
+        )
+        .await;
+
+    let executor = Keypair::new();
+    bench.fund(&executor.pubkey(), 1_000_000_000).await;
+
+    let single = vec![queued_amount(bench, &pool, &users[0]).await];
+    bench
+        .measure(
+            "batch_execute_withdraw x1",
+            &[batch_execute_ix(&pool, &executor.pubkey(), &[&users[0]], single)],
+            &[&executor],
+        )
+        .await;
+
+    let batch: Vec<&User> = users[1..].iter().collect();
+    let mut amounts = Vec::with_capacity(batch.len());
+    for user in &batch {
+        amounts.push(queued_amount(bench, &pool, user).await);
+    }
+    bench
+        .measure(
+            &format!("batch_execute_withdraw x{}", MAX_BATCH_SIZE),
+            &[batch_execute_ix(&pool, &executor.pubkey(), &batch, amounts)],
+            &[&executor],
+        )
+        .await;
+
+    bench
+        .measure(
+            "crank_withdraw_queue (idle)",
+            &[crank_withdraw_queue_ix(&pool, &executor.pubkey())],
+            &[&executor],
+        )
+        .await;
+
+    bench.warp_clock(config.epoch_duration_secs).await;
+    bench
+        .measure(
+            "advance_epoch",
+            &[Instruction {
+                program_id: ipt::ID,
+                accounts: ipt::accounts::AdvanceEpoch {
+                    caller: executor.pubkey(),
+                    pool: pool.pool,
+                }
+                .to_account_metas(None),
+                data: ipt::instruction::AdvanceEpoch {}.data(),
+            }],
+            &[&executor],
+        )
+        .await;
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let record = std::env::args().any(|arg| arg == "--record");
+
+    // Without a baseline every regression would pass unnoticed
+    let baseline = match read_baseline() {
+        Ok(baseline) => baseline,
+        Err(_) if record => BTreeMap::new(),
+        Err(err) => {
+            eprintln!(
+                "Cannot read {}: {} (record it with `cargo run -p ipt-bench -- --record`)",
+                baseline_path().display(),
+                err
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut bench = BenchContext::start().await;
+    run(&mut bench).await;
+
+    let mut failed = false;
+
+    println!("{:<32} {:>10} {:>10} {:>10}", "instruction", "units", "baseline", "ceiling");
+    for (label, &units) in &bench.results {
+        let ceiling = ceiling(label);
+        let recorded = baseline.get(label).copied();
+
+        let mut status = "";
+        if units > ceiling {
+            status = "  EXCEEDS CEILING";
+            failed = true;
+        } else if !record {
+            match recorded {
+                Some(recorded) if units * 100 > recorded * (100 + TOLERANCE_PCT) => {
+                    status = "  REGRESSED";
+                    failed = true;
+                }
+                Some(_) => {}
+                None => {
+                    status = "  NOT IN BASELINE";
+                    failed = true;
+                }
+            }
+        }
+
+        println!(
+            "{:<32} {:>10} {:>10} {:>10}{}",
+            label,
+            units,
+            recorded.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string()),
+            ceiling,
+            status
+        );
+    }
+
+    if record {
+        write_baseline(&bench.results);
+        println!("Baseline written to {}", baseline_path().display());
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -111,4 +111,313 @@ pub enum PoolError {
 
     #[msg("Invalid authority pubkey - cannot be default pubkey")]
     InvalidAuthority,
-}
\ No newline at end of file
+
+    #[msg("Unauthorized: Only protocol admin can perform this action")]
+    UnauthorizedProtocolAdmin,
+
+    #[msg("Fee rate exceeds the protocol fee bound")]
+    FeeExceedsProtocolBound,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Invalid mint decimals")]
+    InvalidDecimals,
+
+    #[msg("Pool is already initialized")]
+    PoolAlreadyInitialized,
+
+    #[msg("Max total supply cannot be below current IPT supply")]
+    MaxTotalSupplyBelowCurrentSupply,
+
+    #[msg("Queue size must increase and stay within the maximum queue capacity")]
+    InvalidQueueSize,
+
+    #[msg("Pool is in migration")]
+    PoolInMigration,
+
+    #[msg("Pool is not in migration")]
+    PoolNotInMigration,
+
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Timelock operation does not match this instruction")]
+    TimelockActionMismatch,
+
+    #[msg("Timelock delay cannot be decreased")]
+    TimelockDelayDecreased,
+
+    #[msg("Migration mint cap exceeded")]
+    MigrationMintCapExceeded,
+
+    #[msg("Queue processing is paused")]
+    QueuePaused,
+
+    #[msg("Exchange rate updates are paused")]
+    RateUpdatesPaused,
+
+    #[msg("Invalid rate bounds - floor must not exceed ceiling and must contain the exchange rate")]
+    InvalidRateBounds,
+
+    #[msg("Exchange rate outside the pool's configured floor and ceiling")]
+    RateOutOfBounds,
+
+    #[msg("Signed rate updates are not enabled for this pool")]
+    OraclePublisherNotSet,
+
+    #[msg("Missing or malformed ed25519 signature from the oracle publisher")]
+    InvalidOracleSignature,
+
+    #[msg("Signed rate payload is stale, from the future, or replays an old nonce")]
+    StaleOraclePayload,
+
+    #[msg("Exchange rate is stale - the oracle has missed its heartbeat")]
+    OracleStale,
+
+    #[msg("Rate observation is older than the last applied one or in the future")]
+    OutOfOrderRateUpdate,
+
+    #[msg("Redemption gate reached for this epoch")]
+    RedemptionGateExceeded,
+
+    #[msg("Merkle proof does not match the distribution root")]
+    InvalidMerkleProof,
+
+    #[msg("Claims would exceed the distribution's funded amount")]
+    DistributionOverclaimed,
+
+    #[msg("Operation requires a rebasing pool")]
+    NotRebasingPool,
+
+    #[msg("Exchange rate is not above par - nothing to rebase")]
+    NothingToRebase,
+
+    #[msg("Distribution kind does not match this claim")]
+    WrongDistributionKind,
+
+    #[msg("Current epoch has not reached its duration")]
+    EpochNotDue,
+
+    #[msg("Unauthorized: Only treasurer can perform this action")]
+    UnauthorizedTreasurer,
+
+    #[msg("Instruction must not be composed with other instructions in this transaction")]
+    TransactionShapeRejected,
+
+    #[msg("No rate feed configured for this pool")]
+    RateFeedNotSet,
+
+    #[msg("Rate feed account is not a verified price update for the configured feed")]
+    InvalidRateFeed,
+
+    #[msg("Withdrawals of this size must be committed and revealed")]
+    WithdrawalCommitmentRequired,
+
+    #[msg("Revealed withdrawal does not match the commitment")]
+    CommitmentMismatch,
+
+    #[msg("Commitment cannot be revealed yet")]
+    RevealTooEarly,
+
+    #[msg("Unauthorized: Only the compliance authority can approve withdrawals")]
+    UnauthorizedComplianceAuthority,
+
+    #[msg("Withdrawal request does not need approval")]
+    ApprovalNotRequired,
+
+    #[msg("Withdrawal request expired before it was approved")]
+    ApprovalExpired,
+
+    #[msg("Withdrawals of this size must also be signed by the withdrawal co-signer")]
+    CosignerRequired,
+
+    #[msg("Buyback exceeds the per-call limit or pays more than NAV")]
+    InvalidBuyback,
+
+    #[msg("Distribution is still open for claims")]
+    DistributionStillOpen,
+
+    #[msg("Only the committer may close a commitment before it is abandoned")]
+    CommitmentStillActive,
+
+    #[msg("Accumulated fees are below the sweep threshold")]
+    FeeSweepBelowThreshold,
+
+    #[msg("Fee recipients must be distinct with non-zero shares totalling at most 100%")]
+    InvalidFeeRecipients,
+
+    #[msg("Fee recipient accounts do not match the configured split")]
+    FeeRecipientMismatch,
+
+    #[msg("Pool name or metadata URI is too long")]
+    MetadataTooLong,
+
+    #[msg("Deposit would take pool NAV above the TVL cap")]
+    TvlCapExceeded,
+
+    #[msg("Amount exceeds the unspent promotion budget")]
+    InsufficientPromoBudget,
+
+    #[msg("Deposit, withdrawal and management fees together exceed the combined cap")]
+    CombinedFeesTooHigh,
+
+    #[msg("Role segregation requires the fee collector to differ from the admin")]
+    RoleSegregationViolated,
+
+    #[msg("Queueing features need a non-zero max_queue_size")]
+    QueueSizeRequired,
+
+    #[msg("Rate floor is above the rate ceiling")]
+    RateBoundsInverted,
+
+    #[msg("Memo reference is too long")]
+    MemoTooLong,
+
+    #[msg("A memo was supplied without the SPL Memo program account")]
+    MemoProgramMissing,
+
+    #[msg("A depositor cannot refer their own deposit")]
+    SelfReferral,
+
+    #[msg("No referral earnings to claim")]
+    NoReferralEarnings,
+
+    #[msg("Too many addresses for one access list update")]
+    AccessListBatchTooLarge,
+
+    #[msg("Access list entry does not match the address or pool")]
+    InvalidAccessListEntry,
+
+    #[msg("Address is on the pool's blocklist")]
+    AddressBlocked,
+
+    #[msg("Address is not on the pool's allowlist")]
+    AddressNotAllowed,
+
+    #[msg("Depositor's access list entry account is required")]
+    AccessEntryRequired,
+
+    #[msg("Only the pool's recovery authority can do this")]
+    UnauthorizedRecoveryAuthority,
+
+    #[msg("An emergency recovery is already pending")]
+    RecoveryAlreadyPending,
+
+    #[msg("No emergency recovery is pending")]
+    NoPendingRecovery,
+
+    #[msg("Emergency recovery delay has not elapsed")]
+    RecoveryDelayActive,
+
+    #[msg("Oracle has not been silent long enough to trip the dead-man switch")]
+    DeadManSwitchNotDue,
+
+    #[msg("Pool is not frozen")]
+    PoolNotFrozen,
+
+    #[msg("Position has not been dormant for the configured period")]
+    PositionNotDormant,
+
+    #[msg("Position is already flagged dormant")]
+    PositionAlreadyFlagged,
+
+    #[msg("Holder has not delegated the escheated IPT to the pool")]
+    EscheatmentNotAuthorized,
+
+    #[msg("Rate updated too recently; wait for the minimum update interval")]
+    RateUpdateTooFrequent,
+
+    #[msg("Rate evidence needs a report hash and a URI of at most 200 bytes")]
+    InvalidRateEvidence,
+
+    #[msg("Statement period must be a YYYYMM month")]
+    InvalidStatementPeriod,
+
+    #[msg("Pool already holds the maximum number of reserve assets")]
+    ReserveAssetLimitReached,
+    #[msg("Reserve asset price is missing or stale")]
+    StaleAssetPrice,
+    #[msg("Deposit would take the reserve asset past its target weight")]
+    AssetWeightExceeded,
+    #[msg("In-kind redemption is unavailable while exits are gated, queued, or under review")]
+    InKindRedemptionUnavailable,
+    #[msg("Reserve asset weight is within its drift tolerance")]
+    ReserveWithinTolerance,
+    #[msg("Pool is winding down")]
+    PoolWindingDown,
+    #[msg("The wind-down waterfall has not reached this payout class")]
+    WindDownStageInactive,
+    #[msg("Claims in the current wind-down class are still outstanding")]
+    WindDownStageIncomplete,
+    #[msg("Pool is not winding down")]
+    PoolNotWindingDown,
+    #[msg("No escrowed payout to claim")]
+    NoEscrowedPayout,
+    #[msg("Pull-mode pools pay batch withdrawals into the user's payout escrow")]
+    PayoutEscrowRequired,
+    #[msg("This feature is switched off program-wide")]
+    FeatureDisabled,
+    #[msg("Pool account predates this program's layout; run migrate_pool")]
+    PoolLayoutOutdated,
+    #[msg("Pool account is already on the current layout")]
+    PoolLayoutCurrent,
+    #[msg("No migration exists from this pool layout version")]
+    UnsupportedPoolLayout,
+    #[msg("Unauthorized: Only the admin or guardian can pause queue processing")]
+    UnauthorizedGuardian,
+    #[msg("Cost-basis ledger has no room for another lot")]
+    CostBasisLedgerFull,
+    #[msg("Amount exceeds the liquidity earmarked for the queue")]
+    InsufficientQueueReserve,
+    #[msg("Deposit exceeds the cap for the depositor's investor tier")]
+    TierDepositCapExceeded,
+    #[msg("Daily limit on queued withdrawal requests reached")]
+    QueueEntryLimitReached,
+    #[msg("Pass the user's position so their queued requests can be counted")]
+    PositionRequired,
+    #[msg("Pool state cannot be changed this way from its current state")]
+    InvalidPoolStateTransition,
+    #[msg("No deposit within the refund window to refund")]
+    RefundWindowExpired,
+    #[msg("Admin authority changes go through propose_admin and accept_admin")]
+    AdminTransferRequiresAcceptance,
+    #[msg("Idle-reserve sweeping is not configured for this pool")]
+    SweepNotConfigured,
+    #[msg("No unreserved USDC above the liquidity buffer to sweep")]
+    NothingToSweep,
+    #[msg("Sweep wrapper moved different amounts than requested")]
+    SweepWrapperMismatch,
+    #[msg("Unwrap all swept USDC before changing the sweep wrapper")]
+    SweptUsdcOutstanding,
+    #[msg("A routed deposit needs between one and MAX_LEGS legs")]
+    InvalidRouteLegCount,
+    #[msg("Route weights must each be non-zero and sum to 10,000 bps")]
+    InvalidRouteWeights,
+    #[msg("Every leg of a routed deposit must be signed by the routing user")]
+    RouteUserMismatch,
+    #[msg("Treasurer changes go through a timelocked set_treasurer")]
+    TreasurerChangeRequiresTimelock,
+    #[msg("Co-signer and threshold changes go through a timelocked set_withdrawal_cosigner")]
+    CosignerChangeRequiresTimelock,
+    #[msg("Pass the referral, partner and cost-basis accounts the refunded deposit touched")]
+    RefundAccountsMissing,
+    #[msg("Pool id is reserved for pools created before pool ids")]
+    ReservedPoolId,
+    #[msg("Escheatment must go to the pool's configured custody account")]
+    EscheatCustodyMismatch,
+    #[msg("Escheatment custody changes go through a timelocked set_escheat_custody")]
+    EscheatCustodyChangeRequiresTimelock,
+    #[msg("Pass the user's position so deposits can be counted against their tier cap")]
+    TierPositionRequired,
+    #[msg("Queue capacity can grow by at most MAX_QUEUE_GROWTH entries per expand_queue")]
+    QueueGrowthTooLarge,
+    #[msg("Oracle authority changes go through propose_oracle_authority and accept_oracle_authority")]
+    OracleChangeRequiresRotation,
+    #[msg("Recovery, guardian and compliance authority changes go through a timelocked set_role_authority")]
+    RoleChangeRequiresTimelock,
+    #[msg("Sweep wrapper changes go through a timelocked set_sweep_wrapper")]
+    SweepWrapperChangeRequiresTimelock,
+    #[msg("This setting has its own instruction; admin_update_config cannot change it")]
+    ConfigFieldHasSetter,
+}
@@ -1,10 +1,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::states::{
+    AccessStatus, AdminAction, AuthorityRole, FeeBucket, InvestorTier, LotMethod, PayoutClass,
+    PoolCategory, PoolState, RealizedLot, RejectedOperation, RejectionReason,
+    ReserveMovementReason, RiskTier,
+};
 use anchor_lang::prelude::*;
 
 #[event]
 pub struct PoolInitialized {
     pub pool: Pubkey,
+    pub pool_id: u64,
     pub admin_authority: Pubkey,
     pub oracle_authority: Pubkey,
     pub fee_collector: Pubkey,
@@ -24,6 +30,8 @@ pub struct UserDepositExecuted {
     pub exchange_rate: u64,
     pub new_ipt_supply: u64,
     pub new_reserves: u64,
+    /// Sequential deposit certificate number within the pool
+    pub certificate_number: u64,
     pub timestamp: i64,
 }
 
@@ -34,27 +42,42 @@ pub struct UserWithdrawalExecuted {
     pub ipt_amount: u64,
     pub usdc_amount: u64,
     pub withdrawal_fee: u64,
+    pub exit_adjustment: u64,
     pub exchange_rate: u64,
     pub new_ipt_supply: u64,
     pub new_reserves: u64,
+    /// User token balances around execution, for reconciliation
+    pub ipt_balance_before: u64,
+    pub ipt_balance_after: u64,
+    pub usdc_balance_before: u64,
+    pub usdc_balance_after: u64,
+    /// User's average entry rate from their position (0 = not passed)
+    pub avg_entry_rate: u64,
+    /// usdc_amount less the withdrawn IPT's value at avg_entry_rate
+    pub realized_pnl: i64,
+    /// Cost-basis lots consumed, if the user keeps a cost-basis ledger
+    pub realized_lots: Vec<RealizedLot>,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct AdminDepositExecuted {
-    pub admin: Pubkey,
+    pub treasurer: Pubkey,
     pub pool: Pubkey,
     pub amount: u64,
     pub new_reserves: u64,
+    pub reason: ReserveMovementReason,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct AdminWithdrawExecuted {
-    pub admin: Pubkey,
+    pub treasurer: Pubkey,
     pub pool: Pubkey,
     pub amount: u64,
+    pub cosigner: Option<Pubkey>,
     pub remaining_reserves: u64,
+    pub reason: ReserveMovementReason,
     pub timestamp: i64,
 }
 
@@ -62,8 +85,12 @@ pub struct AdminWithdrawExecuted {
 pub struct FeeCollectorWithdrawExecuted {
     pub fee_collector: Pubkey,
     pub pool: Pubkey,
+    pub bucket: FeeBucket,
     pub amount: u64,
     pub remaining_accumulated_fees: u64,
+    pub remaining_deposit_fees: u64,
+    pub remaining_withdrawal_fees: u64,
+    pub remaining_management_fees: u64,
     pub timestamp: i64,
 }
 
@@ -73,6 +100,9 @@ pub struct ExchangeRateUpdated {
     pub pool: Pubkey,
     pub old_rate: u64,
     pub new_rate: u64,
+    pub observed_at: i64,
+    /// Pending yield posted with the rate (0 = none reported)
+    pub accrued_yield: u64,
     pub timestamp: i64,
 }
 
@@ -103,6 +133,7 @@ pub struct AddedToQueue {
     pub user: Pubkey,
     pub amount: u64,
     pub position: u32,
+    pub earliest_execution: i64,
 }
 
 #[event]
@@ -111,6 +142,14 @@ pub struct WithdrawExecuted {
     pub ipt_amount: u64,
     pub usdc_amount: u64,
     pub withdrawal_fee: u64,
+    pub exit_adjustment: u64,
+    /// IPT left queued when the per-user batch share capped this payout
+    pub remaining_ipt: u64,
+    /// User token balances around execution, for reconciliation
+    pub ipt_balance_before: u64,
+    pub ipt_balance_after: u64,
+    pub usdc_balance_before: u64,
+    pub usdc_balance_after: u64,
     pub batch_index: u8,
 }
 
@@ -131,13 +170,896 @@ pub struct WithdrawSkipped {
     pub user: Pubkey,
     pub ipt_amount: u64,
     pub reason: String,
+    /// Consecutive skips for this reason
+    pub attempts: u8,
     pub batch_index: u8,
 }
 
+#[event]
+pub struct WithdrawFailed {
+    pub user: Pubkey,
+    pub ipt_amount: u64,
+    pub reason: String,
+    pub attempts: u8,
+}
+
 #[event]
 pub struct WithdrawalCancelled {
     pub user: Pubkey,
     pub ipt_amount: u64,
     pub position: u32,
+    /// IPT burned as the cancellation fee
+    pub cancellation_fee: u64,
+    /// USDC value of the fee, credited to accumulated fees
+    pub cancellation_fee_usdc: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolConfigInitialized {
+    pub protocol_admin: Pubkey,
+    pub max_deposit_fee_bps: u16,
+    pub max_withdrawal_fee_bps: u16,
+    pub max_management_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub protocol_admin: Pubkey,
+    pub new_protocol_admin: Pubkey,
+    pub max_deposit_fee_bps: u16,
+    pub max_withdrawal_fee_bps: u16,
+    pub max_management_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPauseUpdated {
+    pub protocol_admin: Pubkey,
+    pub is_paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxTotalSupplyUpdated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub old_max_total_supply: u64,
+    pub new_max_total_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QueueExpanded {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub old_max_queue_size: u32,
+    pub new_max_queue_size: u32,
+    pub queue_capacity: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminActionScheduled {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub id: u64,
+    pub action: AdminAction,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminActionCancelled {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationStarted {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub mint_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationEnded {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub total_minted: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationMintExecuted {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub reason: String,
+    pub new_ipt_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintAuthorityTransferred {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub ipt_mint: Pubkey,
+    pub successor_program: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionMigrated {
+    pub user: Pubkey,
+    pub source_pool: Pubkey,
+    pub destination_pool: Pubkey,
+    pub ipt_burned: u64,
+    pub usdc_moved: u64,
+    pub ipt_minted: u64,
+    pub source_exchange_rate: u64,
+    pub destination_exchange_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolCloned {
+    pub admin: Pubkey,
+    pub source_pool: Pubkey,
+    pub pool: Pubkey,
+    pub pool_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub old_flags: u8,
+    pub new_flags: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionsClosed {
+    pub pool: Pubkey,
+    pub total_ipt_supply: u64,
+    pub max_total_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OraclePublisherUpdated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub old_publisher: Pubkey,
+    pub new_publisher: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleStale {
+    pub pool: Pubkey,
+    pub caller: Pubkey,
+    pub last_rate_update: i64,
+    pub max_heartbeat_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionCreated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub distribution: Pubkey,
+    pub id: u64,
+    pub total_amount: u64,
+    pub snapshot_slot: u64,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DividendClaimed {
+    pub claimant: Pubkey,
+    pub pool: Pubkey,
+    pub distribution: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RebaseExecuted {
+    pub oracle: Pubkey,
+    pub pool: Pubkey,
+    pub distribution: Pubkey,
+    pub id: u64,
+    pub old_rate: u64,
+    pub minted_supply: u64,
+    pub new_ipt_supply: u64,
+    pub snapshot_slot: u64,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RebaseClaimed {
+    pub claimant: Pubkey,
+    pub pool: Pubkey,
+    pub distribution: Pubkey,
+    pub ipt_amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpochClosed {
+    pub pool: Pubkey,
+    pub caller: Pubkey,
+    pub epoch: u64,
+    pub nav: u64,
+    pub management_fee: u64,
+    pub started_at: i64,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct PoolResized {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub old_queue_capacity: u32,
+    pub queue_capacity: u32,
+    pub account_size: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalShrunk {
+    pub user: Pubkey,
+    pub cancelled_amount: u64,
+    pub remaining_amount: u64,
+    pub position: u32,
+    pub cancellation_fee: u64,
+    pub cancellation_fee_usdc: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RateFeedUpdated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub old_feed_id: [u8; 32],
+    pub new_feed_id: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalCommitted {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub commitment: [u8; 32],
+    pub revealable_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalRevealed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub ipt_amount: u64,
+    pub min_usdc_amount: u64,
+    pub committed_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalApproved {
+    pub compliance_authority: Pubkey,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub ipt_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuybackExecuted {
+    pub admin: Pubkey,
+    pub seller: Pubkey,
+    pub pool: Pubkey,
+    pub ipt_amount: u64,
+    pub usdc_amount: u64,
+    pub bucket: FeeBucket,
+    pub total_buyback_ipt: u64,
+    pub total_buyback_usdc: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IptDonationBurned {
+    pub holder: Pubkey,
+    pub pool: Pubkey,
+    pub ipt_amount: u64,
+    pub new_ipt_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionClosed {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub distribution: Pubkey,
+    pub id: u64,
+    pub unclaimed_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesSwept {
+    pub cranker: Pubkey,
+    pub pool: Pubkey,
+    pub fee_collector_usdc_account: Pubkey,
+    pub amount: u64,
+    pub deposit_fees: u64,
+    pub withdrawal_fees: u64,
+    pub management_fees: u64,
+    /// Paid to each configured fee recipient, in config order; the fee
+    /// collector received the rest of `amount`
+    pub recipient_amounts: Vec<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleRotationProposed {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub current_oracle: Pubkey,
+    pub proposed_oracle: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleRotated {
+    pub pool: Pubkey,
+    pub old_oracle: Pubkey,
+    pub new_oracle: Pubkey,
+    pub overlap_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolMetadataUpdated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub name: String,
+    pub description_uri: String,
+    pub logo_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolRegistryEntryUpdated {
+    pub protocol_admin: Pubkey,
+    pub pool: Pubkey,
+    pub category: PoolCategory,
+    pub risk_tier: RiskTier,
+    pub tags: u32,
+    pub verified: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CapUpdated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub old_tvl_cap: u64,
+    pub new_tvl_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PromotionApplied {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub fee_waived: u64,
+    pub bonus_ipt: u64,
+    /// Promotion budget spent on the bonus IPT
+    pub bonus_usdc: u64,
+    pub remaining_budget: u64,
+    pub timestamp: i64,
+}
+
+/// Logged just before a user flow fails a soft business check, so turned
+/// away demand shows up in the failed transaction's logs
+#[event]
+pub struct OperationRejected {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub operation: RejectedOperation,
+    pub reason: RejectionReason,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRegistered {
+    pub referrer: Pubkey,
+    pub pool: Pubkey,
+    pub referral: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralAccrued {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub deposit_fee: u64,
+    pub amount: u64,
+    pub claimable: u64,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct ReferralEarningsClaimed {
+    pub referrer: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub total_earned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartnerRegistered {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub partner_id: u64,
+    pub partner_account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartnerDepositAttributed {
+    pub partner_id: u64,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub usdc_amount: u64,
+    pub deposit_fee: u64,
+    pub aum_contributed: u64,
+    pub fees_generated: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccessListUpdated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    /// New status of every address, or None when they were removed
+    pub status: Option<AccessStatus>,
+    pub addresses: Vec<Pubkey>,
+    pub blocked_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyRecoveryInitiated {
+    pub recovery_authority: Pubkey,
+    pub pool: Pubkey,
+    pub current_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub new_oracle: Pubkey,
+    pub new_fee_collector: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyRecoveryCancelled {
+    pub cancelled_by: Pubkey,
+    pub pool: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyRecoveryExecuted {
+    pub recovery_authority: Pubkey,
+    pub pool: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub old_oracle: Pubkey,
+    pub new_oracle: Pubkey,
+    pub old_fee_collector: Pubkey,
+    pub new_fee_collector: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeadManSwitchTripped {
+    pub pool: Pubkey,
+    pub caller: Pubkey,
+    pub last_rate_update: i64,
+    pub dead_man_switch_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolUnfrozen {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionFlaggedDormant {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub caller: Pubkey,
+    pub last_activity_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionActivityRecorded {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub was_flagged: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionEscheated {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub amount: u64,
+    pub custody: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RateEvidenceAttached {
+    pub oracle: Pubkey,
+    pub pool: Pubkey,
+    pub rate: u64,
+    pub report_hash: [u8; 32],
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StatementCommitted {
+    pub committed_by: Pubkey,
+    pub pool: Pubkey,
+    pub statement: Pubkey,
+    pub period: u32,
+    pub merkle_root: [u8; 32],
+    pub holder_count: u32,
+    pub total_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveAssetAdded {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub target_weight_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveAssetWeightUpdated {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub old_target_weight_bps: u16,
+    pub new_target_weight_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveAssetPriceUpdated {
+    pub oracle: Pubkey,
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub old_price: u64,
+    pub new_price: u64,
+    pub asset_value: u64,
+    pub asset_reserves_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveAssetDeposited {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub asset_amount: u64,
+    pub usdc_value: u64,
+    pub ipt_amount: u64,
+    /// Deposit fee in USDC value, retained in the asset reserve
+    pub deposit_fee: u64,
+    pub price: u64,
+    pub exchange_rate: u64,
+    /// Sequential deposit certificate number within the pool
+    pub certificate_number: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveAssetWithdrawn {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub ipt_amount: u64,
+    pub asset_amount: u64,
+    pub usdc_value: u64,
+    /// Withdrawal fee in USDC value, retained in the asset reserve
+    pub withdrawal_fee: u64,
+    pub price: u64,
+    pub exchange_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveDriftExceeded {
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub target_weight_bps: u16,
+    pub actual_weight_bps: u16,
+    pub tolerance_bps: u16,
+    pub asset_value: u64,
+    pub total_reserve_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WindDownStarted {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub waterfall: Vec<PayoutClass>,
+    pub exchange_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WindDownStageAdvanced {
+    pub pool: Pubkey,
+    pub settled: PayoutClass,
+    /// None once every class has been paid
+    pub next: Option<PayoutClass>,
+    pub stage: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutEscrowed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub escrow: Pubkey,
+    pub usdc_amount: u64,
+    pub batch_index: u8,
+}
+
+#[event]
+pub struct EscrowedPayoutClaimed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub usdc_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeatureFlagsUpdated {
+    pub protocol_admin: Pubkey,
+    pub old_disabled_features: u32,
+    pub new_disabled_features: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolMigrated {
+    pub protocol_admin: Pubkey,
+    pub pool: Pubkey,
+    pub old_layout_version: u16,
+    pub new_layout_version: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QueueDepthAlert {
+    pub pool: Pubkey,
+    /// true when a threshold was crossed, false once the queue is back under
+    pub raised: bool,
+    pub queue_depth: u32,
+    pub queued_usdc: u64,
+    pub depth_threshold: u32,
+    pub usdc_threshold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CostBasisLedgerConfigured {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub method: LotMethod,
+    pub open_lots: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShareRegisterCommitted {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub commitment: Pubkey,
+    pub cutoff_slot: u64,
+    pub register_hash: [u8; 32],
+    pub holder_count: u32,
+    pub total_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InvestorTierSet {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub tier: InvestorTier,
+    pub addresses: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolStateChanged {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub old_state: PoolState,
+    pub new_state: PoolState,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositRefunded {
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    /// Hash of the incident report the refund resolves
+    pub incident_hash: [u8; 32],
+    pub ipt_burned: u64,
+    pub usdc_refunded: u64,
+    /// Deposit fee taken back out of the pool's fee balance
+    pub fee_reversed: u64,
+    /// Referrer's share of the fee taken back, and the part of it they had
+    /// already claimed, which was kept from the refund
+    pub referral_fee_reversed: u64,
+    pub referral_fee_withheld: u64,
+    pub deposited_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    /// Proposed admin, or the default key when a proposal is withdrawn
+    pub proposed_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub pool: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyPaused {
+    /// Admin or guardian that halted the pool
+    pub authority: Pubkey,
+    pub pool: Pubkey,
+    pub old_flags: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IdleReserveSwept {
+    pub treasurer: Pubkey,
+    pub pool: Pubkey,
+    pub usdc_amount: u64,
+    pub wrapped_received: u64,
+    /// Swept principal outstanding after this sweep
+    pub swept_usdc: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SweptReserveUnwrapped {
+    pub treasurer: Pubkey,
+    pub pool: Pubkey,
+    pub wrapped_amount: u64,
+    pub usdc_received: u64,
+    /// Swept principal the unwrapped tokens stood for; the rest is yield
+    pub principal: u64,
+    pub yield_usdc: u64,
+    pub swept_usdc: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositRouted {
+    pub user: Pubkey,
+    /// Pools deposited into, in leg order
+    pub pools: Vec<Pubkey>,
+    /// Net USDC each leg deposited
+    pub net_usdc_amounts: Vec<u64>,
+    /// USDC paid across every leg, deposit fees included
+    pub usdc_paid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasurerChanged {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub old_treasurer: Pubkey,
+    pub new_treasurer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalCosignerChanged {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub old_cosigner: Pubkey,
+    pub new_cosigner: Pubkey,
+    pub old_threshold: u64,
+    pub new_threshold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscheatCustodyChanged {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub old_custody: Pubkey,
+    pub new_custody: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleAuthorityChanged {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub role: AuthorityRole,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SweepWrapperChanged {
+    pub admin: Pubkey,
+    pub pool: Pubkey,
+    pub operation_id: u64,
+    pub old_program: Pubkey,
+    pub old_mint: Pubkey,
+    pub new_program: Pubkey,
+    pub new_mint: Pubkey,
+    pub timestamp: i64,
+}
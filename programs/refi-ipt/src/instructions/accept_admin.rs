@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// Proposed admin key, proving it can sign before taking over
+    pub new_admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.pending_admin != Pubkey::default()
+            && new_admin.key() == pool.pending_admin @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let old_admin = pool.config.admin_authority;
+    let new_admin = ctx.accounts.new_admin.key();
+
+    // Role segregation and the recovery key still hold under the new admin
+    let mut new_config = pool.config.clone();
+    new_config.admin_authority = new_admin;
+    ValidationUtils::validate_pool_config(&new_config, pool.queue_capacity)?;
+
+    pool.config = new_config;
+    pool.pending_admin = Pubkey::default();
+
+    emit!(AdminTransferred {
+        pool: pool.key(),
+        old_admin,
+        new_admin,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Admin authority transferred from {} to {}", old_admin, new_admin);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptOracleAuthority<'info> {
+    /// Proposed oracle key, proving it can sign before taking over
+    pub new_oracle: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.pending_oracle_authority != Pubkey::default()
+            && new_oracle.key() == pool.pending_oracle_authority @ PoolError::UnauthorizedOracle,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<AcceptOracleAuthority>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let old_oracle = pool.config.oracle_authority;
+    let new_oracle = ctx.accounts.new_oracle.key();
+    let overlap_ends_at = clock
+        .unix_timestamp
+        .checked_add(pool.config.oracle_overlap_secs)
+        .ok_or(PoolError::MathematicalOverflow)?;
+
+    // The old key keeps publishing until the overlap ends, so rates never gap
+    pool.config.oracle_authority = new_oracle;
+    pool.previous_oracle_authority = old_oracle;
+    pool.oracle_overlap_ends_at = overlap_ends_at;
+    pool.pending_oracle_authority = Pubkey::default();
+
+    emit!(OracleRotated {
+        pool: pool.key(),
+        old_oracle,
+        new_oracle,
+        overlap_ends_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Oracle authority rotated from {} to {} (old key valid until {})",
+        old_oracle,
+        new_oracle,
+        overlap_ends_at
+    );
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::CalculationUtils;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct AddReserveAsset<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        constraint = pool_authority.key() == pool.pool_authority @ PoolError::InvalidAuthority
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Mint of the asset to hold; USDC and IPT are already accounted for
+    #[account(
+        constraint = asset_mint.key() != pool.usdc_mint
+            && asset_mint.key() != pool.ipt_mint @ PoolError::InvalidConfigParameter
+    )]
+    pub asset_mint: Account<'info, Mint>,
+
+    /// The asset's pricing and weight record
+    #[account(
+        init,
+        payer = admin,
+        space = ReserveAsset::LEN,
+        seeds = [
+            ReserveAsset::SEED_PREFIX,
+            pool.key().as_ref(),
+            asset_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub reserve_asset: Account<'info, ReserveAsset>,
+
+    /// Reserve token account for the asset
+    #[account(
+        init,
+        payer = admin,
+        token::mint = asset_mint,
+        token::authority = pool_authority,
+        seeds = [
+            ReserveAsset::VAULT_SEED_PREFIX,
+            pool.key().as_ref(),
+            asset_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddReserveAsset>, target_weight_bps: u16) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = &mut ctx.accounts.pool;
+
+    require!(
+        pool.reserve_asset_count < ReserveAsset::MAX_ASSETS,
+        PoolError::ReserveAssetLimitReached
+    );
+    require!(target_weight_bps <= 10_000, PoolError::InvalidConfigParameter);
+    require!(
+        ctx.accounts.asset_mint.decimals <= CalculationUtils::MAX_DECIMALS,
+        PoolError::InvalidDecimals
+    );
+
+    // Unpriced until the oracle posts a first price
+    let asset = &mut ctx.accounts.reserve_asset;
+    asset.pool = pool.key();
+    asset.mint = ctx.accounts.asset_mint.key();
+    asset.vault = ctx.accounts.asset_vault.key();
+    asset.decimals = ctx.accounts.asset_mint.decimals;
+    asset.price = 0;
+    asset.price_updated_at = 0;
+    asset.balance = 0;
+    asset.value = 0;
+    asset.target_weight_bps = target_weight_bps;
+    asset.bump = ctx.bumps.reserve_asset;
+
+    pool.reserve_asset_count += 1;
+
+    emit!(ReserveAssetAdded {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        mint: asset.mint,
+        vault: asset.vault,
+        target_weight_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Reserve asset {} added with target weight {} bps",
+        asset.mint,
+        target_weight_bps
+    );
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::instructions::process_queue::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct AdminDepositAndProcess<'info> {
+    #[account(
+        mut,
+        constraint = treasurer.key() == batch.pool.config.treasurer_authority @ PoolError::UnauthorizedTreasurer
+    )]
+    pub treasurer: Signer<'info>,
+
+    /// Treasurer's USDC token account
+    #[account(
+        mut,
+        token::mint = batch.pool.usdc_mint,
+        token::authority = treasurer
+    )]
+    pub treasurer_usdc_account: Box<Account<'info, TokenAccount>>,
+
+    /// Queue batch the deposit funds; the treasurer may also be the executor
+    pub batch: BatchExecuteWithdraw<'info>,
+    // remaining_accounts: user account pairs for the head of the queue, as
+    // in batch_execute_withdraw; only the pairs the deposit covers are used
+}
+
+/// Top up reserves, then pay the queue head in the same instruction so no
+/// instant withdrawal can take the liquidity first
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AdminDepositAndProcess<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(amount > 0, PoolError::ZeroAmountNotAllowed);
+    require!(
+        ctx.accounts.treasurer_usdc_account.amount >= amount,
+        PoolError::InsufficientAccountBalance
+    );
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.batch.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.treasurer_usdc_account.to_account_info(),
+                to: ctx.accounts.batch.pool_usdc_reserve.to_account_info(),
+                authority: ctx.accounts.treasurer.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    ctx.accounts.batch.pool_usdc_reserve.reload()?;
+
+    let pool = &mut ctx.accounts.batch.pool;
+    pool.total_usdc_reserves = pool
+        .total_usdc_reserves
+        .checked_add(amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+
+    emit!(AdminDepositExecuted {
+        treasurer: ctx.accounts.treasurer.key(),
+        pool: pool.key(),
+        amount,
+        new_reserves: pool.total_usdc_reserves,
+        reason: ReserveMovementReason::Rebalance,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Entries the reserve now covers, as far as accounts were supplied
+    let covered = pool
+        .queued_entries_covered(ctx.accounts.batch.pool_usdc_reserve.amount)?
+        .min(ctx.remaining_accounts.len() / 2)
+        .min(MAX_BATCH_SIZE);
+    let amounts: Vec<u64> = pool
+        .queued()
+        .iter()
+        .take(covered)
+        .map(|pending| pending.amount)
+        .collect();
+
+    msg!(
+        "Treasurer deposited {} USDC, executing {} queued withdrawals",
+        amount,
+        amounts.len()
+    );
+
+    if amounts.is_empty() {
+        return Ok(());
+    }
+    let mut batch_ctx = Context::new(
+        ctx.program_id,
+        &mut ctx.accounts.batch,
+        &ctx.remaining_accounts[..amounts.len() * 2],
+        ctx.bumps.batch,
+    );
+    batch_execute_withdraw(&mut batch_ctx, amounts)
+}
@@ -3,33 +3,37 @@
 use crate::errors::PoolError;
 use crate::events::*;
 use crate::states::*;
+use crate::utils::TokenUtils;
 use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
 use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
 pub struct AdminDepositUsdc<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub treasurer: Signer<'info>,
 
     /// Pool state account
     #[account(
         mut,
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump,
-        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin
+        constraint = treasurer.key() == pool.config.treasurer_authority @ PoolError::UnauthorizedTreasurer,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
 
-    /// Admin's USDC token account
+    /// Treasurer's USDC token account
     #[account(
         mut,
         token::mint = pool.usdc_mint,
-        token::authority = admin
+        token::authority = treasurer
     )]
-    pub admin_usdc_account: Account<'info, TokenAccount>,
+    pub treasurer_usdc_account: Account<'info, TokenAccount>,
 
     /// Pool's USDC reserve
     #[account(
@@ -39,29 +43,37 @@ pub struct AdminDepositUsdc<'info> {
     pub pool_usdc_reserve: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
+
+    /// SPL Memo program, needed only when a memo is attached
+    pub memo_program: Option<Program<'info, Memo>>,
 }
 
-pub fn handler(ctx: Context<AdminDepositUsdc>, amount: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<AdminDepositUsdc>,
+    amount: u64,
+    reason: ReserveMovementReason,
+    memo: Option<String>,
+) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     let clock = Clock::get()?;
 
     // Validate amount
     require!(amount > 0, PoolError::ZeroAmountNotAllowed);
 
-    // Validate admin has sufficient USDC balance
+    // Validate treasurer has sufficient USDC balance
     require!(
-        ctx.accounts.admin_usdc_account.amount >= amount,
+        ctx.accounts.treasurer_usdc_account.amount >= amount,
         PoolError::InsufficientAccountBalance
     );
     
-    // Transfer USDC from admin to pool reserve
+    // Transfer USDC from treasurer to pool reserve
     transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
-                from: ctx.accounts.admin_usdc_account.to_account_info(),
+                from: ctx.accounts.treasurer_usdc_account.to_account_info(),
                 to: ctx.accounts.pool_usdc_reserve.to_account_info(),
-                authority: ctx.accounts.admin.to_account_info(),
+                authority: ctx.accounts.treasurer.to_account_info(),
             },
         ),
         amount,
@@ -72,18 +84,39 @@ pub fn handler(ctx: Context<AdminDepositUsdc>, amount: u64) -> Result<()> {
         .checked_add(amount)
         .ok_or(PoolError::MathematicalOverflow)?;
 
+    // Promotion funding stays in the reserve, earmarked for deposit bonuses
+    if reason == ReserveMovementReason::Promotion {
+        pool.promo_budget = pool
+            .promo_budget
+            .checked_add(amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+    }
+
+    // Queue funding is held back from instant withdrawals
+    if reason == ReserveMovementReason::QueueFunding {
+        pool.queue_reserved_usdc = pool
+            .queue_reserved_usdc
+            .checked_add(amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+    }
+
+    // Reference for bank-side reconciliation
+    TokenUtils::attach_memo(ctx.accounts.memo_program.as_ref(), memo.as_ref())?;
+
     // Emit event
     emit!(AdminDepositExecuted {
-        admin: ctx.accounts.admin.key(),
+        treasurer: ctx.accounts.treasurer.key(),
         pool: pool.key(),
         amount,
         new_reserves: pool.total_usdc_reserves,
+        reason,
         timestamp: clock.unix_timestamp,
     });
 
     msg!(
-        "Admin deposited {} USDC. New reserves: {}",
+        "Treasurer deposited {} USDC ({:?}). New reserves: {}",
         amount,
+        reason,
         pool.total_usdc_reserves
     );
 
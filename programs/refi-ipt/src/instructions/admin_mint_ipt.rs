@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct AdminMintIpt<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.pool_state == PoolState::Migration @ PoolError::PoolNotInMigration,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Scheduled MintIpt operation (consumed on execution)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            timelock_operation.id.to_le_bytes().as_ref()
+        ],
+        bump = timelock_operation.bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Beneficiary's IPT token account
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint
+    )]
+    pub beneficiary_ipt_account: Account<'info, TokenAccount>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<AdminMintIpt>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+    let operation = &ctx.accounts.timelock_operation;
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_timelock_elapsed(operation, clock.unix_timestamp)?;
+
+    let AdminAction::MintIpt {
+        beneficiary,
+        amount,
+        reason,
+    } = operation.action.clone()
+    else {
+        return err!(PoolError::TimelockActionMismatch);
+    };
+
+    require!(
+        ctx.accounts.beneficiary_ipt_account.owner == beneficiary,
+        PoolError::TokenAccountOwnerMismatch
+    );
+
+    let migration_minted = pool
+        .migration_minted
+        .checked_add(amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    require!(
+        migration_minted <= pool.migration_mint_cap,
+        PoolError::MigrationMintCapExceeded
+    );
+
+    let new_total_supply = pool
+        .total_ipt_supply
+        .checked_add(amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    if pool.max_total_supply > 0 {
+        require!(
+            new_total_supply <= pool.max_total_supply,
+            PoolError::MaxTotalSupplyExceeded
+        );
+    }
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.ipt_mint.to_account_info(),
+                to: ctx.accounts.beneficiary_ipt_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        amount,
+    )?;
+
+    pool.migration_minted = migration_minted;
+    pool.total_ipt_supply = new_total_supply;
+
+    emit!(MigrationMintExecuted {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        operation_id: operation.id,
+        beneficiary,
+        amount,
+        reason: reason.clone(),
+        new_ipt_supply: pool.total_ipt_supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Migration mint of {} IPT to {}: {}",
+        amount,
+        beneficiary,
+        reason
+    );
+
+    Ok(())
+}
@@ -16,62 +16,134 @@ pub struct AdminUpdateConfig<'info> {
         mut,
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump,
-        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
+
+    /// Program-wide config (fee bounds)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(ctx: Context<AdminUpdateConfig>, new_config: PoolConfig) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
+    // Settings with their own instruction change only through it, so a
+    // stale full config cannot overwrite them
+    let old_config = &ctx.accounts.pool.config;
+    require!(
+        new_config.fee_collector == old_config.fee_collector
+            && new_config.deposit_fee_bps == old_config.deposit_fee_bps
+            && new_config.withdrawal_fee_bps == old_config.withdrawal_fee_bps
+            && new_config.management_fee_bps == old_config.management_fee_bps
+            && new_config.max_queue_size == old_config.max_queue_size
+            && new_config.max_total_supply == old_config.max_total_supply,
+        PoolError::ConfigFieldHasSetter
+    );
+
+    apply_config(
+        &mut ctx.accounts.pool,
+        ctx.accounts.admin.key(),
+        &ctx.accounts.protocol_config,
+        new_config,
+    )
+}
+
+/// Validate and install `new_config`, emitting an event per changed
+/// setting; shared with the targeted config setters
+pub fn apply_config(
+    pool: &mut Account<Pool>,
+    admin: Pubkey,
+    protocol_config: &ProtocolConfig,
+    new_config: PoolConfig,
+) -> Result<()> {
     let clock = Clock::get()?;
 
     // Validate new configuration
-    ValidationUtils::validate_pool_config(&new_config)?;
+    ValidationUtils::validate_pool_config(&new_config, pool.queue_capacity)?;
+    ValidationUtils::validate_fee_bounds(&new_config, protocol_config)?;
+
+    // New bounds must still admit the live rate
+    require!(
+        new_config.rate_in_bounds(pool.current_exchange_rate),
+        PoolError::InvalidRateBounds
+    );
 
     // Track changes for events
     let old_config = pool.config.clone();
 
-    // Critical checks for sensitive changes
-    if new_config.admin_authority != old_config.admin_authority {
-        emit!(PoolConfigUpdated {
-            admin: ctx.accounts.admin.key(),
-            pool: pool.key(),
-            config_field: "admin_authority".to_string(),
-            old_value: old_config.admin_authority.to_string(),
-            new_value: new_config.admin_authority.to_string(),
-            timestamp: clock.unix_timestamp,
-        });
+    // The share model is fixed for the life of the pool
+    require!(
+        new_config.rebasing == old_config.rebasing,
+        PoolError::InvalidConfigParameter
+    );
 
-        msg!(
-            "CRITICAL: Admin authority changed from {} to {}",
-            old_config.admin_authority,
-            new_config.admin_authority
-        );
-    }
+    // The payout order is fixed once the pool starts winding down
+    require!(
+        pool.pool_state != PoolState::WindDown
+            || new_config.wind_down_order() == old_config.wind_down_order(),
+        PoolError::PoolWindingDown
+    );
 
-    if new_config.oracle_authority != old_config.oracle_authority {
-        emit!(PoolConfigUpdated {
-            admin: ctx.accounts.admin.key(),
-            pool: pool.key(),
-            config_field: "oracle_authority".to_string(),
-            old_value: old_config.oracle_authority.to_string(),
-            new_value: new_config.oracle_authority.to_string(),
-            timestamp: clock.unix_timestamp,
-        });
+    // Shortening the timelock would let pending actions skip their delay
+    require!(
+        new_config.timelock_delay >= old_config.timelock_delay,
+        PoolError::TimelockDelayDecreased
+    );
 
-        msg!(
-            "Oracle authority changed from {} to {}",
-            old_config.oracle_authority,
-            new_config.oracle_authority
-        );
-    }
+    // The admin key only changes hands through propose_admin / accept_admin,
+    // so a mistyped key cannot lock the pool
+    require!(
+        new_config.admin_authority == old_config.admin_authority,
+        PoolError::AdminTransferRequiresAcceptance
+    );
+
+    // Otherwise the admin could name itself treasurer and move the reserve
+    // in one transaction; set_treasurer is timelocked and co-signed
+    require!(
+        new_config.treasurer_authority == old_config.treasurer_authority,
+        PoolError::TreasurerChangeRequiresTimelock
+    );
+    require!(
+        new_config.withdrawal_cosigner == old_config.withdrawal_cosigner
+            && new_config.large_withdrawal_threshold == old_config.large_withdrawal_threshold,
+        PoolError::CosignerChangeRequiresTimelock
+    );
+    require!(
+        new_config.escheat_custody == old_config.escheat_custody,
+        PoolError::EscheatCustodyChangeRequiresTimelock
+    );
+    require!(
+        new_config.recovery_authority == old_config.recovery_authority
+            && new_config.guardian_authority == old_config.guardian_authority
+            && new_config.compliance_authority == old_config.compliance_authority,
+        PoolError::RoleChangeRequiresTimelock
+    );
+    // The pool authority signs for the wrapper on every sweep
+    require!(
+        new_config.sweep_wrapper_program == old_config.sweep_wrapper_program
+            && new_config.sweep_wrapped_mint == old_config.sweep_wrapped_mint,
+        PoolError::SweepWrapperChangeRequiresTimelock
+    );
+
+    // Oracle keys rotate through propose_oracle_authority and
+    // accept_oracle_authority, which keep the old key live for the overlap
+    require!(
+        new_config.oracle_authority == old_config.oracle_authority,
+        PoolError::OracleChangeRequiresRotation
+    );
+
+    // Critical checks for sensitive changes
 
     if new_config.fee_collector != old_config.fee_collector {
         emit!(PoolConfigUpdated {
-            admin: ctx.accounts.admin.key(),
+            admin,
             pool: pool.key(),
             config_field: "fee_collector".to_string(),
             old_value: old_config.fee_collector.to_string(),
@@ -89,7 +161,7 @@ pub fn handler(ctx: Context<AdminUpdateConfig>, new_config: PoolConfig) -> Resul
     // Fee rate changes
     if new_config.deposit_fee_bps != old_config.deposit_fee_bps {
         emit!(PoolConfigUpdated {
-            admin: ctx.accounts.admin.key(),
+            admin,
             pool: pool.key(),
             config_field: "deposit_fee_bps".to_string(),
             old_value: old_config.deposit_fee_bps.to_string(),
@@ -106,7 +178,7 @@ pub fn handler(ctx: Context<AdminUpdateConfig>, new_config: PoolConfig) -> Resul
 
     if new_config.withdrawal_fee_bps != old_config.withdrawal_fee_bps {
         emit!(PoolConfigUpdated {
-            admin: ctx.accounts.admin.key(),
+            admin,
             pool: pool.key(),
             config_field: "withdrawal_fee_bps".to_string(),
             old_value: old_config.withdrawal_fee_bps.to_string(),
@@ -123,7 +195,7 @@ pub fn handler(ctx: Context<AdminUpdateConfig>, new_config: PoolConfig) -> Resul
 
     if new_config.management_fee_bps != old_config.management_fee_bps {
         emit!(PoolConfigUpdated {
-            admin: ctx.accounts.admin.key(),
+            admin,
             pool: pool.key(),
             config_field: "management_fee_bps".to_string(),
             old_value: old_config.management_fee_bps.to_string(),
@@ -138,9 +210,716 @@ pub fn handler(ctx: Context<AdminUpdateConfig>, new_config: PoolConfig) -> Resul
         );
     }
 
+    if new_config.timelock_delay != old_config.timelock_delay {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "timelock_delay".to_string(),
+            old_value: old_config.timelock_delay.to_string(),
+            new_value: new_config.timelock_delay.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Timelock delay changed from {}s to {}s",
+            old_config.timelock_delay,
+            new_config.timelock_delay
+        );
+    }
+
+    if new_config.rate_floor != old_config.rate_floor
+        || new_config.rate_ceiling != old_config.rate_ceiling
+    {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "rate_bounds".to_string(),
+            old_value: format!("{}..{}", old_config.rate_floor, old_config.rate_ceiling),
+            new_value: format!("{}..{}", new_config.rate_floor, new_config.rate_ceiling),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Rate bounds changed from {}..{} to {}..{}",
+            old_config.rate_floor,
+            old_config.rate_ceiling,
+            new_config.rate_floor,
+            new_config.rate_ceiling
+        );
+    }
+
+    if new_config.max_heartbeat_secs != old_config.max_heartbeat_secs {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "max_heartbeat_secs".to_string(),
+            old_value: old_config.max_heartbeat_secs.to_string(),
+            new_value: new_config.max_heartbeat_secs.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Oracle heartbeat changed from {}s to {}s",
+            old_config.max_heartbeat_secs,
+            new_config.max_heartbeat_secs
+        );
+    }
+
+    if new_config.dead_man_switch_secs != old_config.dead_man_switch_secs {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "dead_man_switch_secs".to_string(),
+            old_value: old_config.dead_man_switch_secs.to_string(),
+            new_value: new_config.dead_man_switch_secs.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Dead-man switch changed from {}s to {}s",
+            old_config.dead_man_switch_secs,
+            new_config.dead_man_switch_secs
+        );
+    }
+
+    if new_config.min_rate_update_interval_secs != old_config.min_rate_update_interval_secs {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "min_rate_update_interval_secs".to_string(),
+            old_value: old_config.min_rate_update_interval_secs.to_string(),
+            new_value: new_config.min_rate_update_interval_secs.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Minimum rate update interval changed from {}s to {}s",
+            old_config.min_rate_update_interval_secs,
+            new_config.min_rate_update_interval_secs
+        );
+    }
+
+    if new_config.max_redemption_bps_per_epoch != old_config.max_redemption_bps_per_epoch {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "max_redemption_bps_per_epoch".to_string(),
+            old_value: old_config.max_redemption_bps_per_epoch.to_string(),
+            new_value: new_config.max_redemption_bps_per_epoch.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Redemption gate changed from {}bps to {}bps per epoch",
+            old_config.max_redemption_bps_per_epoch,
+            new_config.max_redemption_bps_per_epoch
+        );
+    }
+
+    if new_config.queue_pressure_depth != old_config.queue_pressure_depth
+        || new_config.queue_pressure_bps != old_config.queue_pressure_bps
+    {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "queue_pressure".to_string(),
+            old_value: format!(
+                "{}bps beyond {}",
+                old_config.queue_pressure_bps, old_config.queue_pressure_depth
+            ),
+            new_value: format!(
+                "{}bps beyond {}",
+                new_config.queue_pressure_bps, new_config.queue_pressure_depth
+            ),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Queue pressure adjustment changed to {}bps beyond depth {}",
+            new_config.queue_pressure_bps,
+            new_config.queue_pressure_depth
+        );
+    }
+
+    if new_config.epoch_duration_secs != old_config.epoch_duration_secs {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "epoch_duration_secs".to_string(),
+            old_value: old_config.epoch_duration_secs.to_string(),
+            new_value: new_config.epoch_duration_secs.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Epoch duration changed from {}s to {}s",
+            old_config.epoch_duration_secs,
+            new_config.epoch_duration_secs
+        );
+    }
+
+    if new_config.isolated_exits != old_config.isolated_exits {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "isolated_exits".to_string(),
+            old_value: old_config.isolated_exits.to_string(),
+            new_value: new_config.isolated_exits.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Isolated exits changed from {} to {}",
+            old_config.isolated_exits,
+            new_config.isolated_exits
+        );
+    }
+
+    let reprioritize = new_config.queue_age_weight != old_config.queue_age_weight
+        || new_config.queue_size_weight != old_config.queue_size_weight;
+    if reprioritize {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "queue_priority_weights".to_string(),
+            old_value: format!(
+                "age {} size {}",
+                old_config.queue_age_weight, old_config.queue_size_weight
+            ),
+            new_value: format!(
+                "age {} size {}",
+                new_config.queue_age_weight, new_config.queue_size_weight
+            ),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Queue priority weights changed to age {} size {}",
+            new_config.queue_age_weight,
+            new_config.queue_size_weight
+        );
+    }
+
+    if new_config.max_batch_share_bps != old_config.max_batch_share_bps {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "max_batch_share_bps".to_string(),
+            old_value: old_config.max_batch_share_bps.to_string(),
+            new_value: new_config.max_batch_share_bps.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Per-user batch share changed from {}bps to {}bps",
+            old_config.max_batch_share_bps,
+            new_config.max_batch_share_bps
+        );
+    }
+
+    if new_config.max_skip_attempts != old_config.max_skip_attempts {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "max_skip_attempts".to_string(),
+            old_value: old_config.max_skip_attempts.to_string(),
+            new_value: new_config.max_skip_attempts.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Queue skip attempts changed from {} to {}",
+            old_config.max_skip_attempts,
+            new_config.max_skip_attempts
+        );
+    }
+
+    if new_config.min_execution_delay_secs != old_config.min_execution_delay_secs {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "min_execution_delay_secs".to_string(),
+            old_value: old_config.min_execution_delay_secs.to_string(),
+            new_value: new_config.min_execution_delay_secs.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Withdrawal execution delay changed from {}s to {}s",
+            old_config.min_execution_delay_secs,
+            new_config.min_execution_delay_secs
+        );
+    }
+
+    if new_config.cancellation_fee_bps != old_config.cancellation_fee_bps
+        || new_config.cancellation_grace_secs != old_config.cancellation_grace_secs
+    {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "cancellation_fee".to_string(),
+            old_value: format!(
+                "{}bps, waived after {}s",
+                old_config.cancellation_fee_bps, old_config.cancellation_grace_secs
+            ),
+            new_value: format!(
+                "{}bps, waived after {}s",
+                new_config.cancellation_fee_bps, new_config.cancellation_grace_secs
+            ),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Cancellation fee changed from {} to {} bps",
+            old_config.cancellation_fee_bps,
+            new_config.cancellation_fee_bps
+        );
+    }
+
+    if new_config.commit_reveal_threshold != old_config.commit_reveal_threshold
+        || new_config.reveal_delay_slots != old_config.reveal_delay_slots
+    {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "commit_reveal".to_string(),
+            old_value: format!(
+                "{} IPT, {} slots",
+                old_config.commit_reveal_threshold, old_config.reveal_delay_slots
+            ),
+            new_value: format!(
+                "{} IPT, {} slots",
+                new_config.commit_reveal_threshold, new_config.reveal_delay_slots
+            ),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Commit-reveal threshold changed from {} to {} IPT",
+            old_config.commit_reveal_threshold,
+            new_config.commit_reveal_threshold
+        );
+    }
+
+    if new_config.approval_threshold != old_config.approval_threshold
+        || new_config.approval_expiry_secs != old_config.approval_expiry_secs
+    {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "withdrawal_approval".to_string(),
+            old_value: format!(
+                "{} IPT, expires after {}s",
+                old_config.approval_threshold, old_config.approval_expiry_secs
+            ),
+            new_value: format!(
+                "{} IPT, expires after {}s",
+                new_config.approval_threshold, new_config.approval_expiry_secs
+            ),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Withdrawal approval threshold changed from {} to {} IPT",
+            old_config.approval_threshold,
+            new_config.approval_threshold
+        );
+    }
+
+    if new_config.max_buyback_usdc != old_config.max_buyback_usdc {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "max_buyback_usdc".to_string(),
+            old_value: old_config.max_buyback_usdc.to_string(),
+            new_value: new_config.max_buyback_usdc.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Buyback limit changed from {} to {} USDC",
+            old_config.max_buyback_usdc,
+            new_config.max_buyback_usdc
+        );
+    }
+
+    if new_config.fee_sweep_threshold != old_config.fee_sweep_threshold {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "fee_sweep_threshold".to_string(),
+            old_value: old_config.fee_sweep_threshold.to_string(),
+            new_value: new_config.fee_sweep_threshold.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Fee sweep threshold changed from {} to {} USDC",
+            old_config.fee_sweep_threshold,
+            new_config.fee_sweep_threshold
+        );
+    }
+
+    if new_config.fee_recipients != old_config.fee_recipients {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "fee_recipients".to_string(),
+            old_value: format!("{:?}", old_config.fee_recipients),
+            new_value: format!("{:?}", new_config.fee_recipients),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Fee split changed to {} recipients",
+            new_config.fee_recipients.len()
+        );
+    }
+
+    if new_config.oracle_overlap_secs != old_config.oracle_overlap_secs {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "oracle_overlap_secs".to_string(),
+            old_value: old_config.oracle_overlap_secs.to_string(),
+            new_value: new_config.oracle_overlap_secs.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Oracle rotation overlap changed from {}s to {}s",
+            old_config.oracle_overlap_secs,
+            new_config.oracle_overlap_secs
+        );
+    }
+
+    if new_config.promo_start_at != old_config.promo_start_at {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "promo_start_at".to_string(),
+            old_value: old_config.promo_start_at.to_string(),
+            new_value: new_config.promo_start_at.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Promotion start changed from {} to {}",
+            old_config.promo_start_at,
+            new_config.promo_start_at
+        );
+    }
+
+    if new_config.promo_end_at != old_config.promo_end_at {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "promo_end_at".to_string(),
+            old_value: old_config.promo_end_at.to_string(),
+            new_value: new_config.promo_end_at.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Promotion end changed from {} to {}",
+            old_config.promo_end_at,
+            new_config.promo_end_at
+        );
+    }
+
+    if new_config.promo_bonus_bps != old_config.promo_bonus_bps {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "promo_bonus_bps".to_string(),
+            old_value: old_config.promo_bonus_bps.to_string(),
+            new_value: new_config.promo_bonus_bps.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Promotion bonus changed from {} to {} bps",
+            old_config.promo_bonus_bps,
+            new_config.promo_bonus_bps
+        );
+    }
+
+    if new_config.promo_waives_deposit_fee != old_config.promo_waives_deposit_fee {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "promo_waives_deposit_fee".to_string(),
+            old_value: old_config.promo_waives_deposit_fee.to_string(),
+            new_value: new_config.promo_waives_deposit_fee.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Promotion fee waiver changed from {} to {}",
+            old_config.promo_waives_deposit_fee,
+            new_config.promo_waives_deposit_fee
+        );
+    }
+
+    if new_config.early_supporter_slots != old_config.early_supporter_slots {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "early_supporter_slots".to_string(),
+            old_value: old_config.early_supporter_slots.to_string(),
+            new_value: new_config.early_supporter_slots.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Early supporter slots changed from {} to {}",
+            old_config.early_supporter_slots,
+            new_config.early_supporter_slots
+        );
+    }
+
+    if new_config.early_supporter_discount_bps != old_config.early_supporter_discount_bps {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "early_supporter_discount_bps".to_string(),
+            old_value: old_config.early_supporter_discount_bps.to_string(),
+            new_value: new_config.early_supporter_discount_bps.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Early supporter discount changed from {} to {} bps",
+            old_config.early_supporter_discount_bps,
+            new_config.early_supporter_discount_bps
+        );
+    }
+
+    if new_config.referral_fee_share_bps != old_config.referral_fee_share_bps {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "referral_fee_share_bps".to_string(),
+            old_value: old_config.referral_fee_share_bps.to_string(),
+            new_value: new_config.referral_fee_share_bps.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Referral fee share changed from {} to {} bps",
+            old_config.referral_fee_share_bps,
+            new_config.referral_fee_share_bps
+        );
+    }
+
+    if new_config.allowlist_only != old_config.allowlist_only {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "allowlist_only".to_string(),
+            old_value: old_config.allowlist_only.to_string(),
+            new_value: new_config.allowlist_only.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Allowlist-only deposits changed from {} to {}",
+            old_config.allowlist_only,
+            new_config.allowlist_only
+        );
+    }
+
+    if new_config.dormancy_period_secs != old_config.dormancy_period_secs {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "dormancy_period_secs".to_string(),
+            old_value: old_config.dormancy_period_secs.to_string(),
+            new_value: new_config.dormancy_period_secs.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Dormancy period changed from {}s to {}s",
+            old_config.dormancy_period_secs,
+            new_config.dormancy_period_secs
+        );
+    }
+
+    if new_config.reserve_drift_tolerance_bps != old_config.reserve_drift_tolerance_bps {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "reserve_drift_tolerance_bps".to_string(),
+            old_value: old_config.reserve_drift_tolerance_bps.to_string(),
+            new_value: new_config.reserve_drift_tolerance_bps.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Reserve drift tolerance changed from {}bps to {}bps",
+            old_config.reserve_drift_tolerance_bps,
+            new_config.reserve_drift_tolerance_bps
+        );
+    }
+
+    if new_config.wind_down_waterfall != old_config.wind_down_waterfall {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "wind_down_waterfall".to_string(),
+            old_value: format!("{:?}", old_config.wind_down_waterfall),
+            new_value: format!("{:?}", new_config.wind_down_waterfall),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Wind-down waterfall changed to {:?}", new_config.wind_down_order());
+    }
+
+    if new_config.pull_payouts != old_config.pull_payouts {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "pull_payouts".to_string(),
+            old_value: old_config.pull_payouts.to_string(),
+            new_value: new_config.pull_payouts.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Batch payouts changed to {} mode",
+            if new_config.pull_payouts { "pull" } else { "push" }
+        );
+    }
+
+    if new_config.queue_alert_depth != old_config.queue_alert_depth
+        || new_config.queue_alert_usdc != old_config.queue_alert_usdc
+    {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "queue_alert".to_string(),
+            old_value: format!(
+                "{} entries or {} USDC",
+                old_config.queue_alert_depth, old_config.queue_alert_usdc
+            ),
+            new_value: format!(
+                "{} entries or {} USDC",
+                new_config.queue_alert_depth, new_config.queue_alert_usdc
+            ),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Queue alert thresholds changed to {} entries or {} USDC",
+            new_config.queue_alert_depth,
+            new_config.queue_alert_usdc
+        );
+    }
+
+    if new_config.tier_terms != old_config.tier_terms {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "tier_terms".to_string(),
+            old_value: format!("{:?}", old_config.tier_terms),
+            new_value: format!("{:?}", new_config.tier_terms),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Investor tier terms changed to {:?}", new_config.tier_terms);
+    }
+
+    if new_config.max_queue_entries_per_day != old_config.max_queue_entries_per_day {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "max_queue_entries_per_day".to_string(),
+            old_value: old_config.max_queue_entries_per_day.to_string(),
+            new_value: new_config.max_queue_entries_per_day.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Daily queue entry limit changed to {}",
+            new_config.max_queue_entries_per_day
+        );
+    }
+
+    if new_config.refund_window_secs != old_config.refund_window_secs {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "refund_window_secs".to_string(),
+            old_value: old_config.refund_window_secs.to_string(),
+            new_value: new_config.refund_window_secs.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Deposit refund window changed to {} seconds",
+            new_config.refund_window_secs
+        );
+    }
+
+    if new_config.slot_stable_quotes != old_config.slot_stable_quotes {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "slot_stable_quotes".to_string(),
+            old_value: old_config.slot_stable_quotes.to_string(),
+            new_value: new_config.slot_stable_quotes.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Slot-stable quotes set to {}", new_config.slot_stable_quotes);
+    }
+
+    if new_config.liquidity_buffer_usdc != old_config.liquidity_buffer_usdc {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "liquidity_buffer_usdc".to_string(),
+            old_value: old_config.liquidity_buffer_usdc.to_string(),
+            new_value: new_config.liquidity_buffer_usdc.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Liquidity buffer changed to {} USDC",
+            new_config.liquidity_buffer_usdc
+        );
+    }
+
+    if new_config.segregate_roles != old_config.segregate_roles {
+        emit!(PoolConfigUpdated {
+            admin,
+            pool: pool.key(),
+            config_field: "segregate_roles".to_string(),
+            old_value: old_config.segregate_roles.to_string(),
+            new_value: new_config.segregate_roles.to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Role segregation changed from {} to {}",
+            old_config.segregate_roles,
+            new_config.segregate_roles
+        );
+    }
+
     // Update the configuration
     pool.config = new_config;
 
+    // Resize the redemption window under the new gate on next use
+    if pool.config.max_redemption_bps_per_epoch != old_config.max_redemption_bps_per_epoch {
+        pool.redemption_epoch = u64::MAX;
+    }
+
+    if reprioritize {
+        pool.reprioritize_queue();
+    }
+
     msg!("Pool configuration updated successfully");
 
     Ok(())
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::instructions::update_exchange_rate::record_exchange_rate;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AdminUpdateRateAndState<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(
+    ctx: Context<AdminUpdateRateAndState>,
+    new_rate: u64,
+    observed_at: i64,
+    pause_flags: u8,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pause_flags & !PauseFlag::ALL == 0,
+        PoolError::InvalidConfigParameter
+    );
+
+    // Correct the rate first so nothing can execute between the new NAV and
+    // the new pause state; the admin path is not held by the rate-update pause
+    record_exchange_rate(pool, admin, new_rate, observed_at, 0)?;
+
+    let old_flags = pool.pause_flags;
+    pool.pause_flags = pause_flags;
+
+    emit!(PauseFlagsUpdated {
+        admin,
+        pool: pool.key(),
+        old_flags,
+        new_flags: pool.pause_flags,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Rate set to {} and pause flags updated from {:#06b} to {:#06b}",
+        new_rate,
+        old_flags,
+        pool.pause_flags
+    );
+
+    Ok(())
+}
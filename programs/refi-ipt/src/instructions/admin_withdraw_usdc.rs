@@ -3,23 +3,27 @@
 use crate::errors::PoolError;
 use crate::events::*;
 use crate::states::*;
+use crate::utils::TokenUtils;
 use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
 use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
 pub struct AdminWithdrawUsdc<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub treasurer: Signer<'info>,
 
     /// Pool state account
     #[account(
         mut,
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump,
-        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin
+        constraint = treasurer.key() == pool.config.treasurer_authority @ PoolError::UnauthorizedTreasurer,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
 
@@ -27,19 +31,20 @@ pub struct AdminWithdrawUsdc<'info> {
     #[account(
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump
     )]
     pub pool_authority: UncheckedAccount<'info>,
 
-    /// Admin's USDC token account
+    /// Treasurer's USDC token account
     #[account(
         mut,
         token::mint = pool.usdc_mint,
-        token::authority = admin
+        token::authority = treasurer
     )]
-    pub admin_usdc_account: Account<'info, TokenAccount>,
+    pub treasurer_usdc_account: Account<'info, TokenAccount>,
 
     /// Pool's USDC reserve
     #[account(
@@ -49,30 +54,60 @@ pub struct AdminWithdrawUsdc<'info> {
     pub pool_usdc_reserve: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
+
+    /// Second authority, required once the epoch's withdrawals reach the
+    /// large withdrawal threshold
+    pub cosigner: Option<Signer<'info>>,
+
+    /// SPL Memo program, needed only when a memo is attached
+    pub memo_program: Option<Program<'info, Memo>>,
 }
 
-pub fn handler(ctx: Context<AdminWithdrawUsdc>, amount: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<AdminWithdrawUsdc>,
+    amount: u64,
+    reason: ReserveMovementReason,
+    memo: Option<String>,
+) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
     let clock = Clock::get()?;
 
+    // A closing pool's reserve belongs to the waterfall's claimants
+    require!(
+        pool.pool_state != PoolState::WindDown,
+        PoolError::PoolWindingDown
+    );
+
     // Validate amount
     require!(amount > 0, PoolError::ZeroAmountNotAllowed);
 
+    // Past the epoch's threshold every withdrawal needs the independent
+    // co-signer as well, so it cannot be split into smaller calls
+    let cosigner = ctx.accounts.cosigner.as_ref().map(|c| c.key());
+    let epoch_total = pool.record_admin_withdrawal(amount)?;
+    if pool.config.requires_cosigner(epoch_total) {
+        require!(
+            cosigner == Some(pool.config.withdrawal_cosigner),
+            PoolError::CosignerRequired
+        );
+    }
+
     // Check if pool has enough reserves
     require!(
         ctx.accounts.pool_usdc_reserve.amount >= amount,
         PoolError::InsufficientReserves
     );
-    // Transfer USDC from pool to admin
+    // Transfer USDC from pool to treasurer
     transfer(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.pool_usdc_reserve.to_account_info(),
-                to: ctx.accounts.admin_usdc_account.to_account_info(),
+                to: ctx.accounts.treasurer_usdc_account.to_account_info(),
                 authority: ctx.accounts.pool_authority.to_account_info(),
             },
-            &[&pool.authority_seeds()],
+            &[&pool.authority_seeds(&pool_id_seed)],
         ),
         amount,
     )?;
@@ -82,18 +117,39 @@ pub fn handler(ctx: Context<AdminWithdrawUsdc>, amount: u64) -> Result<()> {
         .checked_sub(amount)
         .ok_or(PoolError::MathematicalUnderflow)?;
 
+    // Only the unspent promotion budget can be reclaimed as such
+    if reason == ReserveMovementReason::Promotion {
+        pool.promo_budget = pool
+            .promo_budget
+            .checked_sub(amount)
+            .ok_or(PoolError::InsufficientPromoBudget)?;
+    }
+
+    if reason == ReserveMovementReason::QueueFunding {
+        pool.queue_reserved_usdc = pool
+            .queue_reserved_usdc
+            .checked_sub(amount)
+            .ok_or(PoolError::InsufficientQueueReserve)?;
+    }
+
+    // Reference for bank-side reconciliation
+    TokenUtils::attach_memo(ctx.accounts.memo_program.as_ref(), memo.as_ref())?;
+
     // Emit event
     emit!(AdminWithdrawExecuted {
-        admin: ctx.accounts.admin.key(),
+        treasurer: ctx.accounts.treasurer.key(),
         pool: pool.key(),
         amount,
+        cosigner,
         remaining_reserves: pool.total_usdc_reserves,
+        reason,
         timestamp: clock.unix_timestamp,
     });
 
     msg!(
-        "Admin withdrew {} USDC. Remaining reserves: {}",
+        "Treasurer withdrew {} USDC ({:?}). Remaining reserves: {}",
         amount,
+        reason,
         pool.total_usdc_reserves
     );
 
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::CalculationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    /// Any keeper may close a due epoch
+    pub caller: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<AdvanceEpoch>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let elapsed = clock
+        .unix_timestamp
+        .checked_sub(pool.epoch_started_at)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    require!(
+        elapsed >= pool.config.epoch_duration_secs,
+        PoolError::EpochNotDue
+    );
+
+    // Finalize NAV at the closing rate and accrue the management fee over the epoch
+    let nav = pool.nav()?;
+    let management_fee = CalculationUtils::calculate_management_fee(
+        nav,
+        pool.config.management_fee_bps,
+        elapsed,
+    )?;
+
+    pool.credit_fees(FeeBucket::Management, management_fee)?;
+
+    let closed_epoch = pool.current_epoch;
+    let started_at = pool.epoch_started_at;
+
+    pool.last_epoch_nav = nav;
+    pool.current_epoch = closed_epoch
+        .checked_add(1)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    pool.epoch_started_at = clock.unix_timestamp;
+
+    // Open the new epoch's redemption window
+    pool.roll_redemption_epoch()?;
+
+    emit!(EpochClosed {
+        pool: pool.key(),
+        caller: ctx.accounts.caller.key(),
+        epoch: closed_epoch,
+        nav,
+        management_fee,
+        started_at,
+        closed_at: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Epoch {} closed with NAV {}, management fee {}",
+        closed_epoch,
+        nav,
+        management_fee
+    );
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AdvanceWindDown<'info> {
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Permissionless: moves the waterfall on once its current class is paid out
+pub fn handler(ctx: Context<AdvanceWindDown>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    let settled = pool
+        .wind_down_class()
+        .ok_or(PoolError::PoolNotWindingDown)?;
+    require!(
+        pool.wind_down_stage_settled(),
+        PoolError::WindDownStageIncomplete
+    );
+
+    pool.wind_down_stage += 1;
+    let next = pool.wind_down_class();
+
+    emit!(WindDownStageAdvanced {
+        pool: pool.key(),
+        settled,
+        next,
+        stage: pool.wind_down_stage,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Wind-down class {:?} settled, next {:?}", settled, next);
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawalRequest<'info> {
+    pub compliance_authority: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = compliance_authority.key() == pool.config.compliance_authority
+            @ PoolError::UnauthorizedComplianceAuthority,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<ApproveWithdrawalRequest>, user: Pubkey) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+    let expiry_secs = pool.config.approval_expiry_secs;
+
+    let entry = pool
+        .queued_mut()
+        .iter_mut()
+        .find(|w| w.user == user)
+        .ok_or(PoolError::InvalidUserAccount)?;
+    require!(!entry.approved, PoolError::ApprovalNotRequired);
+
+    // Lapsed requests are dropped by the next batch rather than revived
+    require!(
+        !entry.approval_expired(expiry_secs, clock.unix_timestamp),
+        PoolError::ApprovalExpired
+    );
+
+    entry.approved = true;
+    let ipt_amount = entry.amount;
+
+    emit!(WithdrawalApproved {
+        compliance_authority: ctx.accounts.compliance_authority.key(),
+        pool: pool.key(),
+        user,
+        ipt_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Withdrawal of {} IPT for {} approved", ipt_amount, user);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct BeginMigration<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<BeginMigration>, mint_cap: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.pool_state != PoolState::Migration,
+        PoolError::PoolInMigration
+    );
+    require!(pool.pool_state != PoolState::Frozen, PoolError::PoolFrozen);
+    require!(mint_cap > 0, PoolError::ZeroAmountNotAllowed);
+
+    // User flows stay halted until end_migration
+    pool.pool_state = PoolState::Migration;
+    pool.migration_mint_cap = mint_cap;
+    pool.migration_minted = 0;
+
+    emit!(MigrationStarted {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        mint_cap,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Pool migration started with mint cap {}", mint_cap);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct BeginWindDown<'info> {
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<BeginWindDown>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.pool_state != PoolState::Migration,
+        PoolError::PoolInMigration
+    );
+    require!(
+        pool.pool_state != PoolState::WindDown,
+        PoolError::PoolWindingDown
+    );
+
+    // Deposits stop for good; payouts follow the configured waterfall
+    pool.pool_state = PoolState::WindDown;
+    pool.wind_down_stage = 0;
+    pool.wind_down_started_at = clock.unix_timestamp;
+
+    emit!(WindDownStarted {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        waterfall: pool.config.wind_down_order(),
+        exchange_rate: pool.current_exchange_rate,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Pool wind-down started");
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct BurnIptDonation<'info> {
+    pub holder: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Holder's IPT token account
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = holder
+    )]
+    pub holder_ipt_account: Account<'info, TokenAccount>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<BurnIptDonation>, ipt_amount: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(ipt_amount > 0, PoolError::ZeroAmountNotAllowed);
+    require!(
+        ctx.accounts.holder_ipt_account.amount >= ipt_amount,
+        PoolError::InsufficientAccountBalance
+    );
+
+    // Nothing is paid out: the reserve now backs fewer IPT
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.ipt_mint.to_account_info(),
+                from: ctx.accounts.holder_ipt_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        ipt_amount,
+    )?;
+
+    pool.total_ipt_supply = pool
+        .total_ipt_supply
+        .checked_sub(ipt_amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+
+    emit!(IptDonationBurned {
+        holder: ctx.accounts.holder.key(),
+        pool: pool.key(),
+        ipt_amount,
+        new_ipt_supply: pool.total_ipt_supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Holder burned {} IPT as a donation", ipt_amount);
+
+    Ok(())
+}
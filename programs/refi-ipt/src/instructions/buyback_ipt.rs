@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::CalculationUtils;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct BuybackIpt<'info> {
+    pub admin: Signer<'info>,
+
+    /// Holder selling IPT to the pool
+    pub seller: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Seller's IPT token account (burned from)
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = seller
+    )]
+    pub seller_ipt_account: Account<'info, TokenAccount>,
+
+    /// Seller's USDC token account (paid to)
+    #[account(
+        mut,
+        token::mint = pool.usdc_mint
+    )]
+    pub seller_usdc_account: Account<'info, TokenAccount>,
+
+    /// Pool's USDC reserve
+    #[account(
+        mut,
+        address = pool.usdc_reserve
+    )]
+    pub pool_usdc_reserve: Account<'info, TokenAccount>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(
+    ctx: Context<BuybackIpt>,
+    ipt_amount: u64,
+    usdc_amount: u64,
+    bucket: FeeBucket,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+    let clock = Clock::get()?;
+
+    require!(
+        pool.pool_state != PoolState::WindDown,
+        PoolError::PoolWindingDown
+    );
+
+    require!(ipt_amount > 0 && usdc_amount > 0, PoolError::ZeroAmountNotAllowed);
+
+    // Spending is bounded per call, and the pool never pays above NAV
+    let (nav_value, _) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+        ipt_amount,
+        pool.current_exchange_rate,
+        0,
+        pool.usdc_decimals,
+        pool.ipt_decimals,
+    )?;
+    require!(
+        usdc_amount <= pool.config.max_buyback_usdc && usdc_amount <= nav_value,
+        PoolError::InvalidBuyback
+    );
+
+    // Only fees from the chosen stream are spent; holder reserves are untouched
+    require!(
+        pool.fee_balance(bucket) >= usdc_amount,
+        PoolError::InsufficientAccumulatedFees
+    );
+    require!(
+        ctx.accounts.pool_usdc_reserve.amount >= usdc_amount,
+        PoolError::InsufficientReserves
+    );
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.ipt_mint.to_account_info(),
+                from: ctx.accounts.seller_ipt_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        ipt_amount,
+    )?;
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_usdc_reserve.to_account_info(),
+                to: ctx.accounts.seller_usdc_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        usdc_amount,
+    )?;
+
+    pool.total_ipt_supply = pool
+        .total_ipt_supply
+        .checked_sub(ipt_amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.total_usdc_reserves = pool
+        .total_usdc_reserves
+        .checked_sub(usdc_amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.debit_fees(bucket, usdc_amount)?;
+    pool.total_buyback_ipt = pool
+        .total_buyback_ipt
+        .checked_add(ipt_amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    pool.total_buyback_usdc = pool
+        .total_buyback_usdc
+        .checked_add(usdc_amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+
+    emit!(BuybackExecuted {
+        admin: ctx.accounts.admin.key(),
+        seller: ctx.accounts.seller.key(),
+        pool: pool.key(),
+        ipt_amount,
+        usdc_amount,
+        bucket,
+        total_buyback_ipt: pool.total_buyback_ipt,
+        total_buyback_usdc: pool.total_buyback_usdc,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Bought back and burned {} IPT for {} USDC of fees",
+        ipt_amount,
+        usdc_amount
+    );
+
+    Ok(())
+}
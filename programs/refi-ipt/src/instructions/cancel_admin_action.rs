@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelAdminAction<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Scheduled operation (closed, rent returned to admin)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            timelock_operation.id.to_le_bytes().as_ref()
+        ],
+        bump = timelock_operation.bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+}
+
+pub fn handler(ctx: Context<CancelAdminAction>) -> Result<()> {
+    let clock = Clock::get()?;
+    let id = ctx.accounts.timelock_operation.id;
+
+    emit!(AdminActionCancelled {
+        admin: ctx.accounts.admin.key(),
+        pool: ctx.accounts.pool.key(),
+        id,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Admin action {} cancelled", id);
+
+    Ok(())
+}
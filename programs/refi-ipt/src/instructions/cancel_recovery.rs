@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    /// Current admin, or the recovery authority withdrawing its own move
+    pub authority: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = authority.key() == pool.config.admin_authority
+            || authority.key() == pool.config.recovery_authority
+            @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<CancelRecovery>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let new_admin = pool.pending_recovery_admin;
+    require!(new_admin != Pubkey::default(), PoolError::NoPendingRecovery);
+
+    pool.pending_recovery_admin = Pubkey::default();
+    pool.pending_recovery_oracle = Pubkey::default();
+    pool.pending_recovery_fee_collector = Pubkey::default();
+    pool.recovery_executable_at = 0;
+
+    emit!(EmergencyRecoveryCancelled {
+        cancelled_by: ctx.accounts.authority.key(),
+        pool: pool.key(),
+        new_admin,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Emergency recovery to admin {} cancelled by {}",
+        new_admin,
+        ctx.accounts.authority.key()
+    );
+
+    Ok(())
+}
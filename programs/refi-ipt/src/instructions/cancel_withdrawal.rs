@@ -1,9 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
 use crate::states::*;
 use crate::errors::PoolError;
 use crate::events::*;
+use crate::utils::{CalculationUtils, ValidationUtils};
 
 #[derive(Accounts)]
 pub struct CancelWithdrawalRequest<'info> {
@@ -15,17 +17,49 @@ pub struct CancelWithdrawalRequest<'info> {
         mut,
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
+
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// User's IPT token account (cancellation fee is burned from here)
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = user
+    )]
+    pub user_ipt_account: Account<'info, TokenAccount>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 /// User cancels their own withdrawal request
 pub fn handler(ctx: Context<CancelWithdrawalRequest>) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     let user = ctx.accounts.user.key();
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_protocol_not_paused(&ctx.accounts.protocol_config)?;
+
+    // Removing from the middle shifts the queue anyway, so compact first
+    pool.compact_queue();
 
     // Find user's withdrawal request in queue
     let position = pool.pending_queue
@@ -36,19 +70,89 @@ pub fn handler(ctx: Context<CancelWithdrawalRequest>) -> Result<()> {
     // Remove the withdrawal request
     let withdrawn_request = pool.pending_queue.remove(position);
 
+    let (cancellation_fee, cancellation_fee_usdc) = charge_cancellation_fee(
+        pool,
+        &ctx.accounts.user,
+        &ctx.accounts.user_ipt_account,
+        &ctx.accounts.ipt_mint,
+        &ctx.accounts.token_program,
+        withdrawn_request.amount,
+        withdrawn_request.requested_at,
+        clock.unix_timestamp,
+    )?;
+
+    ValidationUtils::check_queue_depth(pool, clock.unix_timestamp)?;
+
     emit!(WithdrawalCancelled {
         user,
         ipt_amount: withdrawn_request.amount,
         position: position as u32,
-        timestamp: Clock::get()?.unix_timestamp,
+        cancellation_fee,
+        cancellation_fee_usdc,
+        timestamp: clock.unix_timestamp,
     });
 
     msg!(
-        "User {} cancelled withdrawal request for {} IPT at position {}",
+        "User {} cancelled withdrawal request for {} IPT at position {} (fee: {} IPT)",
         user,
         withdrawn_request.amount,
-        position
+        position,
+        cancellation_fee
     );
 
     Ok(())
 }
+
+/// Charge the cancellation fee on `ipt_amount` of a request made at
+/// `requested_at`. Early cancellations pay in IPT: it is burned and its USDC
+/// value booked as pool fees, leaving other holders' share unchanged.
+/// Returns the fee in IPT and its USDC value.
+#[allow(clippy::too_many_arguments)]
+pub fn charge_cancellation_fee<'info>(
+    pool: &mut Pool,
+    user: &Signer<'info>,
+    user_ipt_account: &Account<'info, TokenAccount>,
+    ipt_mint: &Account<'info, Mint>,
+    token_program: &Program<'info, Token>,
+    ipt_amount: u64,
+    requested_at: i64,
+    now: i64,
+) -> Result<(u64, u64)> {
+    let cancellation_fee = pool.config.cancellation_fee(ipt_amount, requested_at, now)?;
+    if cancellation_fee == 0 {
+        return Ok((0, 0));
+    }
+
+    require!(
+        user_ipt_account.amount >= cancellation_fee,
+        PoolError::InsufficientAccountBalance
+    );
+
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: ipt_mint.to_account_info(),
+                from: user_ipt_account.to_account_info(),
+                authority: user.to_account_info(),
+            },
+        ),
+        cancellation_fee,
+    )?;
+
+    let (cancellation_fee_usdc, _) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+        cancellation_fee,
+        pool.current_exchange_rate,
+        0,
+        pool.usdc_decimals,
+        pool.ipt_decimals,
+    )?;
+
+    pool.total_ipt_supply = pool
+        .total_ipt_supply
+        .checked_sub(cancellation_fee)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.credit_fees(FeeBucket::Withdrawal, cancellation_fee_usdc)?;
+
+    Ok((cancellation_fee, cancellation_fee_usdc))
+}
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CheckHeartbeat<'info> {
+    /// Anyone may run the watchdog
+    pub caller: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<CheckHeartbeat>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    if !pool.is_oracle_stale(clock.unix_timestamp) {
+        msg!("Oracle heartbeat healthy, last update at {}", pool.last_rate_update);
+        return Ok(());
+    }
+
+    if pool.is_paused(PauseFlag::Deposits) {
+        msg!("Oracle stale, deposits already paused");
+        return Ok(());
+    }
+
+    // Stop new money coming in at a dead NAV; the admin resumes via set_pause_flag
+    pool.pause_flags |= PauseFlag::Deposits.mask();
+
+    emit!(OracleStale {
+        pool: pool.key(),
+        caller: ctx.accounts.caller.key(),
+        last_rate_update: pool.last_rate_update,
+        max_heartbeat_secs: pool.config.max_heartbeat_secs,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Oracle silent since {}, deposits paused",
+        pool.last_rate_update
+    );
+
+    Ok(())
+}
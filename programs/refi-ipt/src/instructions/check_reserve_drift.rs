@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CheckReserveDrift<'info> {
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [
+            ReserveAsset::SEED_PREFIX,
+            pool.key().as_ref(),
+            reserve_asset.mint.as_ref()
+        ],
+        bump = reserve_asset.bump
+    )]
+    pub reserve_asset: Account<'info, ReserveAsset>,
+}
+
+/// Permissionless: USDC flows shift every asset's weight without touching
+/// the asset accounts, so a keeper re-checks drift to prompt a rebalance
+pub fn handler(ctx: Context<CheckReserveDrift>) -> Result<()> {
+    let drifted = ValidationUtils::check_reserve_drift(
+        &ctx.accounts.pool,
+        &ctx.accounts.reserve_asset,
+        Clock::get()?.unix_timestamp,
+    )?;
+    require!(drifted, PoolError::ReserveWithinTolerance);
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ClaimEscrowedPayout<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        constraint = pool_authority.key() == pool.pool_authority @ PoolError::InvalidAuthority
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The user's payout escrow, closed once emptied unless the pool pays
+    /// in pull mode
+    #[account(
+        mut,
+        seeds = [
+            Pool::PAYOUT_ESCROW_SEED,
+            pool.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump,
+        token::mint = pool.usdc_mint,
+        token::authority = pool_authority
+    )]
+    pub payout_escrow: Account<'info, TokenAccount>,
+
+    /// Where the user now wants the USDC
+    #[account(
+        mut,
+        token::mint = pool.usdc_mint,
+        token::authority = user
+    )]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimEscrowedPayout>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+    let clock = Clock::get()?;
+
+    let amount = ctx.accounts.payout_escrow.amount;
+    require!(amount > 0, PoolError::NoEscrowedPayout);
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payout_escrow.to_account_info(),
+                to: ctx.accounts.user_usdc_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        amount,
+    )?;
+
+    // Pull-mode escrows stay open for the next payout; otherwise the
+    // escrow's rent goes to the user it was opened for
+    if !pool.config.pull_payouts {
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.payout_escrow.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ))?;
+    }
+
+    // Anything sent to the escrow from outside the batch is not tracked
+    pool.escrowed_payouts = pool.escrowed_payouts.saturating_sub(amount);
+
+    emit!(EscrowedPayoutClaimed {
+        user: ctx.accounts.user.key(),
+        pool: pool.key(),
+        usdc_amount: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("User claimed {} USDC of escrowed payouts", amount);
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::MerkleUtils;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ClaimRebase<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Rebase being claimed from
+    #[account(
+        mut,
+        seeds = [
+            Distribution::SEED_PREFIX,
+            pool.key().as_ref(),
+            distribution.id.to_le_bytes().as_ref()
+        ],
+        bump = distribution.bump,
+        constraint = distribution.kind == DistributionKind::IptRebase @ PoolError::WrongDistributionKind
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    /// Double-claim guard: init fails if this holder already claimed
+    #[account(
+        init,
+        payer = claimant,
+        space = ClaimReceipt::LEN,
+        seeds = [
+            ClaimReceipt::SEED_PREFIX,
+            distribution.key().as_ref(),
+            claimant.key().as_ref()
+        ],
+        bump
+    )]
+    pub claim_receipt: Account<'info, ClaimReceipt>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    /// Claimant's IPT token account
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = claimant
+    )]
+    pub claimant_ipt_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimRebase>, ipt_amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = &ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+    let distribution_key = ctx.accounts.distribution.key();
+    let claimant = ctx.accounts.claimant.key();
+
+    require!(ipt_amount > 0, PoolError::InvalidAmount);
+
+    let leaf = MerkleUtils::dividend_leaf(&distribution_key, &claimant, ipt_amount);
+    require!(
+        MerkleUtils::verify(&proof, &ctx.accounts.distribution.merkle_root, leaf),
+        PoolError::InvalidMerkleProof
+    );
+
+    let distribution = &mut ctx.accounts.distribution;
+    let total_claimed = distribution
+        .claimed_amount
+        .checked_add(ipt_amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    require!(
+        total_claimed <= distribution.total_amount,
+        PoolError::DistributionOverclaimed
+    );
+    distribution.claimed_amount = total_claimed;
+
+    // Already counted in total_ipt_supply when the rebase ran
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.ipt_mint.to_account_info(),
+                to: ctx.accounts.claimant_ipt_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        ipt_amount,
+    )?;
+
+    let receipt = &mut ctx.accounts.claim_receipt;
+    receipt.distribution = distribution_key;
+    receipt.claimant = claimant;
+    receipt.amount = ipt_amount;
+    receipt.claimed_at = clock.unix_timestamp;
+    receipt.bump = ctx.bumps.claim_receipt;
+
+    emit!(RebaseClaimed {
+        claimant,
+        pool: pool.key(),
+        distribution: distribution_key,
+        ipt_amount,
+        total_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("{} claimed {} rebased IPT", claimant, ipt_amount);
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ClaimReferralEarnings<'info> {
+    pub referrer: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Referrer's record in this pool
+    #[account(
+        mut,
+        seeds = [Referral::SEED_PREFIX, pool.key().as_ref(), referrer.key().as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    /// Referrer's USDC token account
+    #[account(
+        mut,
+        token::mint = pool.usdc_mint,
+        token::authority = referrer
+    )]
+    pub referrer_usdc_account: Account<'info, TokenAccount>,
+
+    /// Pool's USDC reserve, where accrued earnings are held
+    #[account(
+        mut,
+        address = pool.usdc_reserve
+    )]
+    pub pool_usdc_reserve: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_fee_payout(pool)?;
+
+    let amount = ctx.accounts.referral.claimable;
+    require!(amount > 0, PoolError::NoReferralEarnings);
+    require!(
+        ctx.accounts.pool_usdc_reserve.amount >= amount,
+        PoolError::InsufficientReserves
+    );
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_usdc_reserve.to_account_info(),
+                to: ctx.accounts.referrer_usdc_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        amount,
+    )?;
+
+    let referral = &mut ctx.accounts.referral;
+    referral.claimable = 0;
+
+    pool.referral_payable = pool
+        .referral_payable
+        .checked_sub(amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.total_usdc_reserves = pool
+        .total_usdc_reserves
+        .checked_sub(amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+
+    emit!(ReferralEarningsClaimed {
+        referrer: referral.referrer,
+        pool: pool.key(),
+        amount,
+        total_earned: referral.total_earned,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Referrer {} claimed {} USDC", referral.referrer, amount);
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::instructions::init_pool::initialize_pool_state;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(new_pool_id: u64)]
+pub struct ClonePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool whose configuration is copied
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            source_pool.usdc_mint.as_ref(),
+            source_pool.id_seed().as_ref()
+        ],
+        bump = source_pool.bump,
+        constraint = admin.key() == source_pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = source_pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub source_pool: Box<Account<'info, Pool>>,
+
+    /// Program-wide config (fee bounds)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// USDC mint of the new pool
+    pub usdc_mint: Account<'info, anchor_spl::token::Mint>,
+
+    /// New pool state account
+    #[account(
+        init,
+        payer = admin,
+        space = Pool::LEN,
+        seeds = [
+            Pool::SEED_PREFIX,
+            usdc_mint.key().as_ref(),
+            new_pool_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClonePool>, new_pool_id: u64) -> Result<()> {
+    let mut config = ctx.accounts.source_pool.config.clone();
+
+    // The clone starts with the default queue allocation
+    config.max_queue_size = config.max_queue_size.min(Pool::MAX_QUEUE_SIZE as u32);
+
+    // Protocol fee bounds may have tightened since the source pool was created
+    ValidationUtils::validate_pool_config(&config, Pool::MAX_QUEUE_SIZE as u32)?;
+    ValidationUtils::validate_fee_bounds(&config, &ctx.accounts.protocol_config)?;
+
+    initialize_pool_state(
+        &mut ctx.accounts.pool,
+        ctx.accounts.usdc_mint.key(),
+        new_pool_id,
+        config,
+        ctx.bumps.pool,
+    )?;
+
+    emit!(PoolCloned {
+        admin: ctx.accounts.admin.key(),
+        source_pool: ctx.accounts.source_pool.key(),
+        pool: ctx.accounts.pool.key(),
+        pool_id: new_pool_id,
+        timestamp: ctx.accounts.pool.created_at,
+    });
+
+    msg!(
+        "Pool {} cloned from {}",
+        ctx.accounts.pool.key(),
+        ctx.accounts.source_pool.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseClaimReceipt<'info> {
+    /// Receipt being closed; rent goes back to the claimant who paid it
+    #[account(
+        mut,
+        seeds = [
+            ClaimReceipt::SEED_PREFIX,
+            claim_receipt.distribution.as_ref(),
+            claim_receipt.claimant.as_ref()
+        ],
+        bump = claim_receipt.bump,
+        close = claimant
+    )]
+    pub claim_receipt: Account<'info, ClaimReceipt>,
+
+    /// CHECK: Claimant the receipt belongs to
+    #[account(mut, address = claim_receipt.claimant)]
+    pub claimant: UncheckedAccount<'info>,
+
+    /// CHECK: Distribution the receipt guards; it must already be closed,
+    /// otherwise the receipt still blocks a second claim
+    #[account(
+        address = claim_receipt.distribution,
+        constraint = distribution.data_is_empty() @ PoolError::DistributionStillOpen
+    )]
+    pub distribution: UncheckedAccount<'info>,
+}
+
+/// Anyone may sweep a receipt once its distribution is gone
+pub fn handler(ctx: Context<CloseClaimReceipt>) -> Result<()> {
+    msg!(
+        "Claim receipt for {} closed",
+        ctx.accounts.claim_receipt.claimant
+    );
+
+    Ok(())
+}
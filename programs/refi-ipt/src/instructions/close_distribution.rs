@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct CloseDistribution<'info> {
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Distribution being closed; rent goes back to whoever paid it
+    #[account(
+        mut,
+        seeds = [
+            Distribution::SEED_PREFIX,
+            pool.key().as_ref(),
+            distribution.id.to_le_bytes().as_ref()
+        ],
+        bump = distribution.bump,
+        close = payer
+    )]
+    pub distribution: Box<Account<'info, Distribution>>,
+
+    /// CHECK: Original rent payer of the distribution
+    #[account(mut, address = distribution.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Payout vault of a USDC distribution (omitted for rebases)
+    #[account(mut, address = distribution.payout_vault)]
+    pub payout_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Pool's USDC reserve, which receives unclaimed USDC
+    #[account(
+        mut,
+        address = pool.usdc_reserve
+    )]
+    pub pool_usdc_reserve: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CloseDistribution>) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+    let distribution = &ctx.accounts.distribution;
+
+    require!(
+        distribution.closable(clock.unix_timestamp),
+        PoolError::DistributionStillOpen
+    );
+
+    let unclaimed_amount = distribution
+        .total_amount
+        .saturating_sub(distribution.claimed_amount);
+
+    match distribution.kind {
+        DistributionKind::Usdc => {
+            let payout_vault = ctx
+                .accounts
+                .payout_vault
+                .as_ref()
+                .ok_or(PoolError::InvalidUserAccount)?;
+            let authority_seeds = pool.authority_seeds(&pool_id_seed);
+
+            // Unclaimed USDC goes back to the pool for all holders
+            if payout_vault.amount > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: payout_vault.to_account_info(),
+                            to: ctx.accounts.pool_usdc_reserve.to_account_info(),
+                            authority: ctx.accounts.pool_authority.to_account_info(),
+                        },
+                        &[&authority_seeds],
+                    ),
+                    payout_vault.amount,
+                )?;
+            }
+
+            close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: payout_vault.to_account_info(),
+                    destination: ctx.accounts.payer.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[&authority_seeds],
+            ))?;
+
+            pool.total_usdc_reserves = pool
+                .total_usdc_reserves
+                .checked_add(payout_vault.amount)
+                .ok_or(PoolError::MathematicalOverflow)?;
+        }
+        DistributionKind::IptRebase => {
+            // Unclaimed rebase IPT was counted in the supply but never minted
+            pool.total_ipt_supply = pool
+                .total_ipt_supply
+                .checked_sub(unclaimed_amount)
+                .ok_or(PoolError::MathematicalUnderflow)?;
+        }
+    }
+
+    emit!(DistributionClosed {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        distribution: distribution.key(),
+        id: distribution.id,
+        unclaimed_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Distribution {} closed with {} unclaimed",
+        distribution.id,
+        unclaimed_amount
+    );
+
+    Ok(())
+}
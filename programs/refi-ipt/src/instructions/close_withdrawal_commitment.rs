@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseWithdrawalCommitment<'info> {
+    /// The committer, or anyone once the commitment is abandoned
+    pub closer: Signer<'info>,
+
+    /// Commitment being closed; rent goes back to the user who paid it
+    #[account(
+        mut,
+        seeds = [
+            WithdrawalCommitment::SEED_PREFIX,
+            withdrawal_commitment.pool.as_ref(),
+            withdrawal_commitment.user.as_ref()
+        ],
+        bump = withdrawal_commitment.bump,
+        close = user
+    )]
+    pub withdrawal_commitment: Account<'info, WithdrawalCommitment>,
+
+    /// CHECK: User who made the commitment
+    #[account(mut, address = withdrawal_commitment.user)]
+    pub user: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseWithdrawalCommitment>) -> Result<()> {
+    let clock = Clock::get()?;
+    let commitment = &ctx.accounts.withdrawal_commitment;
+
+    let abandoned = clock.slot.saturating_sub(commitment.committed_slot)
+        >= WithdrawalCommitment::ABANDONED_AFTER_SLOTS;
+    require!(
+        ctx.accounts.closer.key() == commitment.user || abandoned,
+        PoolError::CommitmentStillActive
+    );
+
+    msg!("Withdrawal commitment for {} closed", commitment.user);
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(cutoff_slot: u64)]
+pub struct CommitShareRegister<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The cutoff's register commitment; each cutoff is committed once
+    #[account(
+        init,
+        payer = admin,
+        space = ShareRegisterCommitment::LEN,
+        seeds = [
+            ShareRegisterCommitment::SEED_PREFIX,
+            pool.key().as_ref(),
+            cutoff_slot.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub commitment: Account<'info, ShareRegisterCommitment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CommitShareRegister>,
+    cutoff_slot: u64,
+    register_hash: [u8; 32],
+    holder_count: u32,
+    total_balance: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // The register must describe a slot that has already happened
+    require!(cutoff_slot <= clock.slot, PoolError::InvalidConfigParameter);
+    require!(register_hash != [0; 32], PoolError::InvalidConfigParameter);
+
+    let commitment = &mut ctx.accounts.commitment;
+    commitment.pool = ctx.accounts.pool.key();
+    commitment.cutoff_slot = cutoff_slot;
+    commitment.register_hash = register_hash;
+    commitment.holder_count = holder_count;
+    commitment.total_balance = total_balance;
+    commitment.committed_by = ctx.accounts.admin.key();
+    commitment.committed_at = clock.unix_timestamp;
+    commitment.bump = ctx.bumps.commitment;
+
+    emit!(ShareRegisterCommitted {
+        admin: commitment.committed_by,
+        pool: commitment.pool,
+        commitment: commitment.key(),
+        cutoff_slot,
+        register_hash,
+        holder_count,
+        total_balance,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Share register at slot {} committed over {} holders",
+        cutoff_slot,
+        holder_count
+    );
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct CommitStatement<'info> {
+    /// Admin or oracle
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = authority.key() == pool.config.admin_authority
+            || pool.is_oracle(&authority.key(), Clock::get()?.unix_timestamp)
+            @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The month's statement commitment; each month is committed once
+    #[account(
+        init,
+        payer = authority,
+        space = StatementCommitment::LEN,
+        seeds = [
+            StatementCommitment::SEED_PREFIX,
+            pool.key().as_ref(),
+            period.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub statement: Account<'info, StatementCommitment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CommitStatement>,
+    period: u32,
+    merkle_root: [u8; 32],
+    holder_count: u32,
+    total_balance: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        StatementCommitment::is_valid_period(period),
+        PoolError::InvalidStatementPeriod
+    );
+    require!(merkle_root != [0; 32], PoolError::InvalidConfigParameter);
+
+    let statement = &mut ctx.accounts.statement;
+    statement.pool = ctx.accounts.pool.key();
+    statement.period = period;
+    statement.merkle_root = merkle_root;
+    statement.holder_count = holder_count;
+    statement.total_balance = total_balance;
+    statement.committed_by = ctx.accounts.authority.key();
+    statement.committed_at = clock.unix_timestamp;
+    statement.bump = ctx.bumps.statement;
+
+    emit!(StatementCommitted {
+        committed_by: statement.committed_by,
+        pool: statement.pool,
+        statement: statement.key(),
+        period,
+        merkle_root,
+        holder_count,
+        total_balance,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Statement for {} committed over {} holders",
+        period,
+        holder_count
+    );
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CommitWithdrawal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// User's commitment; committing again replaces it and restarts the delay
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = WithdrawalCommitment::LEN,
+        seeds = [
+            WithdrawalCommitment::SEED_PREFIX,
+            pool.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub withdrawal_commitment: Account<'info, WithdrawalCommitment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CommitWithdrawal>, commitment: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = &ctx.accounts.pool;
+
+    let revealable_slot = clock
+        .slot
+        .saturating_add(pool.config.reveal_delay_slots);
+
+    let withdrawal_commitment = &mut ctx.accounts.withdrawal_commitment;
+    withdrawal_commitment.pool = pool.key();
+    withdrawal_commitment.user = ctx.accounts.user.key();
+    withdrawal_commitment.commitment = commitment;
+    withdrawal_commitment.committed_slot = clock.slot;
+    withdrawal_commitment.bump = ctx.bumps.withdrawal_commitment;
+
+    emit!(WithdrawalCommitted {
+        user: ctx.accounts.user.key(),
+        pool: pool.key(),
+        commitment,
+        revealable_slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Withdrawal committed, revealable at slot {}", revealable_slot);
+
+    Ok(())
+}
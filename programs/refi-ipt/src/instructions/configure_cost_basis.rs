@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureCostBasis<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// User's cost-basis ledger, opened on first use
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = CostBasisLedger::LEN,
+        seeds = [CostBasisLedger::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub cost_basis: Account<'info, CostBasisLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Open the ledger or change how withdrawals draw down its lots; lots
+/// already open keep their basis
+pub fn handler(ctx: Context<ConfigureCostBasis>, method: LotMethod) -> Result<()> {
+    let ledger = &mut ctx.accounts.cost_basis;
+    if ledger.owner == Pubkey::default() {
+        ledger.pool = ctx.accounts.pool.key();
+        ledger.owner = ctx.accounts.user.key();
+        ledger.bump = ctx.bumps.cost_basis;
+    }
+    ledger.method = method;
+
+    emit!(CostBasisLedgerConfigured {
+        owner: ledger.owner,
+        pool: ledger.pool,
+        method,
+        open_lots: ledger.lots.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Cost-basis ledger uses {:?} with {} open lots",
+        method,
+        ledger.lots.len()
+    );
+
+    Ok(())
+}
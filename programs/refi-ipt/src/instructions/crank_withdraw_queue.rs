@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::instructions::process_queue::{batch_execute_withdraw, BatchExecuteWithdraw, MAX_BATCH_SIZE};
+use anchor_lang::prelude::*;
+
+pub fn handler<'info>(
+    mut ctx: Context<'_, '_, 'info, 'info, BatchExecuteWithdraw<'info>>,
+) -> Result<Vec<Pubkey>> {
+    // Amounts come from the queue itself; the crank only supplies accounts,
+    // the batch checks they pair up with the entries
+    let amounts: Vec<u64> = ctx
+        .accounts
+        .pool
+        .queued()
+        .iter()
+        .take(ctx.remaining_accounts.len() / 2)
+        .map(|pending| pending.amount)
+        .collect();
+
+    if amounts.is_empty() {
+        msg!("Withdrawal queue crank: nothing to execute");
+    } else {
+        batch_execute_withdraw(&mut ctx, amounts)?;
+    }
+
+    // Users whose token accounts the next crank should supply
+    Ok(ctx
+        .accounts
+        .pool
+        .queued()
+        .iter()
+        .take(MAX_BATCH_SIZE)
+        .map(|pending| pending.user)
+        .collect())
+}
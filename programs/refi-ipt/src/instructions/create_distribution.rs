@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct CreateDistribution<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// New distribution (one PDA per id)
+    #[account(
+        init,
+        payer = admin,
+        space = Distribution::LEN,
+        seeds = [
+            Distribution::SEED_PREFIX,
+            pool.key().as_ref(),
+            pool.next_distribution_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub distribution: Box<Account<'info, Distribution>>,
+
+    /// Vault the payout is claimed from
+    #[account(
+        init,
+        payer = admin,
+        token::mint = usdc_mint,
+        token::authority = pool_authority,
+        seeds = [
+            Distribution::VAULT_SEED_PREFIX,
+            distribution.key().as_ref()
+        ],
+        bump
+    )]
+    pub payout_vault: Account<'info, TokenAccount>,
+
+    /// USDC mint
+    #[account(address = pool.usdc_mint @ PoolError::InvalidUsdcMint)]
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Admin's USDC token account funding the payout
+    #[account(
+        mut,
+        token::mint = pool.usdc_mint,
+        token::authority = admin
+    )]
+    pub admin_usdc_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateDistribution>,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    snapshot_slot: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(total_amount > 0, PoolError::ZeroAmountNotAllowed);
+    require!(
+        ctx.accounts.admin_usdc_account.amount >= total_amount,
+        PoolError::InsufficientAccountBalance
+    );
+    require!(snapshot_slot <= clock.slot, PoolError::InvalidConfigParameter);
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.admin_usdc_account.to_account_info(),
+                to: ctx.accounts.payout_vault.to_account_info(),
+                authority: ctx.accounts.admin.to_account_info(),
+            },
+        ),
+        total_amount,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    let id = pool.next_distribution_id;
+    pool.next_distribution_id = id.checked_add(1).ok_or(PoolError::MathematicalOverflow)?;
+
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.pool = pool.key();
+    distribution.id = id;
+    distribution.kind = DistributionKind::Usdc;
+    distribution.payout_vault = ctx.accounts.payout_vault.key();
+    distribution.merkle_root = merkle_root;
+    distribution.snapshot_slot = snapshot_slot;
+    distribution.total_amount = total_amount;
+    distribution.claimed_amount = 0;
+    distribution.created_at = clock.unix_timestamp;
+    distribution.payer = ctx.accounts.admin.key();
+    distribution.bump = ctx.bumps.distribution;
+
+    emit!(DistributionCreated {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        distribution: distribution.key(),
+        id,
+        total_amount,
+        snapshot_slot,
+        merkle_root,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Distribution {} funded with {} USDC (snapshot slot {})",
+        id,
+        total_amount,
+        snapshot_slot
+    );
+
+    Ok(())
+}
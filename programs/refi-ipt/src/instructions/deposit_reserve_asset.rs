@@ -0,0 +1,270 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::{CalculationUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct DepositReserveAsset<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The asset being deposited
+    #[account(
+        mut,
+        seeds = [
+            ReserveAsset::SEED_PREFIX,
+            pool.key().as_ref(),
+            reserve_asset.mint.as_ref()
+        ],
+        bump = reserve_asset.bump
+    )]
+    pub reserve_asset: Account<'info, ReserveAsset>,
+
+    /// User's token account for the asset
+    #[account(
+        mut,
+        token::mint = reserve_asset.mint,
+        token::authority = user
+    )]
+    pub user_asset_account: Account<'info, TokenAccount>,
+
+    /// User's IPT token account
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = user
+    )]
+    pub user_ipt_account: Account<'info, TokenAccount>,
+
+    /// Pool's reserve account for the asset
+    #[account(
+        mut,
+        address = reserve_asset.vault
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    /// CHECK: Depositor's access list entry PDA, which need not exist;
+    /// required while the pool has an allowlist, blocked addresses or
+    /// investor tiers
+    #[account(
+        seeds = [AccessListEntry::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_entry: Option<UncheckedAccount<'info>>,
+
+    /// Depositor's position, opened by their first user_deposit; required
+    /// while their tier has a deposit cap
+    #[account(
+        mut,
+        seeds = [Position::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(
+    ctx: Context<DepositReserveAsset>,
+    asset_amount: u64,
+    min_ipt_amount: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let asset = &mut ctx.accounts.reserve_asset;
+    let pool_id_seed = pool.id_seed();
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_protocol_not_paused(&ctx.accounts.protocol_config)?;
+    ValidationUtils::validate_feature_enabled(
+        &ctx.accounts.protocol_config,
+        Feature::ReserveAssetDeposits,
+    )?;
+    ValidationUtils::validate_pool_operation(pool, PauseFlag::Deposits)?;
+    ValidationUtils::validate_oracle_fresh(pool, clock.unix_timestamp)?;
+    ValidationUtils::validate_access(pool, ctx.accounts.access_entry.as_deref())?;
+    let tier = ValidationUtils::investor_tier(pool, ctx.accounts.access_entry.as_deref())?;
+    require!(
+        !asset.is_price_stale(pool.config.max_heartbeat_secs, clock.unix_timestamp),
+        PoolError::StaleAssetPrice
+    );
+
+    require!(asset_amount > 0, PoolError::InvalidAmount);
+    // Slot-stable quotes price the whole slot at its first-read rate
+    let exchange_rate = pool.pin_quote_rate(clock.slot);
+    require!(
+        ctx.accounts.user_asset_account.amount >= asset_amount,
+        PoolError::InsufficientAccountBalance
+    );
+
+    // Value the deposit in USDC; the fee stays in the reserve in kind and
+    // is owed to the fee collector at that USDC value
+    let usdc_value =
+        CalculationUtils::asset_value(asset_amount, asset.price, pool.usdc_decimals, asset.decimals)?;
+    ValidationUtils::validate_tier_deposit(
+        &pool.config,
+        tier,
+        ctx.accounts.position.as_deref(),
+        usdc_value,
+    )?;
+    let deposit_fee =
+        CalculationUtils::calculate_fee(usdc_value, pool.config.tier_deposit_fee_bps(tier, 0))?;
+    let net_usdc_value = usdc_value
+        .checked_sub(deposit_fee)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    let (ipt_amount, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+        net_usdc_value,
+        exchange_rate,
+        0,
+        pool.usdc_decimals,
+        pool.ipt_decimals,
+    )?;
+    require!(ipt_amount > 0, PoolError::InvalidAmount);
+    require!(ipt_amount >= min_ipt_amount, PoolError::SlippageExceeded);
+
+    // Same supply and TVL caps as USDC deposits
+    let new_total_supply = pool
+        .total_ipt_supply
+        .checked_add(ipt_amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    require!(
+        pool.max_total_supply == 0 || new_total_supply <= pool.max_total_supply,
+        PoolError::MaxTotalSupplyExceeded
+    );
+    if pool.tvl_cap > 0 {
+        let new_nav = pool
+            .nav()?
+            .checked_add(net_usdc_value)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        require!(new_nav <= pool.tvl_cap, PoolError::TvlCapExceeded);
+    }
+
+    // Keep the asset within its share of total reserves
+    let new_balance = asset
+        .balance
+        .checked_add(asset_amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    let new_value =
+        CalculationUtils::asset_value(new_balance, asset.price, pool.usdc_decimals, asset.decimals)?;
+    let total_value = pool
+        .total_reserve_value()?
+        .checked_sub(asset.value)
+        .and_then(|v| v.checked_add(new_value))
+        .ok_or(PoolError::MathematicalOverflow)?;
+    require!(
+        asset.within_target(new_value, total_value, pool.config.reserve_drift_tolerance_bps),
+        PoolError::AssetWeightExceeded
+    );
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_asset_account.to_account_info(),
+                to: ctx.accounts.asset_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        asset_amount,
+    )?;
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.ipt_mint.to_account_info(),
+                to: ctx.accounts.user_ipt_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        ipt_amount,
+    )?;
+
+    pool.total_ipt_supply = new_total_supply;
+    pool.revalue_reserve_asset(asset.value, new_value)?;
+    asset.balance = new_balance;
+    asset.value = new_value;
+    pool.credit_fees(FeeBucket::Deposit, deposit_fee)?;
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        position.touch(clock.unix_timestamp);
+        position.record_entry(ipt_amount, exchange_rate, usdc_value)?;
+    }
+
+    let certificate_number = pool.issue_certificate()?;
+    emit!(ReserveAssetDeposited {
+        user: ctx.accounts.user.key(),
+        pool: pool.key(),
+        mint: asset.mint,
+        asset_amount,
+        usdc_value,
+        ipt_amount,
+        deposit_fee,
+        price: asset.price,
+        exchange_rate,
+        certificate_number,
+        timestamp: clock.unix_timestamp,
+    });
+
+    ValidationUtils::check_reserve_drift(pool, asset, clock.unix_timestamp)?;
+
+    if pool.close_subscriptions_if_full() {
+        emit!(SubscriptionsClosed {
+            pool: pool.key(),
+            total_ipt_supply: pool.total_ipt_supply,
+            max_total_supply: pool.max_total_supply,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    msg!(
+        "User deposited {} of {} (worth {} USDC), received {} IPT",
+        asset_amount,
+        asset.mint,
+        usdc_value,
+        ipt_amount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct EmergencyPause<'info> {
+    /// Pool admin or guardian
+    pub authority: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.config.can_emergency_pause(&authority.key()) @ PoolError::UnauthorizedGuardian,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Set every pause flag; lifting them is left to the admin (set_pause_flag)
+pub fn handler(ctx: Context<EmergencyPause>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let old_flags = pool.pause_flags;
+    pool.pause_flags = PauseFlag::ALL;
+
+    emit!(PauseFlagsUpdated {
+        admin: ctx.accounts.authority.key(),
+        pool: pool.key(),
+        old_flags,
+        new_flags: pool.pause_flags,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(EmergencyPaused {
+        authority: ctx.accounts.authority.key(),
+        pool: pool.key(),
+        old_flags,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Emergency pause: all operations halted");
+
+    Ok(())
+}
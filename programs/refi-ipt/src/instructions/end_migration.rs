@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct EndMigration<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<EndMigration>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.pool_state == PoolState::Migration,
+        PoolError::PoolNotInMigration
+    );
+
+    let total_minted = pool.migration_minted;
+
+    // Close the migration window so no further admin mints can land
+    pool.pool_state = PoolState::Active;
+    pool.migration_mint_cap = 0;
+
+    emit!(MigrationEnded {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        total_minted,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Pool migration ended, {} IPT minted", total_minted);
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct EscheatPosition<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Pool authority (PDA), the holder's delegate for escheated IPT
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Scheduled EscheatPosition operation (consumed on execution)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            timelock_operation.id.to_le_bytes().as_ref()
+        ],
+        bump = timelock_operation.bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+
+    /// Dormant holder's position
+    #[account(
+        seeds = [Position::SEED_PREFIX, pool.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Holder's IPT token account
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = position.owner
+    )]
+    pub holder_ipt_account: Account<'info, TokenAccount>,
+
+    /// Escheatment custody account named in the scheduled operation
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint
+    )]
+    pub custody_ipt_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<EscheatPosition>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let operation = &ctx.accounts.timelock_operation;
+    let position = &ctx.accounts.position;
+    let pool_id_seed = pool.id_seed();
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_timelock_elapsed(operation, clock.unix_timestamp)?;
+
+    let AdminAction::EscheatPosition {
+        owner,
+        amount,
+        custody,
+    } = operation.action.clone()
+    else {
+        return err!(PoolError::TimelockActionMismatch);
+    };
+    require_keys_eq!(position.owner, owner, PoolError::InvalidUserAccount);
+    require_keys_eq!(
+        ctx.accounts.custody_ipt_account.key(),
+        custody,
+        PoolError::EscheatCustodyMismatch
+    );
+
+    // Any activity since the flag, including during the timelock, lifts it
+    require!(
+        position.dormant_flagged_at != 0
+            && position.is_dormant(pool.config.dormancy_period_secs, clock.unix_timestamp),
+        PoolError::PositionNotDormant
+    );
+
+    // IPT can only move with the holder's standing delegation to the pool
+    let holder = &ctx.accounts.holder_ipt_account;
+    require!(
+        holder.delegate.contains(&ctx.accounts.pool_authority.key())
+            && holder.delegated_amount >= amount,
+        PoolError::EscheatmentNotAuthorized
+    );
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: holder.to_account_info(),
+                to: ctx.accounts.custody_ipt_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        amount,
+    )?;
+
+    emit!(PositionEscheated {
+        admin: ctx.accounts.admin.key(),
+        owner,
+        pool: pool.key(),
+        operation_id: operation.id,
+        amount,
+        custody: ctx.accounts.custody_ipt_account.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Escheated {} IPT of dormant holder {} to custody",
+        amount,
+        owner
+    );
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    pub recovery_authority: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.config.recovery_authority != Pubkey::default()
+            && recovery_authority.key() == pool.config.recovery_authority
+            @ PoolError::UnauthorizedRecoveryAuthority,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<ExecuteRecovery>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let new_admin = pool.pending_recovery_admin;
+    require!(new_admin != Pubkey::default(), PoolError::NoPendingRecovery);
+    require!(
+        clock.unix_timestamp >= pool.recovery_executable_at,
+        PoolError::RecoveryDelayActive
+    );
+
+    let old_admin = pool.config.admin_authority;
+    let old_oracle = pool.config.oracle_authority;
+    let old_fee_collector = pool.config.fee_collector;
+
+    let mut new_config = pool.config.clone();
+    new_config.admin_authority = new_admin;
+    if pool.pending_recovery_oracle != Pubkey::default() {
+        new_config.oracle_authority = pool.pending_recovery_oracle;
+    }
+    if pool.pending_recovery_fee_collector != Pubkey::default() {
+        new_config.fee_collector = pool.pending_recovery_fee_collector;
+    }
+    ValidationUtils::validate_pool_config(&new_config, pool.required_queue_capacity())?;
+
+    // A replaced oracle key is presumed lost with the admin, so it gets no
+    // overlap window, and handovers proposed by the old admin are dropped
+    if new_config.oracle_authority != old_oracle {
+        pool.previous_oracle_authority = Pubkey::default();
+        pool.oracle_overlap_ends_at = 0;
+    }
+    pool.pending_oracle_authority = Pubkey::default();
+    pool.pending_admin = Pubkey::default();
+    pool.config = new_config;
+
+    pool.pending_recovery_admin = Pubkey::default();
+    pool.pending_recovery_oracle = Pubkey::default();
+    pool.pending_recovery_fee_collector = Pubkey::default();
+    pool.recovery_executable_at = 0;
+
+    emit!(EmergencyRecoveryExecuted {
+        recovery_authority: ctx.accounts.recovery_authority.key(),
+        pool: pool.key(),
+        old_admin,
+        new_admin,
+        old_oracle,
+        new_oracle: pool.config.oracle_authority,
+        old_fee_collector,
+        new_fee_collector: pool.config.fee_collector,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "EMERGENCY RECOVERY executed: admin {} replaced by {}",
+        old_admin,
+        new_admin
+    );
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(new_max_queue_size: u32)]
+pub struct ExpandQueue<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account (grown in place, rent paid by admin)
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        // Checked ahead of the realloc, which the runtime would otherwise
+        // reject with a bare InvalidRealloc
+        constraint = new_max_queue_size as usize
+            <= pool.queue_capacity as usize + Pool::MAX_QUEUE_GROWTH @ PoolError::QueueGrowthTooLarge,
+        realloc = Pool::space(new_max_queue_size.max(pool.queue_capacity) as usize),
+        realloc::payer = admin,
+        realloc::zero = false,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExpandQueue>, new_max_queue_size: u32) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let old_max_queue_size = pool.config.max_queue_size;
+
+    require!(
+        new_max_queue_size > old_max_queue_size
+            && new_max_queue_size as usize <= Pool::MAX_QUEUE_CAPACITY,
+        PoolError::InvalidQueueSize
+    );
+
+    // Capacity never shrinks, so the cap can later be lowered and raised again for free
+    pool.queue_capacity = pool.queue_capacity.max(new_max_queue_size);
+    pool.config.max_queue_size = new_max_queue_size;
+
+    emit!(QueueExpanded {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        old_max_queue_size,
+        new_max_queue_size,
+        queue_capacity: pool.queue_capacity,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Queue size raised from {} to {} (capacity {})",
+        old_max_queue_size,
+        new_max_queue_size,
+        pool.queue_capacity
+    );
+
+    Ok(())
+}
@@ -3,7 +3,9 @@
 use crate::errors::PoolError;
 use crate::events::*;
 use crate::states::*;
+use crate::utils::{TokenUtils, ValidationUtils};
 use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
 use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
@@ -16,10 +18,12 @@ pub struct FeeCollectorWithdraw<'info> {
         mut,
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump,
-        constraint = fee_collector.key() == pool.config.fee_collector @ PoolError::UnauthorizedFeeCollector
+        constraint = fee_collector.key() == pool.config.fee_collector @ PoolError::UnauthorizedFeeCollector,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
 
@@ -27,7 +31,8 @@ pub struct FeeCollectorWithdraw<'info> {
     #[account(
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump
     )]
@@ -49,18 +54,29 @@ pub struct FeeCollectorWithdraw<'info> {
     pub pool_usdc_reserve: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
+
+    /// SPL Memo program, needed only when a memo is attached
+    pub memo_program: Option<Program<'info, Memo>>,
 }
 
-pub fn handler(ctx: Context<FeeCollectorWithdraw>, amount: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<FeeCollectorWithdraw>,
+    amount: u64,
+    bucket: FeeBucket,
+    memo: Option<String>,
+) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
     let clock = Clock::get()?;
 
+    ValidationUtils::validate_fee_payout(pool)?;
+
     // Validate amount
     require!(amount > 0, PoolError::ZeroAmountNotAllowed);
 
-    // Check if there are enough accumulated fees
+    // Check if there are enough accumulated fees in the requested stream
     require!(
-        pool.total_accumulated_fees >= amount,
+        pool.fee_balance(bucket) >= amount,
         PoolError::InsufficientAccumulatedFees
     );
 
@@ -79,34 +95,39 @@ pub fn handler(ctx: Context<FeeCollectorWithdraw>, amount: u64) -> Result<()> {
                 to: ctx.accounts.fee_collector_usdc_account.to_account_info(),
                 authority: ctx.accounts.pool_authority.to_account_info(),
             },
-            &[&pool.authority_seeds()],
+            &[&pool.authority_seeds(&pool_id_seed)],
         ),
         amount,
     )?;
 
     // Update pool state
-    pool.total_accumulated_fees = pool
-        .total_accumulated_fees
-        .checked_sub(amount)
-        .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.debit_fees(bucket, amount)?;
 
     pool.total_usdc_reserves = pool
         .total_usdc_reserves
         .checked_sub(amount)
         .ok_or(PoolError::MathematicalUnderflow)?;
 
+    // Reference for bank-side reconciliation
+    TokenUtils::attach_memo(ctx.accounts.memo_program.as_ref(), memo.as_ref())?;
+
     // Emit event
     emit!(FeeCollectorWithdrawExecuted {
         fee_collector: ctx.accounts.fee_collector.key(),
         pool: pool.key(),
+        bucket,
         amount,
         remaining_accumulated_fees: pool.total_accumulated_fees,
+        remaining_deposit_fees: pool.accumulated_deposit_fees,
+        remaining_withdrawal_fees: pool.accumulated_withdrawal_fees,
+        remaining_management_fees: pool.accumulated_management_fees,
         timestamp: clock.unix_timestamp,
     });
 
     msg!(
-        "Fee collector withdrew {} USDC. Remaining accumulated fees: {}",
+        "Fee collector withdrew {} USDC of {:?} fees. Remaining accumulated fees: {}",
         amount,
+        bucket,
         pool.total_accumulated_fees
     );
 
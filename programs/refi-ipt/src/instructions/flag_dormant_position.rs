@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct FlagDormantPosition<'info> {
+    /// Anyone may flag a dormant position
+    pub caller: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Position being flagged
+    #[account(
+        mut,
+        seeds = [Position::SEED_PREFIX, pool.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+}
+
+pub fn handler(ctx: Context<FlagDormantPosition>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    require!(
+        position.dormant_flagged_at == 0,
+        PoolError::PositionAlreadyFlagged
+    );
+    require!(
+        position.is_dormant(
+            ctx.accounts.pool.config.dormancy_period_secs,
+            clock.unix_timestamp
+        ),
+        PoolError::PositionNotDormant
+    );
+
+    // The flag is the owner's notice; any activity from them clears it
+    position.dormant_flagged_at = clock.unix_timestamp;
+
+    emit!(PositionFlaggedDormant {
+        owner: position.owner,
+        pool: ctx.accounts.pool.key(),
+        caller: ctx.accounts.caller.key(),
+        last_activity_at: position.last_activity_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Position of {} flagged dormant, inactive since {}",
+        position.owner,
+        position.last_activity_at
+    );
+
+    Ok(())
+}
@@ -1,15 +1,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::errors::PoolError;
 use crate::events::*;
 use crate::states::*;
 use crate::utils::ValidationUtils;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(pool_id: u64)]
 pub struct InitializePool<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    /// Program-wide config (fee bounds)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// USDC mint
     pub usdc_mint: Account<'info, anchor_spl::token::Mint>,
 
@@ -20,7 +29,8 @@ pub struct InitializePool<'info> {
         space = Pool::LEN,
         seeds = [
             Pool::SEED_PREFIX,
-            usdc_mint.key().as_ref()
+            usdc_mint.key().as_ref(),
+            pool_id.to_le_bytes().as_ref()
         ],
         bump
     )]
@@ -29,20 +39,42 @@ pub struct InitializePool<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<InitializePool>, config: PoolConfig) -> Result<()> {
-    ValidationUtils::validate_pool_config(&config)?;
+pub fn handler(ctx: Context<InitializePool>, pool_id: u64, config: PoolConfig) -> Result<()> {
+    ValidationUtils::validate_pool_config(&config, Pool::MAX_QUEUE_SIZE as u32)?;
+    ValidationUtils::validate_fee_bounds(&config, &ctx.accounts.protocol_config)?;
+
+    initialize_pool_state(
+        &mut ctx.accounts.pool,
+        ctx.accounts.usdc_mint.key(),
+        pool_id,
+        config,
+        ctx.bumps.pool,
+    )
+}
+
+/// Populate a freshly created pool account (shared with clone_pool)
+pub fn initialize_pool_state(
+    pool: &mut Account<Pool>,
+    usdc_mint: Pubkey,
+    pool_id: u64,
+    config: PoolConfig,
+    bump: u8,
+) -> Result<()> {
+    require!(pool_id != Pool::LEGACY_POOL_ID, PoolError::ReservedPoolId);
 
-    let pool = &mut ctx.accounts.pool;
     let clock = Clock::get()?;
     
     // Pool authority is the same as pool PDA (derived from same seeds)
     let pool_authority = pool.key();
 
     // Initialize pool state
+    pool.layout_version = Pool::LAYOUT_VERSION;
     pool.pool_authority = pool_authority;
-    pool.usdc_mint = ctx.accounts.usdc_mint.key();
+    pool.usdc_mint = usdc_mint;
+    pool.pool_id = pool_id;
     pool.ipt_mint = Pubkey::default();
     pool.usdc_reserve = Pubkey::default(); 
+    pool.queue_capacity = Pool::MAX_QUEUE_SIZE as u32;
 
     // Set initial exchange rate
     pool.current_exchange_rate = config.initial_exchange_rate;
@@ -50,30 +82,84 @@ pub fn handler(ctx: Context<InitializePool>, config: PoolConfig) -> Result<()> {
     // Initialize counters
     pool.total_ipt_supply = 0;
     pool.max_total_supply = config.max_total_supply;
+    pool.tvl_cap = 0;
     pool.total_usdc_reserves = 0;
     pool.total_accumulated_fees = 0;
+    pool.accumulated_deposit_fees = 0;
+    pool.accumulated_withdrawal_fees = 0;
+    pool.accumulated_management_fees = 0;
+    pool.next_timelock_id = 0;
+    pool.next_distribution_id = 0;
+    pool.migration_mint_cap = 0;
+    pool.migration_minted = 0;
+    pool.current_epoch = 0;
+    pool.last_epoch_nav = 0;
+    pool.redemption_epoch = u64::MAX;
+    pool.epoch_redemption_limit = 0;
+    pool.epoch_redeemed_usdc = 0;
+    pool.admin_withdrawal_epoch = 0;
+    pool.epoch_admin_withdrawn = 0;
+    pool.oracle_publisher = Pubkey::default();
+    pool.last_oracle_nonce = 0;
+    pool.rate_feed_id = [0; 32];
+    pool.pending_admin = Pubkey::default();
+    pool.pending_oracle_authority = Pubkey::default();
+    pool.previous_oracle_authority = Pubkey::default();
+    pool.oracle_overlap_ends_at = 0;
+    pool.total_buyback_ipt = 0;
+    pool.total_buyback_usdc = 0;
+    pool.promo_budget = 0;
+    pool.promo_spent = 0;
+    pool.depositor_count = 0;
+    pool.certificates_issued = 0;
+    pool.referral_payable = 0;
+    pool.blocked_count = 0;
+    pool.pending_recovery_admin = Pubkey::default();
+    pool.pending_recovery_oracle = Pubkey::default();
+    pool.pending_recovery_fee_collector = Pubkey::default();
+    pool.recovery_executable_at = 0;
+    pool.rate_evidence_hash = [0; 32];
+    pool.accrued_yield = 0;
+    pool.quote_rate_slot = 0;
+    pool.quote_rate = 0;
+    pool.reserve_asset_count = 0;
+    pool.asset_reserves_value = 0;
+    pool.wind_down_stage = 0;
+    pool.wind_down_started_at = 0;
+    pool.escrowed_payouts = 0;
+    pool.queue_alert_raised = false;
+    pool.queue_reserved_usdc = 0;
+    pool.swept_usdc = 0;
+    pool.name = String::new();
+    pool.description_uri = String::new();
+    pool.logo_uri = String::new();
 
     // Set configuration
     pool.config = config.clone();
 
     // Set initial state
     pool.pool_state = PoolState::Active;
+    pool.pause_flags = 0;
 
     // Set timestamps
     pool.last_rate_update = clock.unix_timestamp;
+    pool.last_rate_observed_at = clock.unix_timestamp;
     pool.created_at = clock.unix_timestamp;
+    pool.epoch_started_at = clock.unix_timestamp;
 
     // Set bump
-    pool.bump = ctx.bumps.pool;
+    pool.bump = bump;
     pool.pending_queue = Vec::new();
+    pool.queue_head = 0;
 
     // Emit event
     emit!(PoolInitialized {
         pool: pool.key(),
+        pool_id,
         admin_authority: config.admin_authority,
         oracle_authority: config.oracle_authority,
         fee_collector: config.fee_collector,
-        usdc_mint: ctx.accounts.usdc_mint.key(),
+        usdc_mint,
         ipt_mint: Pubkey::default(),
         initial_exchange_rate: config.initial_exchange_rate,
         timestamp: clock.unix_timestamp,
@@ -85,4 +171,4 @@ pub fn handler(ctx: Context<InitializePool>, config: PoolConfig) -> Result<()> {
     );
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -2,15 +2,30 @@
 
 use crate::errors::PoolError;
 use crate::states::*;
+use crate::utils::CalculationUtils;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 #[derive(Accounts)]
+#[instruction(ipt_decimals: u8)]
 pub struct InitializePoolStep2<'info> {
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
 
-    #[account(mut)]
+    /// Pool state account (step 2 may only run once)
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.ipt_mint == Pubkey::default()
+            && pool.usdc_reserve == Pubkey::default() @ PoolError::PoolAlreadyInitialized,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
     pub pool: Box<Account<'info, Pool>>,
 
     /// CHECK: Pool authority (PDA)
@@ -19,14 +34,15 @@ pub struct InitializePoolStep2<'info> {
     )]
     pub pool_authority: UncheckedAccount<'info>,
 
-    /// USDC mint (to get decimals)
+    /// USDC mint
+    #[account(address = pool.usdc_mint @ PoolError::InvalidUsdcMint)]
     pub usdc_mint: Account<'info, Mint>,
 
     /// IPT mint
     #[account(
         init,
-        payer = payer,
-        mint::decimals = usdc_mint.decimals,
+        payer = admin,
+        mint::decimals = ipt_decimals,
         mint::authority = pool_authority,
         seeds = [
             b"ipt_mint",
@@ -39,7 +55,7 @@ pub struct InitializePoolStep2<'info> {
     /// USDC reserve token account
     #[account(
         init,
-        payer = payer,
+        payer = admin,
         token::mint = usdc_mint,
         token::authority = pool_authority,
         seeds = [
@@ -54,23 +70,31 @@ pub struct InitializePoolStep2<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<InitializePoolStep2>) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
-
-    // Derive PDA addresses and update pool
-    let (ipt_mint, _) = Pubkey::find_program_address(
-        &[b"ipt_mint", pool.key().as_ref()],
-        ctx.program_id,
-    );
-    let (usdc_reserve, _) = Pubkey::find_program_address(
-        &[b"usdc_reserve", pool.key().as_ref()],
-        ctx.program_id,
+pub fn handler(ctx: Context<InitializePoolStep2>, ipt_decimals: u8) -> Result<()> {
+    let usdc_decimals = ctx.accounts.usdc_mint.decimals;
+    require!(
+        usdc_decimals <= CalculationUtils::MAX_DECIMALS
+            && ipt_decimals <= CalculationUtils::MAX_DECIMALS,
+        PoolError::InvalidDecimals
     );
 
+    // Both accounts were created at their PDAs by the seeds constraints above
+    let ipt_mint = ctx.accounts.ipt_mint.key();
+    let usdc_reserve = ctx.accounts.usdc_reserve.key();
+
+    let pool = &mut ctx.accounts.pool;
+
     pool.ipt_mint = ipt_mint;
     pool.usdc_reserve = usdc_reserve;
+    pool.usdc_decimals = usdc_decimals;
+    pool.ipt_decimals = ipt_decimals;
 
-    msg!("Pool mints initialized (step 2) - IPT: {}, Reserve: {}", ipt_mint, usdc_reserve);
+    msg!(
+        "Pool mints initialized (step 2) - IPT: {} ({} decimals), Reserve: {}",
+        ipt_mint,
+        ipt_decimals,
+        usdc_reserve
+    );
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::program::RefiIpt;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitProtocolConfig<'info> {
+    /// Program upgrade authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program-wide config account
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolConfig::LEN,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// This program (used to locate its program data account)
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+    )]
+    pub program: Program<'info, RefiIpt>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ PoolError::UnauthorizedProtocolAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitProtocolConfig>,
+    protocol_admin: Pubkey,
+    max_deposit_fee_bps: u16,
+    max_withdrawal_fee_bps: u16,
+    max_management_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        protocol_admin != Pubkey::default(),
+        PoolError::InvalidAuthority
+    );
+    ValidationUtils::validate_protocol_fee_bounds(
+        max_deposit_fee_bps,
+        max_withdrawal_fee_bps,
+        max_management_fee_bps,
+    )?;
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let clock = Clock::get()?;
+
+    protocol_config.protocol_admin = protocol_admin;
+    protocol_config.max_deposit_fee_bps = max_deposit_fee_bps;
+    protocol_config.max_withdrawal_fee_bps = max_withdrawal_fee_bps;
+    protocol_config.max_management_fee_bps = max_management_fee_bps;
+    protocol_config.is_paused = false;
+    protocol_config.disabled_features = Feature::SHIPPED_DARK;
+    protocol_config.bump = ctx.bumps.protocol_config;
+
+    emit!(ProtocolConfigInitialized {
+        protocol_admin,
+        max_deposit_fee_bps,
+        max_withdrawal_fee_bps,
+        max_management_fee_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Protocol config initialized with admin: {}", protocol_admin);
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    pub recovery_authority: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.config.recovery_authority != Pubkey::default()
+            && recovery_authority.key() == pool.config.recovery_authority
+            @ PoolError::UnauthorizedRecoveryAuthority,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(
+    ctx: Context<InitiateRecovery>,
+    new_admin: Pubkey,
+    new_oracle: Pubkey,
+    new_fee_collector: Pubkey,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.pending_recovery_admin == Pubkey::default(),
+        PoolError::RecoveryAlreadyPending
+    );
+    require!(new_admin != Pubkey::default(), PoolError::InvalidConfigParameter);
+
+    // The delay gives a live admin time to notice and cancel
+    let executable_at = clock
+        .unix_timestamp
+        .checked_add(PoolConfig::RECOVERY_DELAY)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    pool.pending_recovery_admin = new_admin;
+    pool.pending_recovery_oracle = new_oracle;
+    pool.pending_recovery_fee_collector = new_fee_collector;
+    pool.recovery_executable_at = executable_at;
+
+    emit!(EmergencyRecoveryInitiated {
+        recovery_authority: ctx.accounts.recovery_authority.key(),
+        pool: pool.key(),
+        current_admin: pool.config.admin_authority,
+        new_admin,
+        new_oracle,
+        new_fee_collector,
+        executable_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "EMERGENCY RECOVERY initiated: admin {} is to be replaced by {} at {}",
+        pool.config.admin_authority,
+        new_admin,
+        executable_at
+    );
+
+    Ok(())
+}
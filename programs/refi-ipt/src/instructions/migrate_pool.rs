@@ -0,0 +1,372 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::Mint;
+
+/// Rewrites raw pool data from layout `from` to `from + 1`, including the
+/// version field
+pub struct PoolMigration {
+    pub from: u16,
+    pub migrate: fn(&mut Vec<u8>, &MigrationInputs) -> Result<()>,
+}
+
+/// Facts a step may need that the old layout does not record
+pub struct MigrationInputs {
+    /// Decimals of the pool's USDC mint (and of its IPT mint, which
+    /// init_pool_step2 creates with the same decimals)
+    pub usdc_decimals: u8,
+}
+
+/// One step per breaking Pool layout change, applied in order
+pub const POOL_MIGRATIONS: &[PoolMigration] = &[PoolMigration {
+    from: 0,
+    migrate: migrate_from_v0,
+}];
+
+#[derive(AnchorDeserialize)]
+struct PendingWithdrawV0 {
+    user: Pubkey,
+    amount: u64,
+    min_usdc_amount: u64,
+}
+
+#[derive(AnchorDeserialize)]
+struct PoolConfigV0 {
+    admin_authority: Pubkey,
+    oracle_authority: Pubkey,
+    fee_collector: Pubkey,
+    deposit_fee_bps: u16,
+    withdrawal_fee_bps: u16,
+    management_fee_bps: u16,
+    initial_exchange_rate: u64,
+    max_total_supply: u64,
+    max_queue_size: u32,
+}
+
+#[derive(AnchorDeserialize)]
+enum PoolStateV0 {
+    Active,
+    Paused,
+    Frozen,
+    DepositOnly,
+    WithdrawOnly,
+}
+
+/// Pool as written before layout versions (layout 0)
+#[derive(AnchorDeserialize)]
+struct PoolV0 {
+    pool_authority: Pubkey,
+    usdc_mint: Pubkey,
+    ipt_mint: Pubkey,
+    usdc_reserve: Pubkey,
+    pending_queue: Vec<PendingWithdrawV0>,
+    current_exchange_rate: u64,
+    total_ipt_supply: u64,
+    total_usdc_reserves: u64,
+    total_accumulated_fees: u64,
+    max_total_supply: u64,
+    config: PoolConfigV0,
+    pool_state: PoolStateV0,
+    last_rate_update: i64,
+    created_at: i64,
+    bump: u8,
+}
+
+/// Layout 0 to 1: adds the version field, the pool id (the legacy marker,
+/// as the pool's PDA has no id seed) and every field since, with features
+/// off and roles as the old program had them
+fn migrate_from_v0(data: &mut Vec<u8>, inputs: &MigrationInputs) -> Result<()> {
+    let old = PoolV0::deserialize(&mut &data[8..]).map_err(|_| PoolError::UnsupportedPoolLayout)?;
+
+    // Each old state becomes the lifecycle and pause flags that allow the
+    // same operations
+    let halted = PauseFlag::Withdrawals.mask() | PauseFlag::Queue.mask();
+    let (pool_state, pause_flags) = match old.pool_state {
+        PoolStateV0::Active => (PoolState::Active, 0),
+        PoolStateV0::Paused => (PoolState::Active, PauseFlag::Deposits.mask() | halted),
+        PoolStateV0::Frozen => (PoolState::Frozen, halted),
+        PoolStateV0::DepositOnly => (PoolState::Active, halted),
+        PoolStateV0::WithdrawOnly => (PoolState::Active, PauseFlag::Deposits.mask()),
+    };
+
+    let config = PoolConfig {
+        admin_authority: old.config.admin_authority,
+        oracle_authority: old.config.oracle_authority,
+        fee_collector: old.config.fee_collector,
+        // The admin moved reserves before the treasurer role existed
+        treasurer_authority: old.config.admin_authority,
+        deposit_fee_bps: old.config.deposit_fee_bps,
+        withdrawal_fee_bps: old.config.withdrawal_fee_bps,
+        management_fee_bps: old.config.management_fee_bps,
+        initial_exchange_rate: old.config.initial_exchange_rate,
+        max_total_supply: old.config.max_total_supply,
+        max_queue_size: old.config.max_queue_size,
+        timelock_delay: 0,
+        rate_floor: 0,
+        rate_ceiling: 0,
+        max_heartbeat_secs: 0,
+        dead_man_switch_secs: 0,
+        min_rate_update_interval_secs: 0,
+        max_redemption_bps_per_epoch: 0,
+        queue_pressure_depth: 0,
+        queue_pressure_bps: 0,
+        rebasing: false,
+        epoch_duration_secs: 24 * 60 * 60,
+        queue_age_weight: 0,
+        queue_size_weight: 0,
+        max_batch_share_bps: 0,
+        // The old batch dropped a request the first time it was skipped
+        max_skip_attempts: 1,
+        min_execution_delay_secs: 0,
+        cancellation_fee_bps: 0,
+        cancellation_grace_secs: 0,
+        commit_reveal_threshold: 0,
+        reveal_delay_slots: 0,
+        approval_threshold: 0,
+        compliance_authority: Pubkey::default(),
+        approval_expiry_secs: 0,
+        large_withdrawal_threshold: 0,
+        withdrawal_cosigner: Pubkey::default(),
+        max_buyback_usdc: 0,
+        fee_sweep_threshold: 0,
+        fee_recipients: Vec::new(),
+        oracle_overlap_secs: 0,
+        promo_start_at: 0,
+        promo_end_at: 0,
+        promo_bonus_bps: 0,
+        promo_waives_deposit_fee: false,
+        early_supporter_slots: 0,
+        early_supporter_discount_bps: 0,
+        referral_fee_share_bps: 0,
+        allowlist_only: false,
+        recovery_authority: Pubkey::default(),
+        dormancy_period_secs: 0,
+        escheat_custody: Pubkey::default(),
+        wind_down_waterfall: Vec::new(),
+        pull_payouts: false,
+        guardian_authority: Pubkey::default(),
+        queue_alert_depth: 0,
+        queue_alert_usdc: 0,
+        tier_terms: Vec::new(),
+        max_queue_entries_per_day: 0,
+        refund_window_secs: 0,
+        slot_stable_quotes: false,
+        sweep_wrapper_program: Pubkey::default(),
+        sweep_wrapped_mint: Pubkey::default(),
+        liquidity_buffer_usdc: 0,
+        reserve_drift_tolerance_bps: 0,
+        segregate_roles: false,
+        isolated_exits: false,
+    };
+
+    let pending_queue = old
+        .pending_queue
+        .into_iter()
+        .map(|pending| PendingWithdraw {
+            user: pending.user,
+            amount: pending.amount,
+            min_usdc_amount: pending.min_usdc_amount,
+            exit_adjustment_bps: 0,
+            // Old requests keep their FIFO order ahead of any new one
+            requested_at: old.created_at,
+            earliest_execution: 0,
+            approved: true,
+            tier: InvestorTier::Retail,
+            skip_count: 0,
+            last_skip_reason: None,
+        })
+        .collect();
+
+    let pool = Pool {
+        layout_version: 1,
+        pool_authority: old.pool_authority,
+        usdc_mint: old.usdc_mint,
+        pool_id: Pool::LEGACY_POOL_ID,
+        ipt_mint: old.ipt_mint,
+        usdc_reserve: old.usdc_reserve,
+        pending_queue,
+        queue_head: 0,
+        // Every layout 0 account was allocated for 20 entries
+        queue_capacity: 20,
+        current_exchange_rate: old.current_exchange_rate,
+        total_ipt_supply: old.total_ipt_supply,
+        total_usdc_reserves: old.total_usdc_reserves,
+        total_accumulated_fees: old.total_accumulated_fees,
+        // Fees were not split by stream; carry them all in one bucket
+        accumulated_deposit_fees: old.total_accumulated_fees,
+        accumulated_withdrawal_fees: 0,
+        accumulated_management_fees: 0,
+        max_total_supply: old.max_total_supply,
+        tvl_cap: 0,
+        config,
+        pool_state,
+        pause_flags,
+        usdc_decimals: inputs.usdc_decimals,
+        ipt_decimals: inputs.usdc_decimals,
+        next_timelock_id: 0,
+        next_distribution_id: 0,
+        migration_mint_cap: 0,
+        migration_minted: 0,
+        current_epoch: 0,
+        epoch_started_at: old.created_at,
+        last_epoch_nav: 0,
+        redemption_epoch: u64::MAX,
+        epoch_redemption_limit: 0,
+        epoch_redeemed_usdc: 0,
+        admin_withdrawal_epoch: 0,
+        epoch_admin_withdrawn: 0,
+        oracle_publisher: Pubkey::default(),
+        last_oracle_nonce: 0,
+        rate_feed_id: [0; 32],
+        pending_admin: Pubkey::default(),
+        pending_oracle_authority: Pubkey::default(),
+        previous_oracle_authority: Pubkey::default(),
+        oracle_overlap_ends_at: 0,
+        total_buyback_ipt: 0,
+        total_buyback_usdc: 0,
+        promo_budget: 0,
+        promo_spent: 0,
+        depositor_count: 0,
+        certificates_issued: 0,
+        referral_payable: 0,
+        blocked_count: 0,
+        pending_recovery_admin: Pubkey::default(),
+        pending_recovery_oracle: Pubkey::default(),
+        pending_recovery_fee_collector: Pubkey::default(),
+        recovery_executable_at: 0,
+        rate_evidence_hash: [0; 32],
+        accrued_yield: 0,
+        quote_rate_slot: 0,
+        quote_rate: 0,
+        reserve_asset_count: 0,
+        asset_reserves_value: 0,
+        wind_down_stage: 0,
+        wind_down_started_at: 0,
+        escrowed_payouts: 0,
+        queue_alert_raised: false,
+        queue_reserved_usdc: 0,
+        swept_usdc: 0,
+        name: String::new(),
+        description_uri: String::new(),
+        logo_uri: String::new(),
+        last_rate_update: old.last_rate_update,
+        last_rate_observed_at: old.last_rate_update,
+        created_at: old.created_at,
+        bump: old.bump,
+    };
+
+    data.clear();
+    pool.try_serialize(data)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// Pays for any growth and receives rent freed by shrinking
+    #[account(mut)]
+    pub protocol_admin: Signer<'info>,
+
+    /// Program-wide config; the protocol admin's key does not depend on the
+    /// pool's (possibly outdated) layout
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+        constraint = protocol_admin.key() == protocol_config.protocol_admin @ PoolError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Pool account on an older layout; read as raw bytes and
+    /// checked against its own pool authority once migrated
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// The pool's USDC mint, checked against the migrated pool
+    pub usdc_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigratePool>) -> Result<()> {
+    let info = ctx.accounts.pool.to_account_info();
+    let mut data = info.try_borrow_data()?.to_vec();
+    let inputs = MigrationInputs {
+        usdc_decimals: ctx.accounts.usdc_mint.decimals,
+    };
+
+    let old_layout_version =
+        Pool::read_layout_version(&data).ok_or(ErrorCode::AccountDiscriminatorMismatch)?;
+    require!(
+        old_layout_version != Pool::LAYOUT_VERSION,
+        PoolError::PoolLayoutCurrent
+    );
+
+    let mut version = old_layout_version;
+    while version != Pool::LAYOUT_VERSION {
+        let step = POOL_MIGRATIONS
+            .iter()
+            .find(|step| step.from == version)
+            .ok_or(PoolError::UnsupportedPoolLayout)?;
+        (step.migrate)(&mut data, &inputs)?;
+
+        version = Pool::read_layout_version(&data).ok_or(PoolError::UnsupportedPoolLayout)?;
+        require!(version == step.from + 1, PoolError::UnsupportedPoolLayout);
+    }
+
+    // The result must read as a current pool, and as this pool
+    let mut pool = Pool::try_deserialize(&mut &data[..])?;
+    require_keys_eq!(pool.pool_authority, info.key(), PoolError::InvalidAuthority);
+    require_keys_eq!(
+        pool.usdc_mint,
+        ctx.accounts.usdc_mint.key(),
+        PoolError::InvalidUsdcMint
+    );
+
+    // Fit the account to its queue, which resize_pool only does for pools
+    // already on the current layout
+    pool.queue_capacity = pool.required_queue_capacity();
+    data.clear();
+    pool.try_serialize(&mut data)?;
+    data.resize(Pool::space(pool.queue_capacity as usize), 0);
+
+    // Keep the account rent-exempt at its new size
+    let rent = Rent::get()?.minimum_balance(data.len());
+    let lamports = info.lamports();
+    if rent > lamports {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.protocol_admin.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+    } else if lamports > rent {
+        **info.try_borrow_mut_lamports()? -= lamports - rent;
+        **ctx.accounts.protocol_admin.try_borrow_mut_lamports()? += lamports - rent;
+    }
+    info.realloc(data.len(), false)?;
+    info.try_borrow_mut_data()?.copy_from_slice(&data);
+
+    emit!(PoolMigrated {
+        protocol_admin: ctx.accounts.protocol_admin.key(),
+        pool: info.key(),
+        old_layout_version,
+        new_layout_version: version,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Pool migrated from layout {} to {}",
+        old_layout_version,
+        version
+    );
+
+    Ok(())
+}
@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::{CalculationUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    burn, mint_to, transfer, Burn, Mint, MintTo, Token, TokenAccount, Transfer,
+};
+
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pool the position leaves
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            source_pool.usdc_mint.as_ref(),
+            source_pool.id_seed().as_ref()
+        ],
+        bump = source_pool.bump,
+        constraint = source_pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub source_pool: Box<Account<'info, Pool>>,
+
+    /// Pool the position moves into (same USDC mint)
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            destination_pool.usdc_mint.as_ref(),
+            destination_pool.id_seed().as_ref()
+        ],
+        bump = destination_pool.bump,
+        constraint = destination_pool.key() != source_pool.key() @ PoolError::InvalidConfigParameter,
+        constraint = destination_pool.usdc_mint == source_pool.usdc_mint @ PoolError::InvalidUsdcMint,
+        constraint = destination_pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub destination_pool: Box<Account<'info, Pool>>,
+
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Source pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            source_pool.usdc_mint.as_ref(),
+            source_pool.id_seed().as_ref()
+        ],
+        bump = source_pool.bump
+    )]
+    pub source_pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Destination pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            destination_pool.usdc_mint.as_ref(),
+            destination_pool.id_seed().as_ref()
+        ],
+        bump = destination_pool.bump
+    )]
+    pub destination_pool_authority: UncheckedAccount<'info>,
+
+    /// User's IPT token account in the source pool
+    #[account(
+        mut,
+        token::mint = source_pool.ipt_mint,
+        token::authority = user
+    )]
+    pub source_user_ipt_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's IPT token account in the destination pool
+    #[account(
+        mut,
+        token::mint = destination_pool.ipt_mint,
+        token::authority = user
+    )]
+    pub destination_user_ipt_account: Box<Account<'info, TokenAccount>>,
+
+    /// Source pool's USDC reserve
+    #[account(
+        mut,
+        address = source_pool.usdc_reserve
+    )]
+    pub source_usdc_reserve: Box<Account<'info, TokenAccount>>,
+
+    /// Destination pool's USDC reserve
+    #[account(
+        mut,
+        address = destination_pool.usdc_reserve
+    )]
+    pub destination_usdc_reserve: Box<Account<'info, TokenAccount>>,
+
+    /// Source pool's IPT mint
+    #[account(
+        mut,
+        address = source_pool.ipt_mint
+    )]
+    pub source_ipt_mint: Box<Account<'info, Mint>>,
+
+    /// Destination pool's IPT mint
+    #[account(
+        mut,
+        address = destination_pool.ipt_mint
+    )]
+    pub destination_ipt_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: User's access list entry PDA in the destination pool, which
+    /// need not exist; required while that pool has an allowlist, blocked
+    /// addresses or investor tiers
+    #[account(
+        seeds = [
+            AccessListEntry::SEED_PREFIX,
+            destination_pool.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub destination_access_entry: Option<UncheckedAccount<'info>>,
+
+    /// User's position in the source pool, which releases the moved IPT
+    #[account(
+        mut,
+        seeds = [Position::SEED_PREFIX, source_pool.key().as_ref(), user.key().as_ref()],
+        bump = source_position.bump
+    )]
+    pub source_position: Option<Box<Account<'info, Position>>>,
+
+    /// User's position in the destination pool, opened by their first
+    /// user_deposit there; required while their tier has a deposit cap
+    #[account(
+        mut,
+        seeds = [
+            Position::SEED_PREFIX,
+            destination_pool.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump = destination_position.bump
+    )]
+    pub destination_position: Option<Box<Account<'info, Position>>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<MigratePosition>, net_ipt_amount: u64, min_ipt_out: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Both legs price at a fresh rate, pinned for the slot like any other
+    // user flow, so a stale rate on either pool cannot be arbitraged
+    let source_rate = ctx.accounts.source_pool.pin_quote_rate(clock.slot);
+    let destination_rate = ctx.accounts.destination_pool.pin_quote_rate(clock.slot);
+    let source = &ctx.accounts.source_pool;
+    let destination = &ctx.accounts.destination_pool;
+
+    ValidationUtils::validate_protocol_not_paused(&ctx.accounts.protocol_config)?;
+    ValidationUtils::validate_pool_operation(source, PauseFlag::Withdrawals)?;
+    ValidationUtils::validate_pool_operation(destination, PauseFlag::Deposits)?;
+    ValidationUtils::validate_oracle_fresh(source, clock.unix_timestamp)?;
+    ValidationUtils::validate_oracle_fresh(destination, clock.unix_timestamp)?;
+
+    require!(net_ipt_amount > 0, PoolError::InvalidAmount);
+    require!(
+        ctx.accounts.source_user_ipt_account.amount >= net_ipt_amount,
+        PoolError::InsufficientAccountBalance
+    );
+
+    // Value the position at the source rate and re-price it at the destination
+    // rate. No fees are charged: the position never leaves the program.
+    let (usdc_value, _) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+        net_ipt_amount,
+        source_rate,
+        0,
+        source.usdc_decimals,
+        source.ipt_decimals,
+    )?;
+    let (ipt_out, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+        usdc_value,
+        destination_rate,
+        0,
+        destination.usdc_decimals,
+        destination.ipt_decimals,
+    )?;
+
+    // Moving in is a deposit into the destination pool, gated the same way
+    let access_entry = ctx.accounts.destination_access_entry.as_deref();
+    ValidationUtils::validate_access(destination, access_entry)?;
+    let tier = ValidationUtils::investor_tier(destination, access_entry)?;
+    ValidationUtils::validate_tier_deposit(
+        &destination.config,
+        tier,
+        ctx.accounts.destination_position.as_deref().map(|position| &**position),
+        usdc_value,
+    )?;
+
+    require!(ipt_out > 0, PoolError::InvalidAmount);
+    require!(ipt_out >= min_ipt_out, PoolError::SlippageExceeded);
+    // Like an instant exit, migration cannot use liquidity earmarked for
+    // the source pool's queue
+    require!(
+        source.unreserved_liquidity(ctx.accounts.source_usdc_reserve.amount) >= usdc_value,
+        PoolError::InsufficientReserves
+    );
+
+    let new_destination_supply = destination
+        .total_ipt_supply
+        .checked_add(ipt_out)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    if destination.max_total_supply > 0 {
+        require!(
+            new_destination_supply <= destination.max_total_supply,
+            PoolError::MaxTotalSupplyExceeded
+        );
+    }
+
+    // The moved value counts against the destination's TVL cap too
+    if destination.tvl_cap > 0 {
+        let new_nav = destination
+            .nav()?
+            .checked_add(usdc_value)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        require!(new_nav <= destination.tvl_cap, PoolError::TvlCapExceeded);
+    }
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.source_ipt_mint.to_account_info(),
+                from: ctx.accounts.source_user_ipt_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        net_ipt_amount,
+    )?;
+
+    // The backing USDC follows the position
+    let source_id_seed = source.id_seed();
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.source_usdc_reserve.to_account_info(),
+                to: ctx.accounts.destination_usdc_reserve.to_account_info(),
+                authority: ctx.accounts.source_pool_authority.to_account_info(),
+            },
+            &[&source.authority_seeds(&source_id_seed)],
+        ),
+        usdc_value,
+    )?;
+
+    let destination_id_seed = destination.id_seed();
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.destination_ipt_mint.to_account_info(),
+                to: ctx.accounts.destination_user_ipt_account.to_account_info(),
+                authority: ctx.accounts.destination_pool_authority.to_account_info(),
+            },
+            &[&destination.authority_seeds(&destination_id_seed)],
+        ),
+        ipt_out,
+    )?;
+
+    let source = &mut ctx.accounts.source_pool;
+    source.roll_redemption_epoch()?;
+    require!(
+        source.redemption_fits(usdc_value),
+        PoolError::RedemptionGateExceeded
+    );
+    source.record_redemption(usdc_value)?;
+    source.total_ipt_supply = source
+        .total_ipt_supply
+        .checked_sub(net_ipt_amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    source.total_usdc_reserves = source
+        .total_usdc_reserves
+        .checked_sub(usdc_value)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+
+    let destination = &mut ctx.accounts.destination_pool;
+    destination.total_ipt_supply = new_destination_supply;
+    destination.total_usdc_reserves = destination
+        .total_usdc_reserves
+        .checked_add(usdc_value)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    if let Some(position) = ctx.accounts.source_position.as_mut() {
+        position.record_exit(net_ipt_amount);
+    }
+    if let Some(position) = ctx.accounts.destination_position.as_mut() {
+        position.touch(clock.unix_timestamp);
+        position.record_entry(ipt_out, destination_rate, usdc_value)?;
+    }
+    if destination.close_subscriptions_if_full() {
+        emit!(SubscriptionsClosed {
+            pool: destination.key(),
+            total_ipt_supply: destination.total_ipt_supply,
+            max_total_supply: destination.max_total_supply,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    emit!(PositionMigrated {
+        user: ctx.accounts.user.key(),
+        source_pool: ctx.accounts.source_pool.key(),
+        destination_pool: ctx.accounts.destination_pool.key(),
+        ipt_burned: net_ipt_amount,
+        usdc_moved: usdc_value,
+        ipt_minted: ipt_out,
+        source_exchange_rate: source_rate,
+        destination_exchange_rate: destination_rate,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Migrated {} IPT ({} USDC) into {} IPT of the destination pool",
+        net_ipt_amount,
+        usdc_value,
+        ipt_out
+    );
+
+    Ok(())
+}
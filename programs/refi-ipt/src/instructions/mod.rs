@@ -1,28 +1,205 @@
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod accept_admin;
+pub mod accept_oracle_authority;
+pub mod add_reserve_asset;
+pub mod admin_deposit_and_process;
 pub mod admin_deposit_usdc;
+pub mod admin_mint_ipt;
 pub mod admin_update_config;
+pub mod admin_update_rate_and_state;
 pub mod admin_withdraw_usdc;
+pub mod advance_epoch;
+pub mod advance_wind_down;
+pub mod approve_withdrawal_request;
+pub mod begin_migration;
+pub mod begin_wind_down;
+pub mod burn_ipt_donation;
+pub mod buyback_ipt;
+pub mod cancel_admin_action;
+pub mod cancel_recovery;
 pub mod cancel_withdrawal;
+pub mod check_heartbeat;
+pub mod check_reserve_drift;
+pub mod claim_dividend;
+pub mod claim_escrowed_payout;
+pub mod claim_rebase;
+pub mod claim_referral_earnings;
+pub mod clone_pool;
+pub mod close_claim_receipt;
+pub mod close_distribution;
+pub mod close_withdrawal_commitment;
+pub mod commit_share_register;
+pub mod commit_statement;
+pub mod commit_withdrawal;
+pub mod configure_cost_basis;
+pub mod crank_withdraw_queue;
+pub mod create_distribution;
+pub mod deposit_reserve_asset;
+pub mod emergency_pause;
+pub mod end_migration;
+pub mod escheat_position;
+pub mod execute_recovery;
+pub mod expand_queue;
 pub mod fee_collector_withdraw;
+pub mod flag_dormant_position;
 pub mod init_pool;
 pub mod init_pool_step2;
+pub mod init_protocol_config;
+pub mod initiate_recovery;
+pub mod migrate_pool;
+pub mod migrate_position;
+pub mod open_payout_escrow;
 pub mod process_queue;
+pub mod propose_admin;
+pub mod propose_oracle_authority;
+pub mod protocol_pause;
+pub mod rebase;
+pub mod record_position_activity;
+pub mod refresh_rate_from_oracle;
+pub mod refund_deposit;
+pub mod register_partner;
+pub mod register_referral;
+pub mod remove_from_access_list;
+pub mod resize_pool;
+pub mod reveal_withdrawal;
+pub mod route_deposit;
+pub mod schedule_admin_action;
+pub mod set_access_list;
+pub mod set_escheat_custody;
+pub mod set_feature_flags;
+pub mod set_fee_collector;
+pub mod set_fees;
+pub mod set_investor_tier;
+pub mod set_max_queue_size;
+pub mod set_max_total_supply;
+pub mod set_oracle_publisher;
+pub mod set_pause_flag;
+pub mod set_pool_metadata;
+pub mod set_pool_registry_entry;
+pub mod set_pool_state;
+pub mod set_queue_processing_paused;
+pub mod set_rate_feed;
+pub mod set_reserve_asset_weight;
+pub mod set_role_authority;
+pub mod set_sweep_wrapper;
+pub mod set_treasurer;
+pub mod set_tvl_cap;
+pub mod set_withdrawal_cosigner;
+pub mod shrink_withdrawal;
+pub mod sweep_fees;
+pub mod sweep_idle_reserve;
+pub mod transfer_mint_authority;
+pub mod trip_dead_man_switch;
+pub mod unfreeze_pool;
+pub mod unwrap_swept_reserve;
 pub mod update_exchange_rate;
+pub mod update_exchange_rate_signed;
+pub mod update_protocol_config;
+pub mod update_reserve_asset_price;
 pub mod user_deposit;
 pub mod user_withdraw;
 pub mod user_withdrawal_request;
+pub mod vault_deposit;
+pub mod vault_mint;
+pub mod vault_redeem;
+pub mod vault_withdraw;
+pub mod withdraw_reserve_asset;
 
 #[allow(ambiguous_glob_reexports)]
+pub use accept_admin::*;
+pub use accept_oracle_authority::*;
+pub use add_reserve_asset::*;
+pub use admin_deposit_and_process::*;
 pub use admin_deposit_usdc::*;
+pub use admin_mint_ipt::*;
 pub use admin_update_config::*;
+pub use admin_update_rate_and_state::*;
 pub use admin_withdraw_usdc::*;
+pub use advance_epoch::*;
+pub use advance_wind_down::*;
+pub use approve_withdrawal_request::*;
+pub use begin_migration::*;
+pub use begin_wind_down::*;
+pub use burn_ipt_donation::*;
+pub use buyback_ipt::*;
+pub use cancel_admin_action::*;
+pub use cancel_recovery::*;
 pub use cancel_withdrawal::*;
+pub use check_heartbeat::*;
+pub use check_reserve_drift::*;
+pub use claim_dividend::*;
+pub use claim_escrowed_payout::*;
+pub use claim_rebase::*;
+pub use claim_referral_earnings::*;
+pub use clone_pool::*;
+pub use close_claim_receipt::*;
+pub use close_distribution::*;
+pub use close_withdrawal_commitment::*;
+pub use commit_share_register::*;
+pub use commit_statement::*;
+pub use commit_withdrawal::*;
+pub use configure_cost_basis::*;
+pub use create_distribution::*;
+pub use deposit_reserve_asset::*;
+pub use emergency_pause::*;
+pub use end_migration::*;
+pub use escheat_position::*;
+pub use execute_recovery::*;
+pub use expand_queue::*;
 pub use fee_collector_withdraw::*;
+pub use flag_dormant_position::*;
 pub use init_pool::*;
 pub use init_pool_step2::*;
+pub use init_protocol_config::*;
+pub use initiate_recovery::*;
+pub use migrate_pool::*;
+pub use migrate_position::*;
+pub use open_payout_escrow::*;
 pub use process_queue::*;
+pub use propose_admin::*;
+pub use propose_oracle_authority::*;
+pub use protocol_pause::*;
+pub use rebase::*;
+pub use record_position_activity::*;
+pub use refresh_rate_from_oracle::*;
+pub use refund_deposit::*;
+pub use register_partner::*;
+pub use register_referral::*;
+pub use remove_from_access_list::*;
+pub use resize_pool::*;
+pub use reveal_withdrawal::*;
+pub use route_deposit::*;
+pub use schedule_admin_action::*;
+pub use set_access_list::*;
+pub use set_escheat_custody::*;
+pub use set_feature_flags::*;
+pub use set_investor_tier::*;
+pub use set_max_total_supply::*;
+pub use set_oracle_publisher::*;
+pub use set_pause_flag::*;
+pub use set_pool_metadata::*;
+pub use set_pool_registry_entry::*;
+pub use set_pool_state::*;
+pub use set_queue_processing_paused::*;
+pub use set_rate_feed::*;
+pub use set_reserve_asset_weight::*;
+pub use set_role_authority::*;
+pub use set_sweep_wrapper::*;
+pub use set_treasurer::*;
+pub use set_tvl_cap::*;
+pub use set_withdrawal_cosigner::*;
+pub use shrink_withdrawal::*;
+pub use sweep_fees::*;
+pub use sweep_idle_reserve::*;
+pub use transfer_mint_authority::*;
+pub use trip_dead_man_switch::*;
+pub use unfreeze_pool::*;
 pub use update_exchange_rate::*;
+pub use update_exchange_rate_signed::*;
+pub use update_protocol_config::*;
+pub use update_reserve_asset_price::*;
 pub use user_deposit::*;
 pub use user_withdraw::*;
 pub use user_withdrawal_request::*;
+pub use withdraw_reserve_asset::*;
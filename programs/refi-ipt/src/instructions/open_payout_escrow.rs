@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct OpenPayoutEscrow<'info> {
+    /// Anyone may open an escrow, typically the keeper that found the
+    /// user's USDC account frozen or closed
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        constraint = pool_authority.key() == pool.pool_authority @ PoolError::InvalidAuthority
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The user the escrow holds payouts for; only used as a seed
+    pub user: UncheckedAccount<'info>,
+
+    /// USDC mint
+    #[account(address = pool.usdc_mint @ PoolError::InvalidUsdcMint)]
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// The user's payout escrow
+    #[account(
+        init,
+        payer = payer,
+        token::mint = usdc_mint,
+        token::authority = pool_authority,
+        seeds = [
+            Pool::PAYOUT_ESCROW_SEED,
+            pool.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub payout_escrow: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<OpenPayoutEscrow>) -> Result<()> {
+    msg!(
+        "Payout escrow {} opened for {}",
+        ctx.accounts.payout_escrow.key(),
+        ctx.accounts.user.key()
+    );
+
+    Ok(())
+}
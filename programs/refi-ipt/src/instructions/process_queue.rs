@@ -2,53 +2,77 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Mint, Burn, Transfer};
-use anchor_spl::token::spl_token::solana_program::program_option::COption;
-use crate::utils::{CalculationUtils, ValidationUtils};
+use crate::utils::{CalculationUtils, TokenUtils, ValidationUtils};
 use crate::events::*;
 use crate::states::*;
 use crate::errors::PoolError;
 
+// Max users per transaction, keeps a batch within the compute limit
+pub const MAX_BATCH_SIZE: usize = 10;
+
 #[derive(Accounts)]
-#[instruction(batch_size: u8)]
 pub struct BatchExecuteWithdraw<'info> {
     /// Backend/Keeper authority
     #[account(mut)]
     pub executor: Signer<'info>,
 
-    #[account(mut)]
-    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
 
     #[account(
-        seeds = [Pool::SEED_PREFIX, pool.usdc_mint.as_ref()],
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
         bump = pool.bump
     )]
     pub pool_authority: UncheckedAccount<'info>,
 
     #[account(mut, address = pool.usdc_reserve)]
-    pub pool_usdc_reserve: Account<'info, TokenAccount>,
+    pub pool_usdc_reserve: Box<Account<'info, TokenAccount>>,
 
     #[account(mut, address = pool.ipt_mint)]
-    pub ipt_mint: Account<'info, Mint>,
+    pub ipt_mint: Box<Account<'info, Mint>>,
 
     pub token_program: Program<'info, Token>,
 
+    /// CHECK: Instructions sysvar, used to enforce isolated exits
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
     // remaining_accounts format:
     // [user_ipt_0, user_usdc_0, user_ipt_1, user_usdc_1, ...]
-    // Each user needs 2 accounts: IPT account and USDC account
+    // Each user needs 2 accounts: IPT account and USDC account; the USDC
+    // account may be the user's payout escrow if it cannot receive
 }
 
 pub fn batch_execute_withdraw<'info>(
-    ctx: Context<'_, '_, 'info, 'info, BatchExecuteWithdraw<'info>>,
+    ctx: &mut Context<'_, '_, 'info, 'info, BatchExecuteWithdraw<'info>>,
     amounts: Vec<u64>,  // IPT amounts for each user (should match pending_queue amounts)
 ) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
     let batch_size = amounts.len();
 
-    // Validate pool state
-    ValidationUtils::validate_pool_state_for_operation(&pool.pool_state, false)?;
+    // Validate protocol and pool state
+    ValidationUtils::validate_protocol_not_paused(&ctx.accounts.protocol_config)?;
+    ValidationUtils::validate_pool_operation(pool, PauseFlag::Queue)?;
+    ValidationUtils::validate_not_paused(pool, PauseFlag::Withdrawals)?;
+    ValidationUtils::validate_isolated_exit(pool, &ctx.accounts.instructions)?;
 
     require!(
-        batch_size <= 10,  // Max 10 users/tx to avoid compute limit
+        batch_size <= MAX_BATCH_SIZE,
         PoolError::BatchSizeTooLarge
     );
 
@@ -57,7 +81,7 @@ pub fn batch_execute_withdraw<'info>(
     // This prevents index out of bounds panic
     // ============================================================
     require!(
-        batch_size <= pool.pending_queue.len(),
+        batch_size <= pool.queued().len(),
         PoolError::EmptyWithdrawalBatch
     );
 
@@ -81,35 +105,94 @@ pub fn batch_execute_withdraw<'info>(
         );
     }
 
+    pool.roll_redemption_epoch()?;
+
     let mut total_ipt_burned = 0u64;
     let mut total_usdc_transferred = 0u64;
     let mut total_fees = 0u64;
     let mut successful_count = 0usize;
     let mut skipped_count = 0usize;
-
-    // Process each withdrawal in FIFO order
-    for i in 0..batch_size {
+    // Entries to drop from the queue; partially filled ones stay in place
+    let mut settled = vec![false; batch_size];
+
+    // No single request may take more than this share of the batch's reserves
+    let user_share_cap = CalculationUtils::calculate_fee(
+        pool.total_usdc_reserves,
+        pool.config.max_batch_share_bps,
+    )?;
+
+    // Values fixed for the whole batch, read once rather than per entry
+    let pricing = BatchPricing::from_pool(pool);
+    let ipt_mint = pool.ipt_mint;
+    let usdc_mint = pool.usdc_mint;
+    let pool_authority = ctx.accounts.pool_authority.key();
+    let pool_key = pool.key();
+    let now = Clock::get()?.unix_timestamp;
+    let bump = [pool.bump];
+    let authority_seeds: [&[u8]; 4] = [Pool::SEED_PREFIX, usdc_mint.as_ref(), &pool_id_seed, &bump];
+    let signer_seeds = &[&authority_seeds[..]];
+
+    // Process each withdrawal in queue (priority) order
+    for (i, &requested_amount) in amounts.iter().enumerate() {
         // Get amount from queue directly (more reliable than external input)
-        let pending = &pool.pending_queue[i];
-        let ipt_amount = pending.amount;
+        let pending = pool.queued()[i].clone();
+        let mut ipt_amount = pending.amount;
         
         // Validate amounts[i] matches queue (optional safety check)
-        if amounts[i] != ipt_amount {
+        if requested_amount != ipt_amount {
             msg!(
                 "WARNING: amounts[{}] ({}) != pending.amount ({}), using queue value",
                 i,
-                amounts[i],
+                requested_amount,
                 ipt_amount
             );
         }
 
+        // Awaiting compliance sign-off: leave it queued until approved, or
+        // drop it once the approval window has lapsed
+        if !pending.approved {
+            if pending.approval_expired(pool.config.approval_expiry_secs, now) {
+                emit!(WithdrawFailed {
+                    user: pending.user,
+                    ipt_amount: pending.amount,
+                    reason: "Approval expired".to_string(),
+                    attempts: pending.skip_count,
+                });
+                msg!(
+                    "Withdrawal for {} at index {} expired unapproved, removing from queue",
+                    pending.user,
+                    i
+                );
+                skipped_count += 1;
+                settled[i] = true;
+            } else {
+                msg!(
+                    "Withdrawal for {} at index {} awaiting approval",
+                    pending.user,
+                    i
+                );
+            }
+            continue;
+        }
+
+        // Still inside its review window: leave it queued without counting a skip
+        if now < pending.earliest_execution {
+            msg!(
+                "Withdrawal for {} at index {} not executable until {}",
+                pending.user,
+                i,
+                pending.earliest_execution
+            );
+            continue;
+        }
+
         // Get user accounts (each user has 2 accounts)
         let user_ipt_account = &ctx.remaining_accounts[i * 2];
         let user_usdc_account = &ctx.remaining_accounts[i * 2 + 1];
 
-        // Deserialize accounts
-        let ipt_acc = Account::<TokenAccount>::try_from(user_ipt_account)?;
-        let usdc_acc = Account::<TokenAccount>::try_from(user_usdc_account)?;
+        // Read only the token account fields the batch needs
+        let ipt_acc = TokenUtils::read_token_account(user_ipt_account)?;
+        let usdc_acc = TokenUtils::read_token_account(user_usdc_account)?;
 
         // Validate this is the correct user (pending already retrieved above)
         require!(
@@ -119,78 +202,104 @@ pub fn batch_execute_withdraw<'info>(
 
         // Validate accounts
         require!(
-            ipt_acc.mint == pool.ipt_mint,
+            ipt_acc.mint == ipt_mint,
             PoolError::InvalidMint
         );
         require!(
-            usdc_acc.mint == pool.usdc_mint,
+            usdc_acc.mint == usdc_mint,
             PoolError::InvalidMint
         );
 
-        // Check delegation
+        // A payout that cannot reach a frozen or closed account goes to the
+        // user's payout escrow, passed in place of their USDC account; pull
+        // mode pools pay every batch payout that way
+        let escrowed = usdc_acc.owner == pool_authority;
         require!(
-            ipt_acc.delegate == COption::Some(ctx.accounts.pool_authority.key()),
-            PoolError::NotDelegated
+            !escrowed
+                || user_usdc_account.key() == Pool::payout_escrow_address(&pool_key, &pending.user),
+            PoolError::InvalidUserAccount
         );
         require!(
-            ipt_acc.delegated_amount >= ipt_amount,
-            PoolError::InsufficientDelegation
+            escrowed || !pool.config.pull_payouts,
+            PoolError::PayoutEscrowRequired
         );
+        if escrowed {
+            ValidationUtils::validate_feature_enabled(
+                &ctx.accounts.protocol_config,
+                Feature::PayoutEscrows,
+            )?;
+        }
+
+        // Check delegation; a revoked approval is skipped rather than failing the batch
+        if ipt_acc.delegate != Some(pool_authority)
+            || ipt_acc.delegated_amount < ipt_amount
+        {
+            msg!(
+                "User {} at index {} has not delegated {} IPT to the pool",
+                pending.user,
+                i,
+                ipt_amount
+            );
+
+            skipped_count += 1;
+            settled[i] = skip_withdrawal(pool, i, SkipReason::DelegationMissing);
+            continue;
+        }
 
         // Check if user still has enough IPT balance
-        // IMPORTANT: If user has insufficient balance, SKIP and REMOVE from queue
+        // IMPORTANT: If user has insufficient balance, SKIP (and drop once out of retries)
         // This prevents malicious users from blocking the entire queue
         if ipt_acc.amount < ipt_amount {
             msg!(
-                "User {} at index {} has insufficient IPT balance ({} < {}), removing from queue",
+                "User {} at index {} has insufficient IPT balance ({} < {})",
                 pending.user,
                 i,
                 ipt_acc.amount,
                 ipt_amount
             );
 
-            // Skip this user but mark as processed to remove from queue
+            // Skip this user; it leaves the queue once out of retries
             // This prevents queue blocking attacks
             skipped_count += 1;
-
-            // Emit event for tracking
-            emit!(WithdrawSkipped {
-                user: pending.user,
-                ipt_amount,
-                reason: "Insufficient IPT balance".to_string(),
-                batch_index: i as u8,
-            });
+            settled[i] = skip_withdrawal(pool, i, SkipReason::InsufficientBalance);
 
             continue; // Continue to next user instead of breaking
         }
 
         // Calculate USDC amounts
-        let (net_usdc_amount, withdrawal_fee) =
-            CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
-                ipt_amount,
-                pool.current_exchange_rate,
-                pool.config.withdrawal_fee_bps,
-            )?;
+        let (mut net_usdc_amount, mut withdrawal_fee, mut exit_adjustment) =
+            pricing.quote(ipt_amount, pending.tier, pending.exit_adjustment_bps)?;
+        let mut min_usdc_amount = pending.min_usdc_amount;
+
+        // Per-user share cap: fill only part of an oversized request and leave
+        // the rest queued, so limited reserves reach more requesters
+        let full_gross_usdc = net_usdc_amount
+            .checked_add(withdrawal_fee)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        if user_share_cap > 0 && full_gross_usdc > user_share_cap {
+            ipt_amount = pro_rata(pending.amount, user_share_cap, full_gross_usdc)?;
+            if ipt_amount == 0 {
+                continue;
+            }
+            min_usdc_amount = pro_rata(pending.min_usdc_amount, ipt_amount, pending.amount)?;
+            (net_usdc_amount, withdrawal_fee, exit_adjustment) =
+                pricing.quote(ipt_amount, pending.tier, pending.exit_adjustment_bps)?;
+        }
+        let partial = ipt_amount < pending.amount;
 
         // Check slippage protection from original request
-        // If slippage exceeded, skip and remove from queue (user's responsibility to monitor rate)
-        if net_usdc_amount < pending.min_usdc_amount {
+        // If slippage exceeded, skip (user's responsibility to monitor rate)
+        if net_usdc_amount < min_usdc_amount {
             msg!(
-                "Slippage protection failed for user {} at index {} ({} < {}), removing from queue",
+                "Slippage protection failed for user {} at index {} ({} < {})",
                 pending.user,
                 i,
                 net_usdc_amount,
-                pending.min_usdc_amount
+                min_usdc_amount
             );
 
             skipped_count += 1;
-
-            emit!(WithdrawSkipped {
-                user: pending.user,
-                ipt_amount,
-                reason: "Slippage protection exceeded".to_string(),
-                batch_index: i as u8,
-            });
+            settled[i] = skip_withdrawal(pool, i, SkipReason::SlippageExceeded);
 
             continue; // Skip this user
         }
@@ -204,48 +313,49 @@ pub fn batch_execute_withdraw<'info>(
         let used_so_far = total_usdc_transferred
             .checked_add(total_fees)
             .ok_or(PoolError::MathematicalOverflow)?;
-        let available_reserves = pool.total_usdc_reserves
-            .checked_sub(used_so_far)
-            .unwrap_or(0);
+        let available_reserves = pool.total_usdc_reserves.saturating_sub(used_so_far);
             
         if available_reserves < gross_usdc_amount {
             msg!(
-                "Insufficient reserves for user at index {} (available: {}, needed: {}), stopping batch (FIFO)",
+                "Insufficient reserves for user at index {} (available: {}, needed: {}), stopping batch (queue order)",
                 i,
                 available_reserves,
                 gross_usdc_amount
             );
-            break;  // FIFO - stop at this user
+            break;  // Queue order - stop at this user
         }
 
-        // Burn IPT using delegated authority
-        token::burn(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.ipt_mint.to_account_info(),
-                    from: user_ipt_account.clone(),
-                    authority: ctx.accounts.pool_authority.to_account_info(),
-                },
-                &[&pool.authority_seeds()],
-            ),
-            ipt_amount,
-        )?;
+        // Redemption gate: the rest of the queue waits for the next epoch
+        if !pool.redemption_fits(
+            used_so_far
+                .checked_add(gross_usdc_amount)
+                .ok_or(PoolError::MathematicalOverflow)?,
+        ) {
+            msg!(
+                "Redemption gate reached at index {} ({} of {} this epoch), stopping batch",
+                i,
+                pool.epoch_redeemed_usdc,
+                pool.epoch_redemption_limit
+            );
+            break;
+        }
 
-        // Transfer USDC to user
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.pool_usdc_reserve.to_account_info(),
-                    to: user_usdc_account.clone(),
-                    authority: ctx.accounts.pool_authority.to_account_info(),
-                },
-                &[&pool.authority_seeds()],
-            ),
+        settle_withdrawal(
+            &ctx.accounts.token_program,
+            &ctx.accounts.ipt_mint,
+            &ctx.accounts.pool_usdc_reserve,
+            &ctx.accounts.pool_authority,
+            user_ipt_account,
+            user_usdc_account,
+            signer_seeds,
+            ipt_amount,
             net_usdc_amount,
         )?;
 
+        // Read balances back so reconciliation sees what actually landed
+        let ipt_balance_after = TokenUtils::read_amount(user_ipt_account)?;
+        let usdc_balance_after = TokenUtils::read_amount(user_usdc_account)?;
+
         // Accumulate totals
         total_ipt_burned = total_ipt_burned
             .checked_add(ipt_amount)
@@ -261,12 +371,45 @@ pub fn batch_execute_withdraw<'info>(
 
         successful_count += 1;
 
+        if escrowed {
+            pool.escrowed_payouts = pool
+                .escrowed_payouts
+                .checked_add(net_usdc_amount)
+                .ok_or(PoolError::MathematicalOverflow)?;
+
+            emit!(PayoutEscrowed {
+                user: pending.user,
+                pool: pool_key,
+                escrow: user_usdc_account.key(),
+                usdc_amount: net_usdc_amount,
+                batch_index: i as u8,
+            });
+        }
+
+        let remaining_ipt = if partial {
+            let entry = &mut pool.queued_mut()[i];
+            entry.amount -= ipt_amount;
+            entry.min_usdc_amount = entry.min_usdc_amount.saturating_sub(min_usdc_amount);
+            entry.skip_count = 0;
+            entry.last_skip_reason = None;
+            entry.amount
+        } else {
+            settled[i] = true;
+            0
+        };
+
         // Emit per-user event
         emit!(WithdrawExecuted {
             user: ipt_acc.owner,
             ipt_amount,
             usdc_amount: net_usdc_amount,
             withdrawal_fee,
+            exit_adjustment,
+            remaining_ipt,
+            ipt_balance_before: ipt_acc.amount,
+            ipt_balance_after,
+            usdc_balance_before: usdc_acc.amount,
+            usdc_balance_after,
             batch_index: i as u8,
         });
     }
@@ -276,9 +419,7 @@ pub fn batch_execute_withdraw<'info>(
         .checked_sub(total_ipt_burned)
         .ok_or(PoolError::MathematicalUnderflow)?;
 
-    pool.total_accumulated_fees = pool.total_accumulated_fees
-        .checked_add(total_fees)
-        .ok_or(PoolError::MathematicalOverflow)?;
+    pool.credit_fees(FeeBucket::Withdrawal, total_fees)?;
 
     // Calculate new reserves (subtract gross amount which includes fees)
     let total_gross_usdc = total_usdc_transferred
@@ -288,13 +429,17 @@ pub fn batch_execute_withdraw<'info>(
     pool.total_usdc_reserves = pool.total_usdc_reserves
         .checked_sub(total_gross_usdc)
         .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.record_redemption(total_gross_usdc)?;
+    // Batches draw on the queue's earmarked liquidity first
+    pool.queue_reserved_usdc = pool.queue_reserved_usdc.saturating_sub(total_gross_usdc);
 
     // CRITICAL FIX: Remove both successful and skipped items from the queue
     // This prevents malicious users from blocking the queue
-    let total_processed = successful_count + skipped_count;
+    let total_processed = settled.iter().filter(|&&done| done).count();
 
     if total_processed > 0 {
-        pool.pending_queue.drain(0..total_processed);
+        // Advance the head past them; the entries are compacted away lazily
+        pool.consume_queue_window(&settled);
 
         msg!(
             "Removed {} items from queue ({} successful, {} skipped)",
@@ -304,6 +449,8 @@ pub fn batch_execute_withdraw<'info>(
         );
     }
 
+    ValidationUtils::check_queue_depth(pool, now)?;
+
     // Emit batch summary event
     emit!(BatchWithdrawExecuted {
         executor: ctx.accounts.executor.key(),
@@ -313,7 +460,7 @@ pub fn batch_execute_withdraw<'info>(
         total_usdc_transferred,
         total_fees,
         new_pool_reserves: pool.total_usdc_reserves,
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
     });
 
     msg!(
@@ -326,4 +473,138 @@ pub fn batch_execute_withdraw<'info>(
     );
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Burn the user's delegated IPT and pay out their USDC; kept out of line so
+/// the CPI contexts live in their own frame rather than the batch loop's
+#[allow(clippy::too_many_arguments)]
+#[inline(never)]
+fn settle_withdrawal<'info>(
+    token_program: &Program<'info, Token>,
+    ipt_mint: &Account<'info, Mint>,
+    pool_usdc_reserve: &Account<'info, TokenAccount>,
+    pool_authority: &UncheckedAccount<'info>,
+    user_ipt_account: &AccountInfo<'info>,
+    user_usdc_account: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    ipt_amount: u64,
+    net_usdc_amount: u64,
+) -> Result<()> {
+    // Burn IPT using delegated authority
+    token::burn(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Burn {
+                mint: ipt_mint.to_account_info(),
+                from: user_ipt_account.clone(),
+                authority: pool_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        ipt_amount,
+    )?;
+
+    // Transfer USDC to user
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: pool_usdc_reserve.to_account_info(),
+                to: user_usdc_account.clone(),
+                authority: pool_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        net_usdc_amount,
+    )
+}
+
+/// Count a skip against the entry at `index`; returns true once it has failed
+/// terminally and should leave the queue
+fn skip_withdrawal(pool: &mut Pool, index: usize, reason: SkipReason) -> bool {
+    let max_attempts = pool.config.max_skip_attempts;
+    let entry = &mut pool.queued_mut()[index];
+    let terminal = entry.record_skip(reason, max_attempts);
+
+    emit!(WithdrawSkipped {
+        user: entry.user,
+        ipt_amount: entry.amount,
+        reason: reason.as_str().to_string(),
+        attempts: entry.skip_count,
+        batch_index: index as u8,
+    });
+
+    if terminal {
+        // SPL Token only lets the owner revoke an approval; without a queue
+        // entry the pool never spends it again
+        emit!(WithdrawFailed {
+            user: entry.user,
+            ipt_amount: entry.amount,
+            reason: reason.as_str().to_string(),
+            attempts: entry.skip_count,
+        });
+
+        msg!(
+            "Withdrawal for {} failed after {} attempts ({}), removing from queue",
+            entry.user,
+            entry.skip_count,
+            reason.as_str()
+        );
+    }
+
+    terminal
+}
+
+/// Pool pricing inputs, constant for the duration of a batch
+struct BatchPricing {
+    exchange_rate: u64,
+    /// Withdrawal fee rate of each investor tier
+    withdrawal_fee_bps: [u16; 3],
+    usdc_decimals: u8,
+    ipt_decimals: u8,
+}
+
+impl BatchPricing {
+    fn from_pool(pool: &Pool) -> Self {
+        Self {
+            exchange_rate: pool.current_exchange_rate,
+            withdrawal_fee_bps: InvestorTier::ALL.map(|tier| pool.config.withdrawal_fee_bps_for(tier)),
+            usdc_decimals: pool.usdc_decimals,
+            ipt_decimals: pool.ipt_decimals,
+        }
+    }
+
+    /// Net USDC, withdrawal fee and exit adjustment for a `tier` holder
+    /// redeeming `ipt_amount`
+    fn quote(
+        &self,
+        ipt_amount: u64,
+        tier: InvestorTier,
+        exit_adjustment_bps: u16,
+    ) -> Result<(u64, u64, u64)> {
+        let (net_usdc_amount, withdrawal_fee) =
+            CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+                ipt_amount,
+                self.exchange_rate,
+                self.withdrawal_fee_bps[tier as usize],
+                self.usdc_decimals,
+                self.ipt_decimals,
+            )?;
+        let (net_usdc_amount, exit_adjustment) = CalculationUtils::apply_exit_adjustment(
+            net_usdc_amount,
+            withdrawal_fee,
+            exit_adjustment_bps,
+        )?;
+        Ok((net_usdc_amount, withdrawal_fee, exit_adjustment))
+    }
+}
+
+/// `amount × numerator / denominator`, rounded down
+fn pro_rata(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let scaled = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(PoolError::MathematicalOverflow)?
+        .checked_div(denominator as u128)
+        .ok_or(PoolError::DivisionByZero)?;
+    u64::try_from(scaled).map_err(|_| PoolError::MathematicalOverflow.into())
+}
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // Pubkey::default() withdraws a pending proposal
+    require!(
+        new_admin != pool.config.admin_authority,
+        PoolError::InvalidConfigParameter
+    );
+    pool.pending_admin = new_admin;
+
+    emit!(AdminTransferProposed {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        proposed_admin: new_admin,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Admin authority {} proposed to replace {}",
+        new_admin,
+        pool.config.admin_authority
+    );
+
+    Ok(())
+}
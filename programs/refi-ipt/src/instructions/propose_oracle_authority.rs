@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeOracleAuthority<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<ProposeOracleAuthority>, new_oracle: Pubkey) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // Pubkey::default() withdraws a pending proposal
+    require!(
+        new_oracle != pool.config.oracle_authority,
+        PoolError::InvalidConfigParameter
+    );
+    pool.pending_oracle_authority = new_oracle;
+
+    emit!(OracleRotationProposed {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        current_oracle: pool.config.oracle_authority,
+        proposed_oracle: new_oracle,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Oracle authority {} proposed to replace {}",
+        new_oracle,
+        pool.config.oracle_authority
+    );
+
+    Ok(())
+}
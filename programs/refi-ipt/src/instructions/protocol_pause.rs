@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProtocolPause<'info> {
+    pub protocol_admin: Signer<'info>,
+
+    /// Program-wide config account
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+        constraint = protocol_admin.key() == protocol_config.protocol_admin @ PoolError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+/// Engage or release the protocol-wide emergency halt
+pub fn handler(ctx: Context<ProtocolPause>, paused: bool) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let clock = Clock::get()?;
+
+    protocol_config.is_paused = paused;
+
+    emit!(ProtocolPauseUpdated {
+        protocol_admin: ctx.accounts.protocol_admin.key(),
+        is_paused: paused,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if paused {
+        msg!("CRITICAL: Protocol paused - all user operations halted");
+    } else {
+        msg!("Protocol unpaused - user operations resumed");
+    }
+
+    Ok(())
+}
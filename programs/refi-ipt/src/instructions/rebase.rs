@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::{CalculationUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Rebase<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.is_oracle(&oracle.key(), Clock::get()?.unix_timestamp) @ PoolError::UnauthorizedOracle,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// IPT distribution holders claim their rebase from
+    #[account(
+        init,
+        payer = oracle,
+        space = Distribution::LEN,
+        seeds = [
+            Distribution::SEED_PREFIX,
+            pool.key().as_ref(),
+            pool.next_distribution_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub distribution: Box<Account<'info, Distribution>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Rebase>, merkle_root: [u8; 32], snapshot_slot: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(pool.config.rebasing, PoolError::NotRebasingPool);
+    ValidationUtils::validate_not_paused(pool, PauseFlag::RateUpdates)?;
+    require!(snapshot_slot <= clock.slot, PoolError::InvalidConfigParameter);
+
+    let par = CalculationUtils::RATE_PRECISION as u64;
+    let old_rate = pool.current_exchange_rate;
+    require!(old_rate > par, PoolError::NothingToRebase);
+
+    // Yield becomes new IPT; the supply counts it now and holders mint it on claim
+    let minted_supply = CalculationUtils::calculate_rebase_mint(pool.total_ipt_supply, old_rate)?;
+    require!(minted_supply > 0, PoolError::NothingToRebase);
+
+    let new_ipt_supply = pool
+        .total_ipt_supply
+        .checked_add(minted_supply)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    if pool.max_total_supply > 0 {
+        require!(
+            new_ipt_supply <= pool.max_total_supply,
+            PoolError::MaxTotalSupplyExceeded
+        );
+    }
+
+    pool.total_ipt_supply = new_ipt_supply;
+    pool.current_exchange_rate = par;
+    pool.last_rate_update = clock.unix_timestamp;
+
+    let id = pool.next_distribution_id;
+    pool.next_distribution_id = id.checked_add(1).ok_or(PoolError::MathematicalOverflow)?;
+
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.pool = pool.key();
+    distribution.id = id;
+    distribution.kind = DistributionKind::IptRebase;
+    distribution.payout_vault = Pubkey::default();
+    distribution.merkle_root = merkle_root;
+    distribution.snapshot_slot = snapshot_slot;
+    distribution.total_amount = minted_supply;
+    distribution.claimed_amount = 0;
+    distribution.created_at = clock.unix_timestamp;
+    distribution.payer = ctx.accounts.oracle.key();
+    distribution.bump = ctx.bumps.distribution;
+
+    emit!(RebaseExecuted {
+        oracle: ctx.accounts.oracle.key(),
+        pool: pool.key(),
+        distribution: distribution.key(),
+        id,
+        old_rate,
+        minted_supply,
+        new_ipt_supply,
+        snapshot_slot,
+        merkle_root,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Rebased {} IPT at rate {} back to par, supply now {}",
+        minted_supply,
+        old_rate,
+        new_ipt_supply
+    );
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RecordPositionActivity<'info> {
+    pub owner: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Owner's position
+    #[account(
+        mut,
+        seeds = [Position::SEED_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+}
+
+pub fn handler(ctx: Context<RecordPositionActivity>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    let was_flagged = position.dormant_flagged_at != 0;
+    position.touch(clock.unix_timestamp);
+
+    emit!(PositionActivityRecorded {
+        owner: position.owner,
+        pool: ctx.accounts.pool.key(),
+        was_flagged,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Activity recorded for position of {}", position.owner);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::instructions::update_exchange_rate::apply_exchange_rate;
+use crate::states::*;
+use crate::utils::{CalculationUtils, OracleUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RefreshRateFromOracle<'info> {
+    /// Anyone may refresh the rate, e.g. a scheduled automation thread
+    pub cranker: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Pyth price update; owner, layout and feed id are checked in the handler
+    pub price_update: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<RefreshRateFromOracle>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.rate_feed_id != [0; 32],
+        PoolError::RateFeedNotSet
+    );
+
+    let feed = OracleUtils::read_pyth_price_update(&ctx.accounts.price_update)?;
+    require!(
+        feed.feed_id == pool.rate_feed_id,
+        PoolError::InvalidRateFeed
+    );
+    require!(
+        clock.unix_timestamp.saturating_sub(feed.publish_time) <= Pool::MAX_FEED_PRICE_AGE,
+        PoolError::StaleOraclePayload
+    );
+
+    // Reject prices the feed itself is unsure about
+    let max_conf = CalculationUtils::calculate_fee(
+        feed.price.max(0) as u64,
+        Pool::MAX_FEED_CONF_BPS,
+    )?;
+    require!(feed.conf <= max_conf, PoolError::InvalidExchangeRate);
+
+    let new_rate = CalculationUtils::rate_from_feed_price(feed.price, feed.exponent)?;
+
+    // Refreshes run on a fixed cadence, so an unchanged NAV only advances
+    // the observation time instead of failing as a no-op update
+    if new_rate == pool.current_exchange_rate {
+        ValidationUtils::validate_not_paused(pool, PauseFlag::RateUpdates)?;
+        require!(
+            feed.publish_time >= pool.last_rate_observed_at
+                && feed.publish_time <= clock.unix_timestamp,
+            PoolError::OutOfOrderRateUpdate
+        );
+        pool.last_rate_update = clock.unix_timestamp;
+        pool.last_rate_observed_at = feed.publish_time;
+
+        msg!("Exchange rate unchanged at {}", new_rate);
+        return Ok(());
+    }
+
+    let price_update = ctx.accounts.price_update.key();
+    apply_exchange_rate(pool, price_update, new_rate, feed.publish_time, 0)
+}
@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct RefundDeposit<'info> {
+    pub admin: Signer<'info>,
+
+    /// Depositor, who signs to have the minted IPT burned
+    pub user: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Depositor's position, which records the deposit being reversed
+    #[account(
+        mut,
+        seeds = [Position::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    /// User's USDC token account
+    #[account(
+        mut,
+        token::mint = pool.usdc_mint,
+        token::authority = user
+    )]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+
+    /// User's IPT token account
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = user
+    )]
+    pub user_ipt_account: Account<'info, TokenAccount>,
+
+    /// Pool's USDC reserve
+    #[account(
+        mut,
+        address = pool.usdc_reserve
+    )]
+    pub pool_usdc_reserve: Account<'info, TokenAccount>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    /// Referrer credited a share of the deposit's fee; required when one was
+    #[account(
+        mut,
+        seeds = [Referral::SEED_PREFIX, pool.key().as_ref(), referral.referrer.as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    /// Partner the deposit was attributed to; required when it was
+    #[account(
+        mut,
+        seeds = [
+            PartnerAccount::SEED_PREFIX,
+            pool.key().as_ref(),
+            partner_account.partner_id.to_le_bytes().as_ref()
+        ],
+        bump = partner_account.bump
+    )]
+    pub partner_account: Option<Account<'info, PartnerAccount>>,
+
+    /// Depositor's cost-basis ledger; required when the deposit opened a lot
+    #[account(
+        mut,
+        seeds = [CostBasisLedger::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = cost_basis.bump
+    )]
+    pub cost_basis: Option<Account<'info, CostBasisLedger>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RefundDeposit>, incident_hash: [u8; 32]) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let position = &mut ctx.accounts.position;
+    let pool_id_seed = pool.id_seed();
+    let clock = Clock::get()?;
+
+    // A closing pool's reserve belongs to the waterfall's claimants
+    require!(
+        pool.pool_state != PoolState::WindDown,
+        PoolError::PoolWindingDown
+    );
+    require!(incident_hash != [0; 32], PoolError::InvalidConfigParameter);
+    require!(
+        position.deposit_refundable(pool.config.refund_window_secs, clock.unix_timestamp),
+        PoolError::RefundWindowExpired
+    );
+
+    let ipt_amount = position.last_deposit_ipt;
+    let paid_usdc = position.last_deposit_usdc;
+    let fee = position.last_deposit_fee;
+    require!(
+        ctx.accounts.user_ipt_account.amount >= ipt_amount,
+        PoolError::InsufficientAccountBalance
+    );
+
+    // Take the referrer's share back; whatever they already claimed has
+    // left the reserve and is withheld from the refund instead
+    let referral_fee = position.last_deposit_referral_fee;
+    let mut withheld = 0;
+    if referral_fee > 0 {
+        let referral = ctx
+            .accounts
+            .referral
+            .as_mut()
+            .filter(|referral| referral.referrer == position.last_deposit_referrer)
+            .ok_or(PoolError::RefundAccountsMissing)?;
+        let recovered = referral_fee.min(referral.claimable);
+        withheld = referral_fee - recovered;
+        referral.claimable -= recovered;
+        referral.total_earned = referral
+            .total_earned
+            .checked_sub(referral_fee)
+            .ok_or(PoolError::MathematicalUnderflow)?;
+        referral.referred_deposits = referral
+            .referred_deposits
+            .checked_sub(1)
+            .ok_or(PoolError::MathematicalUnderflow)?;
+        pool.referral_payable = pool
+            .referral_payable
+            .checked_sub(recovered)
+            .ok_or(PoolError::MathematicalUnderflow)?;
+    }
+    let usdc_amount = paid_usdc - withheld;
+
+    // Liquidity earmarked for the queue is not available to refunds
+    require!(
+        pool.unreserved_liquidity(ctx.accounts.pool_usdc_reserve.amount) >= usdc_amount,
+        PoolError::InsufficientReserves
+    );
+
+    if position.last_deposit_partner != Pubkey::default() {
+        let partner = ctx
+            .accounts
+            .partner_account
+            .as_mut()
+            .filter(|partner| partner.key() == position.last_deposit_partner)
+            .ok_or(PoolError::RefundAccountsMissing)?;
+        let net_usdc = position.last_deposit_net_usdc;
+        partner.deposit_count = partner
+            .deposit_count
+            .checked_sub(1)
+            .ok_or(PoolError::MathematicalUnderflow)?;
+        partner.aum_contributed = partner
+            .aum_contributed
+            .checked_sub(net_usdc)
+            .ok_or(PoolError::MathematicalUnderflow)?;
+        // The partner was credited the fee the user actually paid
+        partner.fees_generated = partner
+            .fees_generated
+            .checked_sub(paid_usdc.saturating_sub(net_usdc))
+            .ok_or(PoolError::MathematicalUnderflow)?;
+    }
+
+    if position.last_deposit_lot {
+        let ledger = ctx
+            .accounts
+            .cost_basis
+            .as_mut()
+            .ok_or(PoolError::RefundAccountsMissing)?;
+        ledger.reverse_lot(position.last_deposit_at);
+    }
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.ipt_mint.to_account_info(),
+                from: ctx.accounts.user_ipt_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        ipt_amount,
+    )?;
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_usdc_reserve.to_account_info(),
+                to: ctx.accounts.user_usdc_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        usdc_amount,
+    )?;
+
+    // Undo the deposit's bookkeeping; the fee must not have been swept yet
+    pool.total_ipt_supply = pool
+        .total_ipt_supply
+        .checked_sub(ipt_amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.total_usdc_reserves = pool
+        .total_usdc_reserves
+        .checked_sub(usdc_amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.debit_fees(FeeBucket::Deposit, fee)?;
+    pool.restore_promo_budget(position.last_deposit_promo_usdc)?;
+
+    let net_usdc_amount = position.last_deposit_net_usdc;
+    position.reverse_entry(ipt_amount, net_usdc_amount);
+    let deposited_at = position.last_deposit_at;
+    position.last_deposit_ipt = 0;
+    position.last_deposit_usdc = 0;
+    position.last_deposit_fee = 0;
+    position.last_deposit_net_usdc = 0;
+    position.last_deposit_referrer = Pubkey::default();
+    position.last_deposit_referral_fee = 0;
+    position.last_deposit_partner = Pubkey::default();
+    position.last_deposit_promo_usdc = 0;
+    position.last_deposit_lot = false;
+
+    emit!(DepositRefunded {
+        admin: ctx.accounts.admin.key(),
+        user: ctx.accounts.user.key(),
+        pool: pool.key(),
+        incident_hash,
+        ipt_burned: ipt_amount,
+        usdc_refunded: usdc_amount,
+        fee_reversed: fee,
+        referral_fee_reversed: referral_fee,
+        referral_fee_withheld: withheld,
+        deposited_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Refunded deposit: burned {} IPT, returned {} USDC",
+        ipt_amount,
+        usdc_amount
+    );
+
+    Ok(())
+}
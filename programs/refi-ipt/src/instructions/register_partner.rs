@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(partner_id: u64)]
+pub struct RegisterPartner<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Partner's accounting record in this pool
+    #[account(
+        init,
+        payer = admin,
+        space = PartnerAccount::LEN,
+        seeds = [
+            PartnerAccount::SEED_PREFIX,
+            pool.key().as_ref(),
+            partner_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub partner_account: Account<'info, PartnerAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterPartner>, partner_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let partner = &mut ctx.accounts.partner_account;
+    partner.pool = ctx.accounts.pool.key();
+    partner.partner_id = partner_id;
+    partner.deposit_count = 0;
+    partner.aum_contributed = 0;
+    partner.fees_generated = 0;
+    partner.registered_at = clock.unix_timestamp;
+    partner.bump = ctx.bumps.partner_account;
+
+    emit!(PartnerRegistered {
+        admin: ctx.accounts.admin.key(),
+        pool: partner.pool,
+        partner_id,
+        partner_account: partner.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Partner {} registered", partner_id);
+
+    Ok(())
+}
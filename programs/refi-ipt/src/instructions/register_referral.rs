@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RegisterReferral<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// Pool the referral applies to
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Referrer's record in this pool
+    #[account(
+        init,
+        payer = referrer,
+        space = Referral::LEN,
+        seeds = [Referral::SEED_PREFIX, pool.key().as_ref(), referrer.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterReferral>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let referral = &mut ctx.accounts.referral;
+    referral.pool = ctx.accounts.pool.key();
+    referral.referrer = ctx.accounts.referrer.key();
+    referral.referred_deposits = 0;
+    referral.total_earned = 0;
+    referral.claimable = 0;
+    referral.bump = ctx.bumps.referral;
+
+    emit!(ReferralRegistered {
+        referrer: referral.referrer,
+        pool: referral.pool,
+        referral: referral.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Referral registered for {}", referral.referrer);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveFromAccessList<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    // remaining_accounts: the writable access list entry of each address,
+    // in the same order; each is closed and its rent returned to the admin
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveFromAccessList<'info>>,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_key = pool.key();
+    let clock = Clock::get()?;
+
+    require!(
+        addresses.len() <= AccessListEntry::MAX_BATCH,
+        PoolError::AccessListBatchTooLarge
+    );
+    require!(
+        ctx.remaining_accounts.len() == addresses.len(),
+        PoolError::InvalidAccountsCount
+    );
+
+    let admin = ctx.accounts.admin.to_account_info();
+    for (address, info) in addresses.iter().zip(ctx.remaining_accounts.iter()) {
+        let entry: Account<AccessListEntry> = Account::try_from(info)?;
+        require!(
+            entry.pool == pool_key && entry.address == *address,
+            PoolError::InvalidAccessListEntry
+        );
+
+        if entry.status == AccessStatus::Blocked {
+            pool.blocked_count = pool
+                .blocked_count
+                .checked_sub(1)
+                .ok_or(PoolError::MathematicalUnderflow)?;
+        }
+        entry.close(admin.clone())?;
+    }
+
+    emit!(AccessListUpdated {
+        admin: admin.key(),
+        pool: pool_key,
+        status: None,
+        addresses: addresses.clone(),
+        blocked_count: pool.blocked_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Removed {} addresses from the access list", addresses.len());
+
+    Ok(())
+}
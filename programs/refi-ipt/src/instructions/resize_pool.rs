@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ResizePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account, resized to exactly fit its queue (the admin pays
+    /// for growth and receives the rent freed by shrinking); migrate_pool
+    /// resizes pools on older layouts as it upgrades them
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        realloc = Pool::space(pool.required_queue_capacity() as usize),
+        realloc::payer = admin,
+        realloc::zero = false,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ResizePool>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let old_queue_capacity = pool.queue_capacity;
+    pool.queue_capacity = pool.required_queue_capacity();
+    let account_size = pool.to_account_info().data_len() as u64;
+
+    emit!(PoolResized {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        old_queue_capacity,
+        queue_capacity: pool.queue_capacity,
+        account_size,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Pool resized to {} bytes (queue capacity {} -> {})",
+        account_size,
+        old_queue_capacity,
+        pool.queue_capacity
+    );
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::instructions::user_withdraw::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevealWithdrawal<'info> {
+    /// Same accounts as user_withdraw
+    pub withdraw: UserWithdraw<'info>,
+
+    /// Commitment being revealed; closed to the user on success
+    #[account(
+        mut,
+        seeds = [
+            WithdrawalCommitment::SEED_PREFIX,
+            withdraw.pool.key().as_ref(),
+            withdraw.user.key().as_ref()
+        ],
+        bump = withdrawal_commitment.bump
+    )]
+    pub withdrawal_commitment: Account<'info, WithdrawalCommitment>,
+}
+
+pub fn handler(
+    ctx: Context<RevealWithdrawal>,
+    net_ipt_amount: u64,
+    min_usdc_amount: u64,
+    salt: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = ctx.accounts.withdraw.pool.key();
+    let user = ctx.accounts.withdraw.user.key();
+    let commitment = &ctx.accounts.withdrawal_commitment;
+
+    require!(
+        WithdrawalCommitment::digest(&pool, &user, net_ipt_amount, min_usdc_amount, &salt)
+            == commitment.commitment,
+        PoolError::CommitmentMismatch
+    );
+
+    // The delay is read from the current config so lowering it cannot be
+    // raced by a commitment made under the old one
+    let revealable_slot = commitment
+        .committed_slot
+        .saturating_add(ctx.accounts.withdraw.pool.config.reveal_delay_slots);
+    require!(clock.slot >= revealable_slot, PoolError::RevealTooEarly);
+
+    emit!(WithdrawalRevealed {
+        user,
+        pool,
+        ipt_amount: net_ipt_amount,
+        min_usdc_amount,
+        committed_slot: commitment.committed_slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    withdraw(&mut ctx.accounts.withdraw, net_ipt_amount, min_usdc_amount)?;
+
+    let user_info = ctx.accounts.withdraw.user.to_account_info();
+    ctx.accounts.withdrawal_commitment.close(user_info)
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::instructions::user_deposit::{self, UserDeposit, UserDepositBumps};
+use crate::states::*;
+use crate::utils::CalculationUtils;
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+#[derive(Accounts)]
+pub struct RouteDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // remaining_accounts: the full user_deposit account list for each leg,
+    // in leg order; optional accounts are passed as this program's ID
+}
+
+/// Split `net_usdc_amount` across pools by the legs' weights and deposit
+/// each share, so a model portfolio is bought in one transaction
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RouteDeposit<'info>>,
+    net_usdc_amount: u64,
+    legs: Vec<RouteLeg>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        !legs.is_empty() && legs.len() <= RouteLeg::MAX_LEGS,
+        PoolError::InvalidRouteLegCount
+    );
+    let weights: Vec<u16> = legs.iter().map(|leg| leg.weight_bps).collect();
+    let amounts = CalculationUtils::split_by_weights(net_usdc_amount, &weights)?;
+
+    let user = ctx.accounts.user.key();
+    let mut accounts: &'info [AccountInfo<'info>] = ctx.remaining_accounts;
+    let mut pools = Vec::with_capacity(legs.len());
+    let mut usdc_paid: u64 = 0;
+
+    // Legs are loaded and settled one at a time, so a pool or token account
+    // shared between legs is read fresh by each
+    for (leg, amount) in legs.iter().zip(amounts.iter()) {
+        let mut bumps = UserDepositBumps::default();
+        let mut leg_accounts = UserDeposit::try_accounts(
+            ctx.program_id,
+            &mut accounts,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )?;
+        require_keys_eq!(
+            leg_accounts.user.key(),
+            user,
+            PoolError::RouteUserMismatch
+        );
+        pools.push(leg_accounts.pool.key());
+
+        let paid = user_deposit::deposit(
+            Context::new(ctx.program_id, &mut leg_accounts, &[], bumps),
+            *amount,
+            leg.min_ipt_amount,
+        )?;
+        leg_accounts.exit(ctx.program_id)?;
+
+        usdc_paid = usdc_paid
+            .checked_add(paid)
+            .ok_or(PoolError::MathematicalOverflow)?;
+    }
+
+    emit!(DepositRouted {
+        user,
+        pools,
+        net_usdc_amounts: amounts,
+        usdc_paid,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Routed {} USDC across {} pools ({} paid with fees)",
+        net_usdc_amount,
+        legs.len(),
+        usdc_paid
+    );
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ScheduleAdminAction<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Scheduled operation (one PDA per id)
+    #[account(
+        init,
+        payer = admin,
+        space = TimelockOperation::LEN,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            pool.next_timelock_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ScheduleAdminAction>, action: AdminAction) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_admin_action(&action)?;
+
+    // The destination is fixed now, not when the action executes
+    if let AdminAction::EscheatPosition { custody, .. } = &action {
+        require_keys_eq!(
+            *custody,
+            pool.config.escheat_custody,
+            PoolError::EscheatCustodyMismatch
+        );
+    }
+
+    let id = pool.next_timelock_id;
+    let executable_at = clock
+        .unix_timestamp
+        .checked_add(pool.config.timelock_delay)
+        .ok_or(PoolError::MathematicalOverflow)?;
+
+    let operation = &mut ctx.accounts.timelock_operation;
+    operation.pool = pool.key();
+    operation.id = id;
+    operation.action = action.clone();
+    operation.scheduled_at = clock.unix_timestamp;
+    operation.executable_at = executable_at;
+    operation.bump = ctx.bumps.timelock_operation;
+
+    pool.next_timelock_id = id.checked_add(1).ok_or(PoolError::MathematicalOverflow)?;
+
+    emit!(AdminActionScheduled {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        id,
+        action,
+        executable_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Admin action {} scheduled, executable at {}",
+        id,
+        executable_at
+    );
+
+    Ok(())
+}
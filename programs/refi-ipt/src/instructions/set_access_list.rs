@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+#[derive(Accounts)]
+pub struct SetAccessList<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub system_program: Program<'info, System>,
+
+    // remaining_accounts: the writable access list entry PDA of each
+    // address, in the same order; missing entries are created
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetAccessList<'info>>,
+    status: AccessStatus,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_key = pool.key();
+    let clock = Clock::get()?;
+
+    require!(
+        addresses.len() <= AccessListEntry::MAX_BATCH,
+        PoolError::AccessListBatchTooLarge
+    );
+    require!(
+        ctx.remaining_accounts.len() == addresses.len(),
+        PoolError::InvalidAccountsCount
+    );
+
+    let entry_space = AccessListEntry::LEN;
+    for (address, info) in addresses.iter().zip(ctx.remaining_accounts.iter()) {
+        let (expected, bump) = Pubkey::find_program_address(
+            &[AccessListEntry::SEED_PREFIX, pool_key.as_ref(), address.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(info.key(), expected, PoolError::InvalidAccessListEntry);
+
+        let previous = if *info.owner == crate::ID {
+            Some(AccessListEntry::try_deserialize(&mut &info.try_borrow_data()?[..])?)
+        } else {
+            create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    CreateAccount {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: info.clone(),
+                    },
+                    &[&[
+                        AccessListEntry::SEED_PREFIX,
+                        pool_key.as_ref(),
+                        address.as_ref(),
+                        &[bump],
+                    ]],
+                ),
+                Rent::get()?.minimum_balance(entry_space),
+                entry_space as u64,
+                ctx.program_id,
+            )?;
+            None
+        };
+
+        let entry = AccessListEntry {
+            pool: pool_key,
+            address: *address,
+            status,
+            // Listing again keeps the tier set by set_investor_tier
+            tier: previous.as_ref().map_or(InvestorTier::default(), |entry| entry.tier),
+            updated_at: clock.unix_timestamp,
+            bump,
+        };
+        entry.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        let was_blocked = previous.map(|entry| entry.status) == Some(AccessStatus::Blocked);
+        let is_blocked = status == AccessStatus::Blocked;
+        if is_blocked && !was_blocked {
+            pool.blocked_count = pool
+                .blocked_count
+                .checked_add(1)
+                .ok_or(PoolError::MathematicalOverflow)?;
+        } else if was_blocked && !is_blocked {
+            pool.blocked_count = pool
+                .blocked_count
+                .checked_sub(1)
+                .ok_or(PoolError::MathematicalUnderflow)?;
+        }
+    }
+
+    emit!(AccessListUpdated {
+        admin: ctx.accounts.admin.key(),
+        pool: pool_key,
+        status: Some(status),
+        addresses: addresses.clone(),
+        blocked_count: pool.blocked_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Set {} addresses to {:?}", addresses.len(), status);
+
+    Ok(())
+}
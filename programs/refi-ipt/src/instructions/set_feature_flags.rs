@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeatureFlags<'info> {
+    pub protocol_admin: Signer<'info>,
+
+    /// Program-wide config account
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+        constraint = protocol_admin.key() == protocol_config.protocol_admin @ PoolError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+/// Replace the set of subsystems switched off for every pool
+pub fn handler(ctx: Context<SetFeatureFlags>, disabled_features: u32) -> Result<()> {
+    require!(
+        disabled_features & !Feature::ALL == 0,
+        PoolError::InvalidConfigParameter
+    );
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let old_disabled_features = protocol_config.disabled_features;
+    protocol_config.disabled_features = disabled_features;
+
+    emit!(FeatureFlagsUpdated {
+        protocol_admin: ctx.accounts.protocol_admin.key(),
+        old_disabled_features,
+        new_disabled_features: disabled_features,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Disabled features changed from {:#06b} to {:#06b}",
+        old_disabled_features,
+        disabled_features
+    );
+
+    Ok(())
+}
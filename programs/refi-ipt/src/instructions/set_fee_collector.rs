@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::instructions::admin_update_config::{apply_config, AdminUpdateConfig};
+use anchor_lang::prelude::*;
+
+/// Change the fee collector, leaving the rest of the config as it is
+pub fn handler(ctx: Context<AdminUpdateConfig>, fee_collector: Pubkey) -> Result<()> {
+    let mut new_config = ctx.accounts.pool.config.clone();
+    new_config.fee_collector = fee_collector;
+
+    apply_config(
+        &mut ctx.accounts.pool,
+        ctx.accounts.admin.key(),
+        &ctx.accounts.protocol_config,
+        new_config,
+    )
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::instructions::admin_update_config::{apply_config, AdminUpdateConfig};
+use anchor_lang::prelude::*;
+
+/// Change the deposit, withdrawal and management fee rates, leaving the
+/// rest of the config as it is
+pub fn handler(
+    ctx: Context<AdminUpdateConfig>,
+    deposit_fee_bps: u16,
+    withdrawal_fee_bps: u16,
+    management_fee_bps: u16,
+) -> Result<()> {
+    let mut new_config = ctx.accounts.pool.config.clone();
+    new_config.deposit_fee_bps = deposit_fee_bps;
+    new_config.withdrawal_fee_bps = withdrawal_fee_bps;
+    new_config.management_fee_bps = management_fee_bps;
+
+    apply_config(
+        &mut ctx.accounts.pool,
+        ctx.accounts.admin.key(),
+        &ctx.accounts.protocol_config,
+        new_config,
+    )
+}
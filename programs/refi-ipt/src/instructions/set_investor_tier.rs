@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetInvestorTier<'info> {
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    // remaining_accounts: the writable access list entry of each address,
+    // in the same order; addresses must already be on the access list
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetInvestorTier<'info>>,
+    tier: InvestorTier,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    let pool_key = ctx.accounts.pool.key();
+    let clock = Clock::get()?;
+
+    require!(
+        addresses.len() <= AccessListEntry::MAX_BATCH,
+        PoolError::AccessListBatchTooLarge
+    );
+    require!(
+        ctx.remaining_accounts.len() == addresses.len(),
+        PoolError::InvalidAccountsCount
+    );
+
+    for (address, info) in addresses.iter().zip(ctx.remaining_accounts.iter()) {
+        let mut entry: Account<AccessListEntry> = Account::try_from(info)?;
+        require!(
+            entry.pool == pool_key && entry.address == *address,
+            PoolError::InvalidAccessListEntry
+        );
+
+        entry.tier = tier;
+        entry.updated_at = clock.unix_timestamp;
+        entry.exit(ctx.program_id)?;
+    }
+
+    emit!(InvestorTierSet {
+        admin: ctx.accounts.admin.key(),
+        pool: pool_key,
+        tier,
+        addresses: addresses.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Set {} addresses to the {:?} tier", addresses.len(), tier);
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::instructions::admin_update_config::{apply_config, AdminUpdateConfig};
+use anchor_lang::prelude::*;
+
+/// Change the withdrawal queue cap, within the allocated queue capacity
+pub fn handler(ctx: Context<AdminUpdateConfig>, max_queue_size: u32) -> Result<()> {
+    let mut new_config = ctx.accounts.pool.config.clone();
+    new_config.max_queue_size = max_queue_size;
+
+    apply_config(
+        &mut ctx.accounts.pool,
+        ctx.accounts.admin.key(),
+        &ctx.accounts.protocol_config,
+        new_config,
+    )
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxTotalSupply<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<SetMaxTotalSupply>, new_max_total_supply: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // 0 lifts the cap; any other value must still cover the circulating supply
+    require!(
+        new_max_total_supply == 0 || new_max_total_supply >= pool.total_ipt_supply,
+        PoolError::MaxTotalSupplyBelowCurrentSupply
+    );
+
+    let old_max_total_supply = pool.max_total_supply;
+
+    // Keep the enforced cap and the stored config in sync
+    pool.max_total_supply = new_max_total_supply;
+    pool.config.max_total_supply = new_max_total_supply;
+
+    emit!(MaxTotalSupplyUpdated {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        old_max_total_supply,
+        new_max_total_supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Max total supply updated from {} to {}",
+        old_max_total_supply,
+        new_max_total_supply
+    );
+
+    Ok(())
+}
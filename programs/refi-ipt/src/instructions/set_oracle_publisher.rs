@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetOraclePublisher<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<SetOraclePublisher>, new_publisher: Pubkey) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // Pubkey::default() disables signed rate updates
+    let old_publisher = pool.oracle_publisher;
+    pool.oracle_publisher = new_publisher;
+
+    emit!(OraclePublisherUpdated {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        old_publisher,
+        new_publisher,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Oracle publisher updated from {} to {}",
+        old_publisher,
+        new_publisher
+    );
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPauseFlag<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<SetPauseFlag>, flag: PauseFlag, paused: bool) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let old_flags = pool.pause_flags;
+    if paused {
+        pool.pause_flags |= flag.mask();
+    } else {
+        pool.pause_flags &= !flag.mask();
+    }
+
+    emit!(PauseFlagsUpdated {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        old_flags,
+        new_flags: pool.pause_flags,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Pause flags updated from {:#06b} to {:#06b}",
+        old_flags,
+        pool.pause_flags
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPoolMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(
+    ctx: Context<SetPoolMetadata>,
+    name: String,
+    description_uri: String,
+    logo_uri: String,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        name.len() <= Pool::MAX_NAME_LEN
+            && description_uri.len() <= Pool::MAX_URI_LEN
+            && logo_uri.len() <= Pool::MAX_URI_LEN,
+        PoolError::MetadataTooLong
+    );
+
+    pool.name = name;
+    pool.description_uri = description_uri;
+    pool.logo_uri = logo_uri;
+
+    emit!(PoolMetadataUpdated {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        name: pool.name.clone(),
+        description_uri: pool.description_uri.clone(),
+        logo_uri: pool.logo_uri.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Pool metadata updated: {}", pool.name);
+
+    Ok(())
+}
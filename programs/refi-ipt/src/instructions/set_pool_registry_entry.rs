@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPoolRegistryEntry<'info> {
+    #[account(mut)]
+    pub protocol_admin: Signer<'info>,
+
+    /// Program-wide config account
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+        constraint = protocol_admin.key() == protocol_config.protocol_admin @ PoolError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Pool being described
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The pool's registry entry, created on first use
+    #[account(
+        init_if_needed,
+        payer = protocol_admin,
+        space = PoolRegistryEntry::LEN,
+        seeds = [PoolRegistryEntry::SEED_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub registry_entry: Account<'info, PoolRegistryEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetPoolRegistryEntry>,
+    category: PoolCategory,
+    risk_tier: RiskTier,
+    tags: u32,
+    verified: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = ctx.accounts.pool.key();
+
+    let entry = &mut ctx.accounts.registry_entry;
+    entry.pool = pool;
+    entry.category = category;
+    entry.risk_tier = risk_tier;
+    entry.tags = tags;
+    entry.verified = verified;
+    entry.updated_at = clock.unix_timestamp;
+    entry.bump = ctx.bumps.registry_entry;
+
+    emit!(PoolRegistryEntryUpdated {
+        protocol_admin: ctx.accounts.protocol_admin.key(),
+        pool,
+        category,
+        risk_tier,
+        tags,
+        verified,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Registry entry updated (verified: {})", verified);
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPoolState<'info> {
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<SetPoolState>, new_state: PoolState) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+    let old_state = pool.pool_state;
+
+    // Pausing individual flows stays with set_pause_flag; migration and
+    // wind-down go through their own instructions
+    require!(
+        old_state.admin_can_set(new_state),
+        PoolError::InvalidPoolStateTransition
+    );
+    // As with unfreeze_pool, the rate has to be live again first
+    if new_state == PoolState::Active {
+        require!(
+            !pool.dead_man_switch_due(clock.unix_timestamp),
+            PoolError::OracleStale
+        );
+    }
+
+    pool.pool_state = new_state;
+
+    emit!(PoolStateChanged {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        old_state,
+        new_state,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Pool state changed from {:?} to {:?}", old_state, new_state);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetQueueProcessingPaused<'info> {
+    /// Pool admin or guardian
+    pub authority: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.config.can_pause_queue(&authority.key()) @ PoolError::UnauthorizedGuardian,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Halt or resume batch_execute_withdraw; users can still queue and cancel
+pub fn handler(ctx: Context<SetQueueProcessingPaused>, paused: bool) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let old_flags = pool.pause_flags;
+    if paused {
+        pool.pause_flags |= PauseFlag::Queue.mask();
+    } else {
+        pool.pause_flags &= !PauseFlag::Queue.mask();
+    }
+
+    emit!(PauseFlagsUpdated {
+        admin: ctx.accounts.authority.key(),
+        pool: pool.key(),
+        old_flags,
+        new_flags: pool.pause_flags,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Queue processing {}",
+        if paused { "paused" } else { "resumed" }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRateFeed<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<SetRateFeed>, new_feed_id: [u8; 32]) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // An all-zero feed id disables oracle refreshes
+    let old_feed_id = pool.rate_feed_id;
+    pool.rate_feed_id = new_feed_id;
+
+    emit!(RateFeedUpdated {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        old_feed_id,
+        new_feed_id,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Rate feed updated");
+
+    Ok(())
+}
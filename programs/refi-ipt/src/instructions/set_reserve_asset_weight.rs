@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetReserveAssetWeight<'info> {
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [
+            ReserveAsset::SEED_PREFIX,
+            pool.key().as_ref(),
+            reserve_asset.mint.as_ref()
+        ],
+        bump = reserve_asset.bump
+    )]
+    pub reserve_asset: Account<'info, ReserveAsset>,
+}
+
+pub fn handler(ctx: Context<SetReserveAssetWeight>, target_weight_bps: u16) -> Result<()> {
+    require!(target_weight_bps <= 10_000, PoolError::InvalidConfigParameter);
+
+    let asset = &mut ctx.accounts.reserve_asset;
+    let old_target_weight_bps = asset.target_weight_bps;
+    asset.target_weight_bps = target_weight_bps;
+
+    emit!(ReserveAssetWeightUpdated {
+        admin: ctx.accounts.admin.key(),
+        pool: ctx.accounts.pool.key(),
+        mint: asset.mint,
+        old_target_weight_bps,
+        new_target_weight_bps: target_weight_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRoleAuthority<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Scheduled SetRoleAuthority operation (consumed on execution)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            timelock_operation.id.to_le_bytes().as_ref()
+        ],
+        bump = timelock_operation.bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+}
+
+pub fn handler(ctx: Context<SetRoleAuthority>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let operation = &ctx.accounts.timelock_operation;
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_timelock_elapsed(operation, clock.unix_timestamp)?;
+
+    let AdminAction::SetRoleAuthority { role, authority } = operation.action.clone() else {
+        return err!(PoolError::TimelockActionMismatch);
+    };
+
+    let mut new_config = pool.config.clone();
+    let old_authority = std::mem::replace(role.authority_mut(&mut new_config), authority);
+    ValidationUtils::validate_pool_config(&new_config, pool.queue_capacity)?;
+    pool.config = new_config;
+
+    emit!(RoleAuthorityChanged {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        operation_id: operation.id,
+        role,
+        old_authority,
+        new_authority: authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "{:?} authority changed from {} to {}",
+        role,
+        old_authority,
+        authority
+    );
+
+    Ok(())
+}
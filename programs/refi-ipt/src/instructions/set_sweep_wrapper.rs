@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetSweepWrapper<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Scheduled SetSweepWrapper operation (consumed on execution)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            timelock_operation.id.to_le_bytes().as_ref()
+        ],
+        bump = timelock_operation.bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+}
+
+pub fn handler(ctx: Context<SetSweepWrapper>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let operation = &ctx.accounts.timelock_operation;
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_timelock_elapsed(operation, clock.unix_timestamp)?;
+
+    let AdminAction::SetSweepWrapper { program, mint } = operation.action.clone() else {
+        return err!(PoolError::TimelockActionMismatch);
+    };
+
+    // Swept USDC can only come back through the wrapper it went into
+    require!(pool.swept_usdc == 0, PoolError::SweptUsdcOutstanding);
+
+    let old_program = pool.config.sweep_wrapper_program;
+    let old_mint = pool.config.sweep_wrapped_mint;
+    pool.config.sweep_wrapper_program = program;
+    pool.config.sweep_wrapped_mint = mint;
+
+    emit!(SweepWrapperChanged {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        operation_id: operation.id,
+        old_program,
+        old_mint,
+        new_program: program,
+        new_mint: mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Sweep wrapper changed to {} ({})", program, mint);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetTreasurer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Current treasurer, agreeing to hand over the role
+    pub treasurer: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = treasurer.key() == pool.config.treasurer_authority @ PoolError::UnauthorizedTreasurer,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Scheduled SetTreasurer operation (consumed on execution)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            timelock_operation.id.to_le_bytes().as_ref()
+        ],
+        bump = timelock_operation.bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+}
+
+pub fn handler(ctx: Context<SetTreasurer>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let operation = &ctx.accounts.timelock_operation;
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_timelock_elapsed(operation, clock.unix_timestamp)?;
+
+    let AdminAction::SetTreasurer { treasurer } = operation.action.clone() else {
+        return err!(PoolError::TimelockActionMismatch);
+    };
+
+    // Role segregation still holds under the new treasurer
+    let old_treasurer = pool.config.treasurer_authority;
+    let mut new_config = pool.config.clone();
+    new_config.treasurer_authority = treasurer;
+    ValidationUtils::validate_pool_config(&new_config, pool.queue_capacity)?;
+    pool.config = new_config;
+
+    emit!(TreasurerChanged {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        operation_id: operation.id,
+        old_treasurer,
+        new_treasurer: treasurer,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "CRITICAL: Treasurer authority changed from {} to {}",
+        old_treasurer,
+        treasurer
+    );
+
+    Ok(())
+}
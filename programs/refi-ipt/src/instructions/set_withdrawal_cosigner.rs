@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetWithdrawalCosigner<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Current co-signer, required while one is configured so the admin
+    /// cannot switch the check off alone
+    pub cosigner: Option<Signer<'info>>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Scheduled SetWithdrawalCosigner operation (consumed on execution)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            timelock_operation.id.to_le_bytes().as_ref()
+        ],
+        bump = timelock_operation.bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+}
+
+pub fn handler(ctx: Context<SetWithdrawalCosigner>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let operation = &ctx.accounts.timelock_operation;
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_timelock_elapsed(operation, clock.unix_timestamp)?;
+
+    let AdminAction::SetWithdrawalCosigner { cosigner, threshold } = operation.action.clone()
+    else {
+        return err!(PoolError::TimelockActionMismatch);
+    };
+
+    let old_cosigner = pool.config.withdrawal_cosigner;
+    let old_threshold = pool.config.large_withdrawal_threshold;
+    if old_cosigner != Pubkey::default() {
+        require!(
+            ctx.accounts.cosigner.as_ref().map(|c| c.key()) == Some(old_cosigner),
+            PoolError::CosignerRequired
+        );
+    }
+
+    let mut new_config = pool.config.clone();
+    new_config.withdrawal_cosigner = cosigner;
+    new_config.large_withdrawal_threshold = threshold;
+    ValidationUtils::validate_pool_config(&new_config, pool.queue_capacity)?;
+    pool.config = new_config;
+
+    emit!(WithdrawalCosignerChanged {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        operation_id: operation.id,
+        old_cosigner,
+        new_cosigner: cosigner,
+        old_threshold,
+        new_threshold: threshold,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "CRITICAL: Withdrawal co-signer changed from {} to {} above {} USDC per epoch",
+        old_cosigner,
+        cosigner,
+        threshold
+    );
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::instructions::cancel_withdrawal::charge_cancellation_fee;
+use crate::states::*;
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::utils::ValidationUtils;
+
+#[derive(Accounts)]
+pub struct ShrinkWithdrawalRequest<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// User's IPT token account (cancellation fee is burned from here)
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = user
+    )]
+    pub user_ipt_account: Account<'info, TokenAccount>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ShrinkWithdrawalRequest>, cancel_amount: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let user = ctx.accounts.user.key();
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_protocol_not_paused(&ctx.accounts.protocol_config)?;
+
+    // Find user's withdrawal request in queue
+    let position = pool
+        .queued()
+        .iter()
+        .position(|w| w.user == user)
+        .ok_or(PoolError::InvalidUserAccount)?;
+    let request = pool.queued()[position].clone();
+
+    // Cancelling everything goes through cancel_withdrawal_request
+    require!(
+        cancel_amount > 0 && cancel_amount < request.amount,
+        PoolError::InvalidAmount
+    );
+
+    let remaining_amount = request.amount - cancel_amount;
+    // Slippage floor shrinks with the request
+    let min_usdc_amount = (request.min_usdc_amount as u128 * remaining_amount as u128
+        / request.amount as u128) as u64;
+
+    let (cancellation_fee, cancellation_fee_usdc) = charge_cancellation_fee(
+        pool,
+        &ctx.accounts.user,
+        &ctx.accounts.user_ipt_account,
+        &ctx.accounts.ipt_mint,
+        &ctx.accounts.token_program,
+        cancel_amount,
+        request.requested_at,
+        clock.unix_timestamp,
+    )?;
+
+    let entry = &mut pool.queued_mut()[position];
+    entry.amount = remaining_amount;
+    entry.min_usdc_amount = min_usdc_amount;
+
+    // The entry keeps its place; a smaller request can only move up under
+    // size-weighted priority
+    if pool.config.queue_size_weight > 0 {
+        pool.reprioritize_queue();
+    }
+
+    ValidationUtils::check_queue_depth(pool, clock.unix_timestamp)?;
+
+    emit!(WithdrawalShrunk {
+        user,
+        cancelled_amount: cancel_amount,
+        remaining_amount,
+        position: position as u32,
+        cancellation_fee,
+        cancellation_fee_usdc,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "User {} cancelled {} IPT of their withdrawal request, {} IPT stays queued at position {}",
+        user,
+        cancel_amount,
+        remaining_amount,
+        position
+    );
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::{TokenUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    /// Anyone may sweep once fees reach the threshold
+    pub cranker: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Fee collector's USDC associated token account; the only possible destination
+    #[account(
+        mut,
+        associated_token::mint = pool.usdc_mint,
+        associated_token::authority = pool.config.fee_collector
+    )]
+    pub fee_collector_usdc_account: Account<'info, TokenAccount>,
+
+    /// Pool's USDC reserve
+    #[account(
+        mut,
+        address = pool.usdc_reserve
+    )]
+    pub pool_usdc_reserve: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    // remaining_accounts: the token account of each configured fee
+    // recipient, in config order
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SweepFees<'info>>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_fee_payout(pool)?;
+
+    // Sweeping everything at once keeps dust transfers out of the reserve
+    let threshold = pool.config.fee_sweep_threshold;
+    require!(
+        threshold > 0 && pool.total_accumulated_fees >= threshold,
+        PoolError::FeeSweepBelowThreshold
+    );
+
+    let amount = pool.total_accumulated_fees;
+    require!(
+        ctx.accounts.pool_usdc_reserve.amount >= amount,
+        PoolError::InsufficientReserves
+    );
+
+    let recipients = pool.config.fee_recipients.clone();
+    require!(
+        ctx.remaining_accounts.len() == recipients.len(),
+        PoolError::InvalidAccountsCount
+    );
+    let recipient_amounts = pool.config.fee_split(amount)?;
+    let signer_seeds = pool.authority_seeds(&pool_id_seed);
+
+    // Every share goes out in this transaction or none does
+    let mut collector_amount = amount;
+    for ((recipient, account), share) in recipients
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+        .zip(recipient_amounts.iter().copied())
+    {
+        require_keys_eq!(
+            account.key(),
+            recipient.token_account,
+            PoolError::FeeRecipientMismatch
+        );
+        require_keys_eq!(
+            TokenUtils::read_token_account(account)?.mint,
+            pool.usdc_mint,
+            PoolError::InvalidMint
+        );
+        if share == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_usdc_reserve.to_account_info(),
+                    to: account.clone(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[&signer_seeds],
+            ),
+            share,
+        )?;
+        collector_amount -= share;
+    }
+
+    if collector_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_usdc_reserve.to_account_info(),
+                    to: ctx.accounts.fee_collector_usdc_account.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[&signer_seeds],
+            ),
+            collector_amount,
+        )?;
+    }
+
+    let deposit_fees = pool.fee_balance(FeeBucket::Deposit);
+    let withdrawal_fees = pool.fee_balance(FeeBucket::Withdrawal);
+    let management_fees = pool.fee_balance(FeeBucket::Management);
+    pool.debit_fees(FeeBucket::Deposit, deposit_fees)?;
+    pool.debit_fees(FeeBucket::Withdrawal, withdrawal_fees)?;
+    pool.debit_fees(FeeBucket::Management, management_fees)?;
+
+    pool.total_usdc_reserves = pool
+        .total_usdc_reserves
+        .checked_sub(amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+
+    emit!(FeesSwept {
+        cranker: ctx.accounts.cranker.key(),
+        pool: pool.key(),
+        fee_collector_usdc_account: ctx.accounts.fee_collector_usdc_account.key(),
+        amount,
+        deposit_fees,
+        withdrawal_fees,
+        management_fees,
+        recipient_amounts,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Swept {} USDC of fees, {} to the fee collector",
+        amount,
+        collector_amount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::{TokenUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct SweepIdleReserve<'info> {
+    /// Treasurer authority; pays for the wrapper vault on first use. Only
+    /// the treasurer may hand the wrapper accounts signed for by the pool
+    /// authority
+    #[account(
+        mut,
+        constraint = treasurer.key() == pool.config.treasurer_authority @ PoolError::UnauthorizedTreasurer
+    )]
+    pub treasurer: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.config.sweeps_idle_reserve() @ PoolError::SweepNotConfigured,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        constraint = pool_authority.key() == pool.pool_authority @ PoolError::InvalidAuthority
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Pool's USDC reserve
+    #[account(
+        mut,
+        address = pool.usdc_reserve
+    )]
+    pub pool_usdc_reserve: Box<Account<'info, TokenAccount>>,
+
+    /// Interest-bearing token the wrapper issues
+    #[account(
+        address = pool.config.sweep_wrapped_mint @ PoolError::SweepNotConfigured
+    )]
+    pub wrapped_mint: Box<Account<'info, Mint>>,
+
+    /// Pool's holding of the wrapped token
+    #[account(
+        init_if_needed,
+        payer = treasurer,
+        token::mint = wrapped_mint,
+        token::authority = pool_authority,
+        seeds = [Pool::SWEEP_VAULT_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub sweep_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The wrapper program configured by the admin
+    #[account(
+        executable,
+        address = pool.config.sweep_wrapper_program @ PoolError::SweepNotConfigured
+    )]
+    pub wrapper_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Wrap every unreserved USDC above the liquidity buffer; accounts the
+/// wrapper needs beyond the two token accounts go in remaining accounts
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SweepIdleReserve<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = &ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+
+    // Sweeping adds to the pool's positions, so it stops with deposits
+    ValidationUtils::validate_pool_operation(pool, PauseFlag::Deposits)?;
+
+    let amount = pool.sweepable_usdc(ctx.accounts.pool_usdc_reserve.amount);
+    require!(amount > 0, PoolError::NothingToSweep);
+
+    let usdc_before = ctx.accounts.pool_usdc_reserve.amount;
+    let wrapped_before = ctx.accounts.sweep_vault.amount;
+
+    let mut accounts = vec![
+        ctx.accounts.pool_usdc_reserve.to_account_info(),
+        ctx.accounts.sweep_vault.to_account_info(),
+    ];
+    accounts.extend_from_slice(ctx.remaining_accounts);
+    TokenUtils::invoke_wrapper(
+        &ctx.accounts.wrapper_program.to_account_info(),
+        "wrap",
+        amount,
+        &ctx.accounts.pool_authority.to_account_info(),
+        &accounts,
+        &[&pool.authority_seeds(&pool_id_seed)],
+    )?;
+
+    // Book what actually moved, not what the wrapper was asked to do
+    ctx.accounts.pool_usdc_reserve.reload()?;
+    ctx.accounts.sweep_vault.reload()?;
+    let wrapped_received = ctx
+        .accounts
+        .sweep_vault
+        .amount
+        .saturating_sub(wrapped_before);
+    require!(
+        usdc_before.checked_sub(ctx.accounts.pool_usdc_reserve.amount) == Some(amount)
+            && wrapped_received > 0,
+        PoolError::SweepWrapperMismatch
+    );
+
+    let pool = &mut ctx.accounts.pool;
+    pool.total_usdc_reserves = pool
+        .total_usdc_reserves
+        .checked_sub(amount)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    pool.swept_usdc = pool
+        .swept_usdc
+        .checked_add(amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+
+    emit!(IdleReserveSwept {
+        treasurer: ctx.accounts.treasurer.key(),
+        pool: pool.key(),
+        usdc_amount: amount,
+        wrapped_received,
+        swept_usdc: pool.swept_usdc,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Swept {} USDC into {} wrapped tokens; {} USDC swept in total",
+        amount,
+        wrapped_received,
+        pool.swept_usdc
+    );
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    set_authority, spl_token::instruction::AuthorityType, Mint, SetAuthority, Token,
+};
+
+#[derive(Accounts)]
+pub struct TransferMintAuthority<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Scheduled TransferMintAuthority operation (consumed on execution)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TimelockOperation::SEED_PREFIX,
+            pool.key().as_ref(),
+            timelock_operation.id.to_le_bytes().as_ref()
+        ],
+        bump = timelock_operation.bump
+    )]
+    pub timelock_operation: Account<'info, TimelockOperation>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Successor program, matched against the scheduled action
+    #[account(executable)]
+    pub successor_program: UncheckedAccount<'info>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<TransferMintAuthority>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+    let operation = &ctx.accounts.timelock_operation;
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_timelock_elapsed(operation, clock.unix_timestamp)?;
+
+    let AdminAction::TransferMintAuthority { successor_program } = operation.action.clone() else {
+        return err!(PoolError::TimelockActionMismatch);
+    };
+
+    require!(
+        ctx.accounts.successor_program.key() == successor_program,
+        PoolError::TimelockActionMismatch
+    );
+
+    // The successor signs for the mint with the same pool seeds under its own program id
+    let (new_authority, _) = Pubkey::find_program_address(
+        &[Pool::SEED_PREFIX, pool.usdc_mint.as_ref(), &pool_id_seed],
+        &successor_program,
+    );
+
+    set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.pool_authority.to_account_info(),
+                account_or_mint: ctx.accounts.ipt_mint.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        AuthorityType::MintTokens,
+        Some(new_authority),
+    )?;
+
+    // This pool can no longer mint; holders can still exit through withdrawals
+    pool.pause_flags |= PauseFlag::Deposits.mask();
+
+    emit!(MintAuthorityTransferred {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        operation_id: operation.id,
+        ipt_mint: pool.ipt_mint,
+        successor_program,
+        new_authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "IPT mint authority transferred to {} (program {})",
+        new_authority,
+        successor_program
+    );
+
+    Ok(())
+}
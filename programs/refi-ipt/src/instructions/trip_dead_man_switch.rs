@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TripDeadManSwitch<'info> {
+    /// Anyone may trip the switch once the oracle has gone silent
+    pub caller: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<TripDeadManSwitch>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(pool.pool_state != PoolState::Frozen, PoolError::PoolFrozen);
+    require!(
+        pool.pool_state != PoolState::Migration,
+        PoolError::PoolInMigration
+    );
+    require!(
+        pool.dead_man_switch_due(clock.unix_timestamp),
+        PoolError::DeadManSwitchNotDue
+    );
+
+    // An abandoned deployment stops taking money but keeps paying it out;
+    // only the admin can unfreeze
+    pool.pool_state = PoolState::Frozen;
+
+    emit!(DeadManSwitchTripped {
+        pool: pool.key(),
+        caller: ctx.accounts.caller.key(),
+        last_rate_update: pool.last_rate_update,
+        dead_man_switch_secs: pool.config.dead_man_switch_secs,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Oracle silent since {}, dead-man switch tripped: pool frozen to withdrawals only",
+        pool.last_rate_update
+    );
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UnfreezePool<'info> {
+    pub admin: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = admin.key() == pool.config.admin_authority @ PoolError::UnauthorizedAdmin,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<UnfreezePool>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(pool.pool_state == PoolState::Frozen, PoolError::PoolNotFrozen);
+    // The rate has to be live again first (admin_update_rate_and_state)
+    require!(
+        !pool.dead_man_switch_due(clock.unix_timestamp),
+        PoolError::OracleStale
+    );
+
+    pool.pool_state = PoolState::Active;
+
+    emit!(PoolUnfrozen {
+        admin: ctx.accounts.admin.key(),
+        pool: pool.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Pool unfrozen, deposits may resume");
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::instructions::sweep_idle_reserve::SweepIdleReserve;
+use crate::utils::TokenUtils;
+use anchor_lang::prelude::*;
+
+/// Unwrap `wrapped_amount` of the pool's wrapper tokens back into the USDC
+/// reserve, e.g. to refill the liquidity buffer ahead of withdrawals
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SweepIdleReserve<'info>>,
+    wrapped_amount: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = &ctx.accounts.pool;
+    let pool_id_seed = pool.id_seed();
+
+    require!(wrapped_amount > 0, PoolError::ZeroAmountNotAllowed);
+
+    let usdc_before = ctx.accounts.pool_usdc_reserve.amount;
+    let wrapped_before = ctx.accounts.sweep_vault.amount;
+    let principal = pool.swept_principal(wrapped_amount, wrapped_before)?;
+
+    let mut accounts = vec![
+        ctx.accounts.sweep_vault.to_account_info(),
+        ctx.accounts.pool_usdc_reserve.to_account_info(),
+    ];
+    accounts.extend_from_slice(ctx.remaining_accounts);
+    TokenUtils::invoke_wrapper(
+        &ctx.accounts.wrapper_program.to_account_info(),
+        "unwrap",
+        wrapped_amount,
+        &ctx.accounts.pool_authority.to_account_info(),
+        &accounts,
+        &[&pool.authority_seeds(&pool_id_seed)],
+    )?;
+
+    ctx.accounts.pool_usdc_reserve.reload()?;
+    ctx.accounts.sweep_vault.reload()?;
+    let usdc_received = ctx
+        .accounts
+        .pool_usdc_reserve
+        .amount
+        .saturating_sub(usdc_before);
+    require!(
+        wrapped_before.checked_sub(ctx.accounts.sweep_vault.amount) == Some(wrapped_amount),
+        PoolError::SweepWrapperMismatch
+    );
+
+    let pool = &mut ctx.accounts.pool;
+    pool.total_usdc_reserves = pool
+        .total_usdc_reserves
+        .checked_add(usdc_received)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    pool.swept_usdc = pool
+        .swept_usdc
+        .checked_sub(principal)
+        .ok_or(PoolError::MathematicalUnderflow)?;
+    // A wrapper that lost value returns less than the principal
+    let yield_usdc = usdc_received.saturating_sub(principal);
+
+    emit!(SweptReserveUnwrapped {
+        treasurer: ctx.accounts.treasurer.key(),
+        pool: pool.key(),
+        wrapped_amount,
+        usdc_received,
+        principal,
+        yield_usdc,
+        swept_usdc: pool.swept_usdc,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Unwrapped {} wrapped tokens for {} USDC ({} yield); {} USDC still swept",
+        wrapped_amount,
+        usdc_received,
+        yield_usdc,
+        pool.swept_usdc
+    );
+
+    Ok(())
+}
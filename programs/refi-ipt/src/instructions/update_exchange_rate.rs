@@ -16,36 +16,139 @@ pub struct UpdateExchangeRate<'info> {
         mut,
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump,
-        constraint = oracle.key() == pool.config.oracle_authority @ PoolError::UnauthorizedOracle
+        constraint = pool.is_oracle(&oracle.key(), Clock::get()?.unix_timestamp) @ PoolError::UnauthorizedOracle,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
+
+    /// Today's rate summary; passing it records the update, opening the
+    /// day's summary on its first update
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = NavSummary::LEN,
+        seeds = [
+            NavSummary::SEED_PREFIX,
+            pool.key().as_ref(),
+            NavSummary::period_at(Clock::get()?.unix_timestamp).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub nav_summary: Option<Account<'info, NavSummary>>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<UpdateExchangeRate>, new_rate: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<UpdateExchangeRate>,
+    new_rate: u64,
+    observed_at: i64,
+    accrued_yield: u64,
+    evidence: Option<RateEvidence>,
+) -> Result<()> {
+    let oracle = ctx.accounts.oracle.key();
     let pool = &mut ctx.accounts.pool;
+    let old_rate = pool.current_exchange_rate;
+    apply_exchange_rate(pool, oracle, new_rate, observed_at, accrued_yield)?;
+
+    if let Some(summary) = ctx.accounts.nav_summary.as_mut() {
+        let now = pool.last_rate_update;
+        if summary.updates == 0 {
+            summary.pool = pool.key();
+            summary.period = NavSummary::period_at(now);
+            summary.bump = ctx.bumps.nav_summary;
+        }
+        summary.record(old_rate, new_rate, pool.nav()?, now)?;
+    }
+
+    // Tie the posted rate to the NAV report it was calculated in
+    if let Some(evidence) = evidence {
+        require!(
+            evidence.report_hash != [0; 32] && evidence.uri.len() <= RateEvidence::MAX_URI_LEN,
+            PoolError::InvalidRateEvidence
+        );
+        pool.rate_evidence_hash = evidence.report_hash;
+
+        emit!(RateEvidenceAttached {
+            oracle,
+            pool: pool.key(),
+            rate: new_rate,
+            report_hash: evidence.report_hash,
+            uri: evidence.uri,
+            timestamp: pool.last_rate_update,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate and record a new exchange rate; shared by every oracle path
+pub fn apply_exchange_rate(
+    pool: &mut Account<Pool>,
+    oracle: Pubkey,
+    new_rate: u64,
+    observed_at: i64,
+    accrued_yield: u64,
+) -> Result<()> {
+    ValidationUtils::validate_not_paused(pool, PauseFlag::RateUpdates)?;
+
+    // A misbehaving oracle service cannot churn the rate; admin corrections
+    // go through record_exchange_rate directly and are not throttled
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now.saturating_sub(pool.last_rate_update) >= pool.config.min_rate_update_interval_secs,
+        PoolError::RateUpdateTooFrequent
+    );
+
+    record_exchange_rate(pool, oracle, new_rate, observed_at, accrued_yield)
+}
+
+/// Validate and record a new exchange rate regardless of the rate-update
+/// pause; paths that do not report accrued yield pass 0
+pub fn record_exchange_rate(
+    pool: &mut Account<Pool>,
+    oracle: Pubkey,
+    new_rate: u64,
+    observed_at: i64,
+    accrued_yield: u64,
+) -> Result<()> {
     let clock = Clock::get()?;
 
     // Validate the new exchange rate
     ValidationUtils::validate_exchange_rate(new_rate)?;
+    ValidationUtils::validate_rate_bounds(&pool.config, new_rate)?;
 
     let old_rate = pool.current_exchange_rate;
 
     // Don't allow no-op updates
     require!(new_rate != old_rate, PoolError::InvalidExchangeRate);
 
+    // Delayed retries must not roll the rate back to an older observation
+    require!(
+        observed_at >= pool.last_rate_observed_at && observed_at <= clock.unix_timestamp,
+        PoolError::OutOfOrderRateUpdate
+    );
+
     // Update the exchange rate
     pool.current_exchange_rate = new_rate;
     pool.last_rate_update = clock.unix_timestamp;
+    pool.last_rate_observed_at = observed_at;
+    // Evidence for the previous rate does not carry over
+    pool.rate_evidence_hash = [0; 32];
+    pool.accrued_yield = accrued_yield;
 
     // Emit event
     emit!(ExchangeRateUpdated {
-        oracle: ctx.accounts.oracle.key(),
+        oracle,
         pool: pool.key(),
         old_rate,
         new_rate,
+        observed_at,
+        accrued_yield,
         timestamp: clock.unix_timestamp,
     });
 
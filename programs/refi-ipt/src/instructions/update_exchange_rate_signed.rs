@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::instructions::update_exchange_rate::apply_exchange_rate;
+use crate::states::*;
+use crate::utils::SignatureUtils;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+#[derive(Accounts)]
+pub struct UpdateExchangeRateSigned<'info> {
+    /// Anyone may relay a payload signed by the oracle publisher
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Instructions sysvar, used to inspect the preceding ed25519 instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateExchangeRateSigned>,
+    new_rate: u64,
+    timestamp: i64,
+    nonce: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let publisher = pool.oracle_publisher;
+    require!(
+        publisher != Pubkey::default(),
+        PoolError::OraclePublisherNotSet
+    );
+
+    // Freshness and replay protection; ordering is checked when applying
+    require!(
+        clock.unix_timestamp.saturating_sub(timestamp) <= Pool::MAX_SIGNED_RATE_AGE,
+        PoolError::StaleOraclePayload
+    );
+    require!(
+        nonce > pool.last_oracle_nonce,
+        PoolError::StaleOraclePayload
+    );
+
+    // The ed25519 program must have verified the payload in the instruction
+    // immediately before this one
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current_index = load_current_index_checked(&instructions)?;
+    require!(current_index > 0, PoolError::InvalidOracleSignature);
+    let ed25519_ix = load_instruction_at_checked(current_index as usize - 1, &instructions)?;
+    let message = SignatureUtils::signed_rate_message(&pool.key(), new_rate, timestamp, nonce);
+    SignatureUtils::verify_ed25519_ix(&ed25519_ix, &publisher, &message)?;
+
+    pool.last_oracle_nonce = nonce;
+
+    apply_exchange_rate(pool, publisher, new_rate, timestamp, 0)
+}
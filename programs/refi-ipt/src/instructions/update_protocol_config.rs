@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::ValidationUtils;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    pub protocol_admin: Signer<'info>,
+
+    /// Program-wide config account
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+        constraint = protocol_admin.key() == protocol_config.protocol_admin @ PoolError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateProtocolConfig>,
+    new_protocol_admin: Pubkey,
+    max_deposit_fee_bps: u16,
+    max_withdrawal_fee_bps: u16,
+    max_management_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        new_protocol_admin != Pubkey::default(),
+        PoolError::InvalidAuthority
+    );
+    ValidationUtils::validate_protocol_fee_bounds(
+        max_deposit_fee_bps,
+        max_withdrawal_fee_bps,
+        max_management_fee_bps,
+    )?;
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let clock = Clock::get()?;
+
+    if new_protocol_admin != protocol_config.protocol_admin {
+        msg!(
+            "CRITICAL: Protocol admin changed from {} to {}",
+            protocol_config.protocol_admin,
+            new_protocol_admin
+        );
+    }
+
+    protocol_config.protocol_admin = new_protocol_admin;
+    protocol_config.max_deposit_fee_bps = max_deposit_fee_bps;
+    protocol_config.max_withdrawal_fee_bps = max_withdrawal_fee_bps;
+    protocol_config.max_management_fee_bps = max_management_fee_bps;
+
+    emit!(ProtocolConfigUpdated {
+        protocol_admin: ctx.accounts.protocol_admin.key(),
+        new_protocol_admin,
+        max_deposit_fee_bps,
+        max_withdrawal_fee_bps,
+        max_management_fee_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Protocol config updated successfully");
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::{CalculationUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateReserveAssetPrice<'info> {
+    pub oracle: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.is_oracle(&oracle.key(), Clock::get()?.unix_timestamp) @ PoolError::UnauthorizedOracle,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [
+            ReserveAsset::SEED_PREFIX,
+            pool.key().as_ref(),
+            reserve_asset.mint.as_ref()
+        ],
+        bump = reserve_asset.bump
+    )]
+    pub reserve_asset: Account<'info, ReserveAsset>,
+}
+
+pub fn handler(ctx: Context<UpdateReserveAssetPrice>, new_price: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = &mut ctx.accounts.pool;
+    ValidationUtils::validate_not_paused(pool, PauseFlag::RateUpdates)?;
+    require!(new_price > 0, PoolError::InvalidExchangeRate);
+
+    // Revalue the holding so the pool aggregate follows the new price
+    let asset = &mut ctx.accounts.reserve_asset;
+    let old_price = asset.price;
+    let new_value = CalculationUtils::asset_value(
+        asset.balance,
+        new_price,
+        pool.usdc_decimals,
+        asset.decimals,
+    )?;
+    pool.revalue_reserve_asset(asset.value, new_value)?;
+
+    asset.price = new_price;
+    asset.price_updated_at = clock.unix_timestamp;
+    asset.value = new_value;
+
+    emit!(ReserveAssetPriceUpdated {
+        oracle: ctx.accounts.oracle.key(),
+        pool: pool.key(),
+        mint: asset.mint,
+        old_price,
+        new_price,
+        asset_value: new_value,
+        asset_reserves_value: pool.asset_reserves_value,
+        timestamp: clock.unix_timestamp,
+    });
+
+    ValidationUtils::check_reserve_drift(pool, asset, clock.unix_timestamp)?;
+
+    msg!(
+        "Reserve asset {} priced at {}, worth {} USDC",
+        asset.mint,
+        new_price,
+        new_value
+    );
+
+    Ok(())
+}
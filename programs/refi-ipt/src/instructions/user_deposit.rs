@@ -17,17 +17,27 @@ pub struct UserDeposit<'info> {
         mut,
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
 
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: Pool authority (PDA)
     #[account(
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump
     )]
@@ -63,51 +73,189 @@ pub struct UserDeposit<'info> {
     )]
     pub ipt_mint: Account<'info, Mint>,
 
+    /// Depositor's position; passing it assigns a depositor ordinal on the
+    /// first deposit and applies any early-supporter fee tier
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Position::LEN,
+        seeds = [Position::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    /// Referrer's record; passing it accrues a share of the deposit fee to
+    /// the referrer
+    #[account(
+        mut,
+        seeds = [Referral::SEED_PREFIX, pool.key().as_ref(), referral.referrer.as_ref()],
+        bump = referral.bump,
+        constraint = referral.referrer != user.key() @ PoolError::SelfReferral
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    /// Distribution partner the deposit is attributed to, if any
+    #[account(
+        mut,
+        seeds = [
+            PartnerAccount::SEED_PREFIX,
+            pool.key().as_ref(),
+            partner_account.partner_id.to_le_bytes().as_ref()
+        ],
+        bump = partner_account.bump
+    )]
+    pub partner_account: Option<Account<'info, PartnerAccount>>,
+
+    /// CHECK: Depositor's access list entry PDA, which need not exist;
+    /// required while the pool has an allowlist, blocked addresses or
+    /// investor tiers
+    #[account(
+        seeds = [AccessListEntry::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_entry: Option<UncheckedAccount<'info>>,
+
+    /// Depositor's cost-basis ledger; passing it records this deposit as a lot
+    #[account(
+        mut,
+        seeds = [CostBasisLedger::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = cost_basis.bump
+    )]
+    pub cost_basis: Option<Account<'info, CostBasisLedger>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<UserDeposit>, net_usdc_amount: u64, min_ipt_amount: u64) -> Result<()> {
+    deposit(ctx, net_usdc_amount, min_ipt_amount).map(|_| ())
+}
+
+/// Execute a deposit and return the USDC the user paid; shared with the
+/// vault adapter instructions
+pub fn deposit(ctx: Context<UserDeposit>, net_usdc_amount: u64, min_ipt_amount: u64) -> Result<u64> {
     let pool = &mut ctx.accounts.pool; 
+    let pool_id_seed = pool.id_seed();
     let clock = Clock::get()?;
 
-    // Validate pool state
-    ValidationUtils::validate_pool_state_for_operation(&pool.pool_state, true)?;
+    let user = ctx.accounts.user.key();
+    let pool_key = pool.key();
+    let reject = |reason, error| {
+        ValidationUtils::reject(
+            pool_key,
+            user,
+            RejectedOperation::Deposit,
+            reason,
+            net_usdc_amount,
+            clock.unix_timestamp,
+            error,
+        )
+    };
 
-    // Validate deposit amount is greater than 0
+    // Validate protocol and pool state
+    ValidationUtils::validate_protocol_not_paused(&ctx.accounts.protocol_config)
+        .map_err(|e| reject(RejectionReason::ProtocolPaused, e))?;
+    ValidationUtils::validate_pool_operation(pool, PauseFlag::Deposits)
+        .map_err(|e| reject(RejectionReason::OperationDisabled, e))?;
+    ValidationUtils::validate_oracle_fresh(pool, clock.unix_timestamp)
+        .map_err(|e| reject(RejectionReason::OracleStale, e))?;
+
+    ValidationUtils::validate_access(pool, ctx.accounts.access_entry.as_deref())?;
+    let tier = ValidationUtils::investor_tier(pool, ctx.accounts.access_entry.as_deref())?;
+
+    // Validate deposit amount is greater than 0 and within the tier's cap
     require!(net_usdc_amount > 0, PoolError::InvalidAmount);
+    // Slot-stable quotes price the whole slot at its first-read rate
+    let exchange_rate = pool.pin_quote_rate(clock.slot);
+    ValidationUtils::validate_tier_deposit(
+        &pool.config,
+        tier,
+        ctx.accounts.position.as_deref(),
+        net_usdc_amount,
+    )?;
+
+    // Number new positions in deposit order
+    let mut depositor_ordinal = 0;
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        let pool_key = pool.key();
+        position.open(
+            pool,
+            pool_key,
+            ctx.accounts.user.key(),
+            clock.unix_timestamp,
+            ctx.bumps.position,
+        )?;
+        position.touch(clock.unix_timestamp);
+        depositor_ordinal = position.depositor_ordinal;
+    }
 
     // Calculate IPT amount, deposit fee, and gross USDC amount from net amount
     let (ipt_amount, deposit_fee, gross_usdc_amount) =
         CalculationUtils::calculate_ipt_from_net_usdc_deposit(
             net_usdc_amount,
-            pool.current_exchange_rate,
-            pool.config.deposit_fee_bps,
+            exchange_rate,
+            pool.config.tier_deposit_fee_bps(tier, depositor_ordinal),
+            pool.usdc_decimals,
+            pool.ipt_decimals,
         )?;
 
-    // Check user has sufficient USDC balance for gross amount
+    // Check slippage protection
+    require!(ipt_amount >= min_ipt_amount, PoolError::SlippageExceeded);
+
+    // A running promotion waives the fee and/or adds bonus IPT, both paid
+    // from the promotion budget already held in the reserve
+    let (fee_waived, bonus_usdc) =
+        pool.promotion_for_deposit(net_usdc_amount, deposit_fee, clock.unix_timestamp)?;
+    let (bonus_ipt, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+        bonus_usdc,
+        exchange_rate,
+        0,
+        pool.usdc_decimals,
+        pool.ipt_decimals,
+    )?;
+    let paid_usdc_amount = gross_usdc_amount - fee_waived;
+    let minted_ipt_amount = ipt_amount
+        .checked_add(bonus_ipt)
+        .ok_or(PoolError::MathematicalOverflow)?;
+
+    // Check user has sufficient USDC balance for the amount they pay
     require!(
-        ctx.accounts.user_usdc_account.amount >= gross_usdc_amount,
+        ctx.accounts.user_usdc_account.amount >= paid_usdc_amount,
         PoolError::InsufficientAccountBalance
     );
 
-    // Check slippage protection
-    require!(ipt_amount >= min_ipt_amount, PoolError::SlippageExceeded);
-
     // Check max total supply limit (if set)
     if pool.max_total_supply > 0 {
         let new_total_supply = pool
             .total_ipt_supply
-            .checked_add(ipt_amount)
+            .checked_add(minted_ipt_amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+
+        if new_total_supply > pool.max_total_supply {
+            return Err(reject(
+                RejectionReason::SupplyCapReached,
+                PoolError::MaxTotalSupplyExceeded.into(),
+            ));
+        }
+    }
+
+    // Check the USDC-denominated TVL cap (if set)
+    if pool.tvl_cap > 0 {
+        let new_nav = pool
+            .nav()?
+            .checked_add(net_usdc_amount)
+            .and_then(|nav| nav.checked_add(bonus_usdc))
             .ok_or(PoolError::MathematicalOverflow)?;
 
-        require!(
-            new_total_supply <= pool.max_total_supply,
-            PoolError::MaxTotalSupplyExceeded
-        );
+        if new_nav > pool.tvl_cap {
+            return Err(reject(
+                RejectionReason::TvlCapReached,
+                PoolError::TvlCapExceeded.into(),
+            ));
+        }
     }
 
-    // Transfer gross USDC amount (including fees) from user to pool reserve
+    // Transfer gross USDC amount (including any unwaived fee) from user to pool reserve
     transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -117,7 +265,7 @@ pub fn handler(ctx: Context<UserDeposit>, net_usdc_amount: u64, min_ipt_amount:
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
-        gross_usdc_amount,
+        paid_usdc_amount,
     )?;
 
     // Mint IPT to user
@@ -129,47 +277,166 @@ pub fn handler(ctx: Context<UserDeposit>, net_usdc_amount: u64, min_ipt_amount:
                 to: ctx.accounts.user_ipt_account.to_account_info(),
                 authority: ctx.accounts.pool_authority.to_account_info(),
             },
-            &[&pool.authority_seeds()],
+            &[&pool.authority_seeds(&pool_id_seed)],
         ),
-        ipt_amount,
+        minted_ipt_amount,
     )?;
 
     // Update pool state
     pool.total_ipt_supply = pool
         .total_ipt_supply
-        .checked_add(ipt_amount)
+        .checked_add(minted_ipt_amount)
         .ok_or(PoolError::MathematicalOverflow)?;
 
     pool.total_usdc_reserves = pool
         .total_usdc_reserves
-        .checked_add(gross_usdc_amount)
+        .checked_add(paid_usdc_amount)
         .ok_or(PoolError::MathematicalOverflow)?;
 
-    pool.total_accumulated_fees = pool
-        .total_accumulated_fees
-        .checked_add(deposit_fee)
-        .ok_or(PoolError::MathematicalOverflow)?;
+    // The referrer's share of the fee is set aside for them in the reserve
+    let mut protocol_fee = deposit_fee;
+    let mut referrer = Pubkey::default();
+    let mut referral_fee = 0;
+    if let Some(referral) = ctx.accounts.referral.as_mut() {
+        ValidationUtils::validate_feature_enabled(
+            &ctx.accounts.protocol_config,
+            Feature::ReferralRewards,
+        )?;
+        let share =
+            CalculationUtils::calculate_fee(deposit_fee, pool.config.referral_fee_share_bps)?;
+        protocol_fee -= share;
+        referrer = referral.referrer;
+        referral_fee = share;
+        referral.referred_deposits = referral
+            .referred_deposits
+            .checked_add(1)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        referral.total_earned = referral
+            .total_earned
+            .checked_add(share)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        referral.claimable = referral
+            .claimable
+            .checked_add(share)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        pool.referral_payable = pool
+            .referral_payable
+            .checked_add(share)
+            .ok_or(PoolError::MathematicalOverflow)?;
+
+        emit!(ReferralAccrued {
+            referrer: referral.referrer,
+            user: ctx.accounts.user.key(),
+            pool: pool.key(),
+            deposit_fee,
+            amount: share,
+            claimable: referral.claimable,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    pool.credit_fees(FeeBucket::Deposit, protocol_fee)?;
+
+    let certificate_number = pool.issue_certificate()?;
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        position.record_entry(minted_ipt_amount, exchange_rate, net_usdc_amount)?;
+        position.last_certificate_number = certificate_number;
+        position.last_deposit_at = clock.unix_timestamp;
+        position.last_deposit_ipt = minted_ipt_amount;
+        position.last_deposit_usdc = paid_usdc_amount;
+        position.last_deposit_fee = protocol_fee;
+        position.last_deposit_net_usdc = net_usdc_amount;
+        position.last_deposit_referrer = referrer;
+        position.last_deposit_referral_fee = referral_fee;
+        position.last_deposit_partner = ctx
+            .accounts
+            .partner_account
+            .as_ref()
+            .map_or(Pubkey::default(), |partner| partner.key());
+        position.last_deposit_promo_usdc = fee_waived + bonus_usdc;
+        position.last_deposit_lot = ctx.accounts.cost_basis.is_some();
+    }
+
+    if let Some(ledger) = ctx.accounts.cost_basis.as_mut() {
+        ledger.record_lot(CostBasisLot {
+            ipt_amount: minted_ipt_amount,
+            cost_usdc: paid_usdc_amount,
+            exchange_rate,
+            acquired_at: clock.unix_timestamp,
+        })?;
+    }
+
+    if let Some(partner) = ctx.accounts.partner_account.as_mut() {
+        partner.deposit_count = partner
+            .deposit_count
+            .checked_add(1)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        partner.aum_contributed = partner
+            .aum_contributed
+            .checked_add(net_usdc_amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        partner.fees_generated = partner
+            .fees_generated
+            .checked_add(deposit_fee - fee_waived)
+            .ok_or(PoolError::MathematicalOverflow)?;
+
+        emit!(PartnerDepositAttributed {
+            partner_id: partner.partner_id,
+            user: ctx.accounts.user.key(),
+            pool: pool.key(),
+            usdc_amount: net_usdc_amount,
+            deposit_fee: deposit_fee - fee_waived,
+            aum_contributed: partner.aum_contributed,
+            fees_generated: partner.fees_generated,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if fee_waived > 0 || bonus_usdc > 0 {
+        pool.spend_promo_budget(fee_waived + bonus_usdc)?;
+        emit!(PromotionApplied {
+            user: ctx.accounts.user.key(),
+            pool: pool.key(),
+            fee_waived,
+            bonus_ipt,
+            bonus_usdc,
+            remaining_budget: pool.promo_budget,
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
     // Emit event
     emit!(UserDepositExecuted {
         user: ctx.accounts.user.key(),
         pool: pool.key(),
-        usdc_amount: gross_usdc_amount,
-        ipt_amount,
+        usdc_amount: paid_usdc_amount,
+        ipt_amount: minted_ipt_amount,
         deposit_fee,
-        exchange_rate: pool.current_exchange_rate,
+        exchange_rate,
         new_ipt_supply: pool.total_ipt_supply,
         new_reserves: pool.total_usdc_reserves,
+        certificate_number,
         timestamp: clock.unix_timestamp,
     });
 
+    // Switch to withdraw-only once the cap is filled exactly
+    if pool.close_subscriptions_if_full() {
+        emit!(SubscriptionsClosed {
+            pool: pool.key(),
+            total_ipt_supply: pool.total_ipt_supply,
+            max_total_supply: pool.max_total_supply,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Supply cap {} reached, subscriptions closed", pool.max_total_supply);
+    }
+
     msg!(
         "User deposited {} USDC (fee: {}), received {} IPT. Pool reserves: {}",
-        gross_usdc_amount,
-        deposit_fee,
-        ipt_amount,
+        paid_usdc_amount,
+        deposit_fee - fee_waived,
+        minted_ipt_amount,
         pool.total_usdc_reserves
     );
 
-    Ok(())
+    Ok(paid_usdc_amount)
 }
@@ -17,17 +17,27 @@ pub struct UserWithdraw<'info> {
         mut,
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
 
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: Pool authority (PDA)
     #[account(
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump
     )]
@@ -64,6 +74,37 @@ pub struct UserWithdraw<'info> {
     pub ipt_mint: Account<'info, Mint>,
 
     pub token_program: Program<'info, Token>,
+
+    /// CHECK: Instructions sysvar, used to enforce isolated exits
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// User's position; passing it reports their average entry rate and
+    /// implied P&L on a direct withdrawal, and it is required to queue a
+    /// request while the pool limits queue entries per day
+    #[account(
+        mut,
+        seeds = [Position::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    /// User's cost-basis ledger; passing it consumes lots for a direct
+    /// withdrawal and reports their realized gain
+    #[account(
+        mut,
+        seeds = [CostBasisLedger::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = cost_basis.bump
+    )]
+    pub cost_basis: Option<Account<'info, CostBasisLedger>>,
+
+    /// CHECK: User's access list entry PDA, which need not exist; required
+    /// while the pool has investor tiers
+    #[account(
+        seeds = [AccessListEntry::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_entry: Option<UncheckedAccount<'info>>,
 }
 
 // #[access_control(not_locked(&ctx.accounts))]
@@ -72,15 +113,50 @@ pub fn handler(
     net_ipt_amount: u64,
     min_usdc_amount: u64,
 ) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
+    // Large exits must go through commit_withdrawal / reveal_withdrawal
+    require!(
+        !ctx.accounts.pool.config.requires_commitment(net_ipt_amount),
+        PoolError::WithdrawalCommitmentRequired
+    );
+
+    withdraw(ctx.accounts, net_ipt_amount, min_usdc_amount)
+}
+
+/// Pay the withdrawal from reserves or queue it; shared with reveal_withdrawal
+pub fn withdraw(
+    accounts: &mut UserWithdraw,
+    net_ipt_amount: u64,
+    min_usdc_amount: u64,
+) -> Result<()> {
+    let pool = &mut accounts.pool;
+    let pool_id_seed = pool.id_seed();
     let clock = Clock::get()?;
-    let user = ctx.accounts.user.key();
-    ValidationUtils::validate_pool_state_for_operation(&pool.pool_state, false)?;
+    let user = accounts.user.key();
+    let pool_key = pool.key();
+    let reject = |reason, error| {
+        ValidationUtils::reject(
+            pool_key,
+            user,
+            RejectedOperation::Withdrawal,
+            reason,
+            net_ipt_amount,
+            clock.unix_timestamp,
+            error,
+        )
+    };
+    ValidationUtils::validate_protocol_not_paused(&accounts.protocol_config)
+        .map_err(|e| reject(RejectionReason::ProtocolPaused, e))?;
+    ValidationUtils::validate_pool_operation(pool, PauseFlag::Withdrawals)
+        .map_err(|e| reject(RejectionReason::OperationDisabled, e))?;
+    ValidationUtils::validate_isolated_exit(pool, &accounts.instructions)?;
 
     require!(net_ipt_amount > 0, PoolError::InvalidAmount);
+    // Slot-stable quotes price the whole slot at its first-read rate
+    let exchange_rate = pool.pin_quote_rate(clock.slot);
+    let tier = ValidationUtils::investor_tier(pool, accounts.access_entry.as_deref())?;
 
     // Sync state with actual balance BEFORE processing
-    let actual_reserve_balance = ctx.accounts.pool_usdc_reserve.amount;
+    let actual_reserve_balance = accounts.pool_usdc_reserve.amount;
     if pool.total_usdc_reserves != actual_reserve_balance {
         msg!(
             "SYNC: pool.total_usdc_reserves {} -> {} (actual balance)",
@@ -92,17 +168,27 @@ pub fn handler(
 
     // Check user has sufficient IPT balance
     require!(
-        ctx.accounts.user_ipt_account.amount >= net_ipt_amount,
+        accounts.user_ipt_account.amount >= net_ipt_amount,
         PoolError::InsufficientAccountBalance
     );
     // Calculate net USDC amount and withdrawal fee from net IPT amount
     let (net_usdc_amount, withdrawal_fee) =
         CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
             net_ipt_amount,
-            pool.current_exchange_rate,
-            pool.config.withdrawal_fee_bps,
+            exchange_rate,
+            pool.config.withdrawal_fee_bps_for(tier),
+            pool.usdc_decimals,
+            pool.ipt_decimals,
         )?;
 
+    // A deep queue makes new exits pay an adjustment that stays with holders
+    let exit_adjustment_bps = pool.exit_adjustment_bps();
+    let (net_usdc_amount, exit_adjustment) = CalculationUtils::apply_exit_adjustment(
+        net_usdc_amount,
+        withdrawal_fee,
+        exit_adjustment_bps,
+    )?;
+
     // Check slippage protection (user expects at least min_usdc_amount USDC)
     require!(
         net_usdc_amount >= min_usdc_amount,
@@ -112,15 +198,44 @@ pub fn handler(
     let gross_usdc_amount = net_usdc_amount
         .checked_add(withdrawal_fee)
         .ok_or(PoolError::MathematicalOverflow)?;
-    if ctx.accounts.pool_usdc_reserve.amount >= gross_usdc_amount {
+
+    // Past the epoch's redemption gate, exits wait in the queue
+    pool.roll_redemption_epoch()?;
+    let within_gate = pool.redemption_fits(gross_usdc_amount);
+    if !within_gate {
+        msg!(
+            "Redemption gate reached ({} of {} this epoch), queueing withdrawal",
+            pool.epoch_redeemed_usdc,
+            pool.epoch_redemption_limit
+        );
+    }
+
+    // A review window means every exit waits in the queue, as do exits
+    // that need compliance sign-off
+    let review_window = pool.config.min_execution_delay_secs;
+    let needs_approval = pool.config.requires_approval(net_ipt_amount);
+
+    // Nothing queued once the waterfall has passed the queue would be paid,
+    // so a winding-down pool pays holders directly or not at all
+    let winding_down = pool.pool_state == PoolState::WindDown;
+
+    // Liquidity earmarked for the queue is not available to instant exits
+    let available = pool.unreserved_liquidity(accounts.pool_usdc_reserve.amount);
+
+    if (winding_down || (within_gate && review_window == 0 && !needs_approval))
+        && available >= gross_usdc_amount
+    {
+        let ipt_balance_before = accounts.user_ipt_account.amount;
+        let usdc_balance_before = accounts.user_usdc_account.amount;
+
         // Burn net IPT amount from user
         burn(
             CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+                accounts.token_program.to_account_info(),
                 Burn {
-                    mint: ctx.accounts.ipt_mint.to_account_info(),
-                    from: ctx.accounts.user_ipt_account.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
+                    mint: accounts.ipt_mint.to_account_info(),
+                    from: accounts.user_ipt_account.to_account_info(),
+                    authority: accounts.user.to_account_info(),
                 },
             ),
             net_ipt_amount,
@@ -129,16 +244,18 @@ pub fn handler(
         // Transfer net USDC amount (after fees) from pool reserve to user
         transfer(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.pool_usdc_reserve.to_account_info(),
-                    to: ctx.accounts.user_usdc_account.to_account_info(),
-                    authority: ctx.accounts.pool_authority.to_account_info(),
+                    from: accounts.pool_usdc_reserve.to_account_info(),
+                    to: accounts.user_usdc_account.to_account_info(),
+                    authority: accounts.pool_authority.to_account_info(),
                 },
-                &[&pool.authority_seeds()],
+                &[&pool.authority_seeds(&pool_id_seed)],
             ),
             net_usdc_amount,
         )?;
+        accounts.user_ipt_account.reload()?;
+        accounts.user_usdc_account.reload()?;
     
         // Update pool state
         pool.total_ipt_supply = pool
@@ -151,21 +268,47 @@ pub fn handler(
             .checked_sub(gross_usdc_amount)
             .ok_or(PoolError::MathematicalUnderflow)?;
 
-        pool.total_accumulated_fees = pool
-            .total_accumulated_fees
-            .checked_add(withdrawal_fee)
-            .ok_or(PoolError::MathematicalOverflow)?;
+        pool.credit_fees(FeeBucket::Withdrawal, withdrawal_fee)?;
+        pool.record_redemption(gross_usdc_amount)?;
+
+        let (avg_entry_rate, realized_pnl) = match accounts.position.as_mut() {
+            Some(position) => {
+                let avg_entry_rate = position.avg_entry_rate;
+                position.record_exit(net_ipt_amount);
+                let realized_pnl = CalculationUtils::implied_pnl(
+                    net_ipt_amount,
+                    net_usdc_amount,
+                    avg_entry_rate,
+                    pool.usdc_decimals,
+                    pool.ipt_decimals,
+                )?;
+                (avg_entry_rate, realized_pnl)
+            }
+            None => (0, 0),
+        };
+        let realized_lots = match accounts.cost_basis.as_mut() {
+            Some(ledger) => ledger.dispose(net_ipt_amount, net_usdc_amount)?,
+            None => Vec::new(),
+        };
 
         // Emit event
         emit!(UserWithdrawalExecuted {
-            user: ctx.accounts.user.key(),
+            user: accounts.user.key(),
             pool: pool.key(),
             ipt_amount: net_ipt_amount,
             usdc_amount: net_usdc_amount,
             withdrawal_fee,
-            exchange_rate: pool.current_exchange_rate,
+            exit_adjustment,
+            exchange_rate,
             new_ipt_supply: pool.total_ipt_supply,
             new_reserves: pool.total_usdc_reserves,
+            ipt_balance_before,
+            ipt_balance_after: accounts.user_ipt_account.amount,
+            usdc_balance_before,
+            usdc_balance_after: accounts.user_usdc_account.amount,
+            avg_entry_rate,
+            realized_pnl,
+            realized_lots,
             timestamp: clock.unix_timestamp,
         });
 
@@ -179,39 +322,58 @@ pub fn handler(
     
         Ok(())
     } else {
+        require!(!winding_down, PoolError::InsufficientReserves);
         approve(
             CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+                accounts.token_program.to_account_info(),
                 Approve {
-                    to: ctx.accounts.user_ipt_account.to_account_info(),
-                    delegate: ctx.accounts.pool_authority.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
+                    to: accounts.user_ipt_account.to_account_info(),
+                    delegate: accounts.pool_authority.to_account_info(),
+                    authority: accounts.user.to_account_info(),
                 },
             ),
             net_ipt_amount,  // Only approve exactly this amount
         )?;
         // Check queue size limit
-        require!(
-            pool.pending_queue.len() < pool.config.max_queue_size as usize,
-            PoolError::QueueFull
-        );
+        if pool.queued().len() >= pool.config.max_queue_size as usize {
+            return Err(reject(RejectionReason::QueueFull, PoolError::QueueFull.into()));
+        }
 
         // Check user is not already in queue
         require!(
-            !pool.pending_queue.iter().any(|w| w.user == user),
+            !pool.queued().iter().any(|w| w.user == user),
             PoolError::AlreadyInQueue
         );
 
-        let position = pool.pending_queue.len() as u32;
-        pool.pending_queue.push(PendingWithdraw {
+        // Count the request against the user's daily queue entry limit
+        let daily_limit = pool.config.max_queue_entries_per_day;
+        match accounts.position.as_mut() {
+            Some(position) => position.record_queue_entry(clock.unix_timestamp, daily_limit)?,
+            None => require!(daily_limit == 0, PoolError::PositionRequired),
+        }
+
+        let earliest_execution = clock
+            .unix_timestamp
+            .checked_add(review_window)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        let position = pool.enqueue_withdrawal(PendingWithdraw {
             user,
             amount: net_ipt_amount,
             min_usdc_amount,
-        });
+            exit_adjustment_bps,
+            requested_at: clock.unix_timestamp,
+            earliest_execution,
+            approved: !needs_approval,
+            tier,
+            skip_count: 0,
+            last_skip_reason: None,
+        }) as u32;
+        ValidationUtils::check_queue_depth(pool, clock.unix_timestamp)?;
         emit!(AddedToQueue {
             user,
             amount: net_ipt_amount,
             position,
+            earliest_execution,
         });
 
         Ok(())
@@ -16,17 +16,27 @@ pub struct UserWithdrawalRequest<'info> {
     #[account(
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
     )]
     pub pool: Account<'info, Pool>,
 
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: Pool authority (PDA)
     #[account(
         seeds = [
             Pool::SEED_PREFIX,
-            pool.usdc_mint.as_ref()
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
         ],
         bump = pool.bump
     )]
@@ -44,6 +54,14 @@ pub struct UserWithdrawalRequest<'info> {
         address = pool.ipt_mint
     )]
     pub ipt_mint: Account<'info, Mint>,
+
+    /// CHECK: User's access list entry PDA, which need not exist; required
+    /// while the pool has investor tiers
+    #[account(
+        seeds = [AccessListEntry::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_entry: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(
@@ -54,11 +72,14 @@ pub fn handler(
     let pool = &ctx.accounts.pool;
     let clock = Clock::get()?;
 
-    // Validate pool state
-    ValidationUtils::validate_pool_state_for_operation(&pool.pool_state, false)?;
+    // Validate protocol and pool state
+    ValidationUtils::validate_protocol_not_paused(&ctx.accounts.protocol_config)?;
+    ValidationUtils::validate_pool_operation(pool, PauseFlag::Withdrawals)?;
 
     // Validate net IPT amount is greater than 0
     require!(net_ipt_amount > 0, PoolError::InvalidAmount);
+    let exchange_rate = pool.quote_rate_at(clock.slot);
+    let tier = ValidationUtils::investor_tier(pool, ctx.accounts.access_entry.as_deref())?;
 
     // Check user has sufficient IPT balance
     require!(
@@ -83,9 +104,16 @@ pub fn handler(
     let (expected_net_usdc_amount, withdrawal_fee) =
         CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
             net_ipt_amount,
-            pool.current_exchange_rate,
-            pool.config.withdrawal_fee_bps,
+            exchange_rate,
+            pool.config.withdrawal_fee_bps_for(tier),
+            pool.usdc_decimals,
+            pool.ipt_decimals,
         )?;
+    let (expected_net_usdc_amount, _) = CalculationUtils::apply_exit_adjustment(
+        expected_net_usdc_amount,
+        withdrawal_fee,
+        pool.exit_adjustment_bps(),
+    )?;
 
     // Check slippage protection
     require!(
@@ -101,7 +129,7 @@ pub fn handler(
         expected_usdc_amount: expected_net_usdc_amount,
         expected_withdrawal_fee: withdrawal_fee,
         min_usdc_amount,
-        exchange_rate: pool.current_exchange_rate,
+        exchange_rate,
         timestamp: clock.unix_timestamp,
     });
 
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::instructions::user_deposit::{self, UserDeposit};
+use anchor_lang::prelude::*;
+
+/// Vault-standard deposit: `assets` USDC go to work in the pool, with any
+/// deposit fee paid on top, for at least `min_shares` IPT
+pub fn handler(ctx: Context<UserDeposit>, assets: u64, min_shares: u64) -> Result<()> {
+    user_deposit::handler(ctx, assets, min_shares)
+}
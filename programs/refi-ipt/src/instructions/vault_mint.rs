@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::instructions::user_deposit::{deposit, UserDeposit};
+use crate::utils::CalculationUtils;
+use anchor_lang::prelude::*;
+
+/// Vault-standard mint: at least `shares` IPT for no more than
+/// `max_assets` USDC paid, fee included
+pub fn handler(ctx: Context<UserDeposit>, shares: u64, max_assets: u64) -> Result<()> {
+    let assets = {
+        let pool = &mut ctx.accounts.pool;
+        let exchange_rate = pool.pin_quote_rate(Clock::get()?.slot);
+        CalculationUtils::net_usdc_for_ipt(
+            shares,
+            exchange_rate,
+            pool.usdc_decimals,
+            pool.ipt_decimals,
+        )?
+    };
+
+    let paid = deposit(ctx, assets, shares)?;
+    require!(paid <= max_assets, PoolError::SlippageExceeded);
+
+    Ok(())
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::instructions::user_withdraw::{self, UserWithdraw};
+use anchor_lang::prelude::*;
+
+/// Vault-standard redeem: burn `shares` IPT for at least `min_assets` USDC
+/// after fees; like user_withdraw, the exit may be queued
+pub fn handler(ctx: Context<UserWithdraw>, shares: u64, min_assets: u64) -> Result<()> {
+    user_withdraw::handler(ctx, shares, min_assets)
+}
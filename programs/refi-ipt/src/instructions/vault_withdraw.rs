@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::instructions::user_withdraw::{self, UserWithdraw};
+use crate::utils::{CalculationUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+
+/// Vault-standard withdraw: at least `assets` USDC after fees for no more
+/// than `max_shares` IPT; like user_withdraw, the exit may be queued
+pub fn handler(ctx: Context<UserWithdraw>, assets: u64, max_shares: u64) -> Result<()> {
+    let shares = {
+        let tier =
+            ValidationUtils::investor_tier(&ctx.accounts.pool, ctx.accounts.access_entry.as_deref())?;
+        let pool = &mut ctx.accounts.pool;
+        let exchange_rate = pool.pin_quote_rate(Clock::get()?.slot);
+        let deductions_bps = pool
+            .config
+            .withdrawal_fee_bps_for(tier)
+            .saturating_add(pool.exit_adjustment_bps());
+        CalculationUtils::ipt_for_net_usdc(
+            assets,
+            exchange_rate,
+            deductions_bps,
+            pool.usdc_decimals,
+            pool.ipt_decimals,
+        )?
+    };
+    require!(shares <= max_shares, PoolError::SlippageExceeded);
+
+    user_withdraw::handler(ctx, shares, assets)
+}
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use crate::events::*;
+use crate::states::*;
+use crate::utils::{CalculationUtils, ValidationUtils};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawReserveAsset<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pool state account
+    #[account(
+        mut,
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump,
+        constraint = pool.layout_version == Pool::LAYOUT_VERSION @ PoolError::PoolLayoutOutdated
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Program-wide config (emergency pause)
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Pool authority (PDA)
+    #[account(
+        seeds = [
+            Pool::SEED_PREFIX,
+            pool.usdc_mint.as_ref(),
+            pool.id_seed().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The asset paid out
+    #[account(
+        mut,
+        seeds = [
+            ReserveAsset::SEED_PREFIX,
+            pool.key().as_ref(),
+            reserve_asset.mint.as_ref()
+        ],
+        bump = reserve_asset.bump
+    )]
+    pub reserve_asset: Account<'info, ReserveAsset>,
+
+    /// User's token account for the asset
+    #[account(
+        mut,
+        token::mint = reserve_asset.mint,
+        token::authority = user
+    )]
+    pub user_asset_account: Account<'info, TokenAccount>,
+
+    /// User's IPT token account
+    #[account(
+        mut,
+        token::mint = pool.ipt_mint,
+        token::authority = user
+    )]
+    pub user_ipt_account: Account<'info, TokenAccount>,
+
+    /// Pool's reserve account for the asset
+    #[account(
+        mut,
+        address = reserve_asset.vault
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+
+    /// IPT mint
+    #[account(
+        mut,
+        address = pool.ipt_mint
+    )]
+    pub ipt_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Instructions sysvar, used to enforce isolated exits
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: User's access list entry PDA, which need not exist; required
+    /// while the pool has investor tiers
+    #[account(
+        seeds = [AccessListEntry::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_entry: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(
+    ctx: Context<WithdrawReserveAsset>,
+    net_ipt_amount: u64,
+    min_asset_amount: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let asset = &mut ctx.accounts.reserve_asset;
+    let pool_id_seed = pool.id_seed();
+    let clock = Clock::get()?;
+
+    ValidationUtils::validate_protocol_not_paused(&ctx.accounts.protocol_config)?;
+    ValidationUtils::validate_feature_enabled(
+        &ctx.accounts.protocol_config,
+        Feature::InKindRedemptions,
+    )?;
+    ValidationUtils::validate_pool_operation(pool, PauseFlag::Withdrawals)?;
+    ValidationUtils::validate_isolated_exit(pool, &ctx.accounts.instructions)?;
+    require!(
+        !pool.config.requires_commitment(net_ipt_amount),
+        PoolError::WithdrawalCommitmentRequired
+    );
+    require!(
+        !asset.is_price_stale(pool.config.max_heartbeat_secs, clock.unix_timestamp),
+        PoolError::StaleAssetPrice
+    );
+
+    require!(net_ipt_amount > 0, PoolError::InvalidAmount);
+    // Slot-stable quotes price the whole slot at its first-read rate
+    let exchange_rate = pool.pin_quote_rate(clock.slot);
+    let tier = ValidationUtils::investor_tier(pool, ctx.accounts.access_entry.as_deref())?;
+    require!(
+        ctx.accounts.user_ipt_account.amount >= net_ipt_amount,
+        PoolError::InsufficientAccountBalance
+    );
+
+    let (net_usdc_value, withdrawal_fee) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+        net_ipt_amount,
+        exchange_rate,
+        pool.config.withdrawal_fee_bps_for(tier),
+        pool.usdc_decimals,
+        pool.ipt_decimals,
+    )?;
+    let (net_usdc_value, _) = CalculationUtils::apply_exit_adjustment(
+        net_usdc_value,
+        withdrawal_fee,
+        pool.exit_adjustment_bps(),
+    )?;
+    let gross_usdc_value = net_usdc_value
+        .checked_add(withdrawal_fee)
+        .ok_or(PoolError::MathematicalOverflow)?;
+
+    // In-kind exits have no queue, so anything that would make a USDC exit
+    // wait must go through user_withdraw instead
+    pool.roll_redemption_epoch()?;
+    require!(
+        pool.redemption_fits(gross_usdc_value)
+            && pool.config.min_execution_delay_secs == 0
+            && !pool.config.requires_approval(net_ipt_amount),
+        PoolError::InKindRedemptionUnavailable
+    );
+
+    // Pay the net value in the asset; fee and adjustment stay in kind
+    let asset_amount = CalculationUtils::asset_amount_for_value(
+        net_usdc_value,
+        asset.price,
+        pool.usdc_decimals,
+        asset.decimals,
+    )?;
+    require!(asset_amount >= min_asset_amount, PoolError::SlippageExceeded);
+    require!(
+        asset_amount <= ctx.accounts.asset_vault.amount,
+        PoolError::InsufficientReserves
+    );
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.ipt_mint.to_account_info(),
+                from: ctx.accounts.user_ipt_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        net_ipt_amount,
+    )?;
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.asset_vault.to_account_info(),
+                to: ctx.accounts.user_asset_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&pool.authority_seeds(&pool_id_seed)],
+        ),
+        asset_amount,
+    )?;
+
+    let new_balance = asset.balance.saturating_sub(asset_amount);
+    let new_value =
+        CalculationUtils::asset_value(new_balance, asset.price, pool.usdc_decimals, asset.decimals)?;
+    pool.revalue_reserve_asset(asset.value, new_value)?;
+    asset.balance = new_balance;
+    asset.value = new_value;
+
+    pool.total_ipt_supply = pool
+        .total_ipt_supply
+        .checked_sub(net_ipt_amount)
+        .ok_or(PoolError::MathematicalOverflow)?;
+    pool.record_redemption(gross_usdc_value)?;
+
+    emit!(ReserveAssetWithdrawn {
+        user: ctx.accounts.user.key(),
+        pool: pool.key(),
+        mint: asset.mint,
+        ipt_amount: net_ipt_amount,
+        asset_amount,
+        usdc_value: net_usdc_value,
+        withdrawal_fee,
+        price: asset.price,
+        exchange_rate,
+        timestamp: clock.unix_timestamp,
+    });
+
+    ValidationUtils::check_reserve_drift(pool, asset, clock.unix_timestamp)?;
+
+    msg!(
+        "User burned {} IPT, received {} of {} (worth {} USDC)",
+        net_ipt_amount,
+        asset_amount,
+        asset.mint,
+        net_usdc_value
+    );
+
+    Ok(())
+}
@@ -19,14 +19,68 @@ declare_id!("HpPJBUex6FdSw7CGvYzjtUmM1629RNTqgCyu6pfcyNBx");
 pub mod refi_ipt {
     use super::*;
 
+    /// Initialize the program-wide config (upgrade authority only, once)
+    pub fn init_protocol_config(
+        ctx: Context<InitProtocolConfig>,
+        protocol_admin: Pubkey,
+        max_deposit_fee_bps: u16,
+        max_withdrawal_fee_bps: u16,
+        max_management_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::init_protocol_config::handler(
+            ctx,
+            protocol_admin,
+            max_deposit_fee_bps,
+            max_withdrawal_fee_bps,
+            max_management_fee_bps,
+        )
+    }
+
+    /// Protocol admin updates the program-wide config
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        new_protocol_admin: Pubkey,
+        max_deposit_fee_bps: u16,
+        max_withdrawal_fee_bps: u16,
+        max_management_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::update_protocol_config::handler(
+            ctx,
+            new_protocol_admin,
+            max_deposit_fee_bps,
+            max_withdrawal_fee_bps,
+            max_management_fee_bps,
+        )
+    }
+
+    /// Protocol admin engages or releases the global emergency pause
+    pub fn protocol_pause(ctx: Context<ProtocolPause>, paused: bool) -> Result<()> {
+        instructions::protocol_pause::handler(ctx, paused)
+    }
+
+    /// Rewrite a pool written by an older program onto the current layout (protocol admin only)
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool::handler(ctx)
+    }
+
+    /// Switch subsystems off or back on for every pool (protocol admin only)
+    pub fn set_feature_flags(ctx: Context<SetFeatureFlags>, disabled_features: u32) -> Result<()> {
+        instructions::set_feature_flags::handler(ctx, disabled_features)
+    }
+
     /// Initialize a new investment pool (step 1: create pool account)
-    pub fn init_pool(ctx: Context<InitializePool>, config: PoolConfig) -> Result<()> {
-        instructions::init_pool::handler(ctx, config)
+    pub fn init_pool(ctx: Context<InitializePool>, pool_id: u64, config: PoolConfig) -> Result<()> {
+        instructions::init_pool::handler(ctx, pool_id, config)
+    }
+
+    /// Initialize a new pool copying an existing pool's configuration (run step 2 after)
+    pub fn clone_pool(ctx: Context<ClonePool>, new_pool_id: u64) -> Result<()> {
+        instructions::clone_pool::handler(ctx, new_pool_id)
     }
 
     /// Initialize pool mints and reserve (step 2: create IPT mint and USDC reserve)
-    pub fn init_pool_step2(ctx: Context<InitializePoolStep2>) -> Result<()> {
-        instructions::init_pool_step2::handler(ctx)
+    pub fn init_pool_step2(ctx: Context<InitializePoolStep2>, ipt_decimals: u8) -> Result<()> {
+        instructions::init_pool_step2::handler(ctx, ipt_decimals)
     }
 
     /// User deposits net USDC into pool and receives IPT (fees calculated internally)
@@ -47,22 +101,130 @@ pub mod refi_ipt {
         instructions::user_withdraw::handler(ctx, net_ipt_amount, min_usdc_amount)
     }
 
-    /// Admin deposits USDC to increase pool reserves
-    pub fn admin_deposit_usdc(ctx: Context<AdminDepositUsdc>, amount: u64) -> Result<()> {
-        instructions::admin_deposit_usdc::handler(ctx, amount)
+    /// User splits one deposit across several pools by weight, minting
+    /// each pool's IPT in the same instruction
+    pub fn route_deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RouteDeposit<'info>>,
+        net_usdc_amount: u64,
+        legs: Vec<RouteLeg>,
+    ) -> Result<()> {
+        instructions::route_deposit::handler(ctx, net_usdc_amount, legs)
+    }
+
+    /// Vault-standard deposit of `assets` USDC for at least `min_shares` IPT
+    pub fn vault_deposit(ctx: Context<UserDeposit>, assets: u64, min_shares: u64) -> Result<()> {
+        instructions::vault_deposit::handler(ctx, assets, min_shares)
+    }
+
+    /// Vault-standard mint of `shares` IPT for at most `max_assets` USDC
+    pub fn vault_mint(ctx: Context<UserDeposit>, shares: u64, max_assets: u64) -> Result<()> {
+        instructions::vault_mint::handler(ctx, shares, max_assets)
+    }
+
+    /// Vault-standard withdrawal of `assets` USDC for at most `max_shares` IPT
+    pub fn vault_withdraw(ctx: Context<UserWithdraw>, assets: u64, max_shares: u64) -> Result<()> {
+        instructions::vault_withdraw::handler(ctx, assets, max_shares)
     }
 
-    /// Admin withdraws USDC from pool reserves
-    pub fn admin_withdraw_usdc(ctx: Context<AdminWithdrawUsdc>, amount: u64) -> Result<()> {
-        instructions::admin_withdraw_usdc::handler(ctx, amount)
+    /// Vault-standard redemption of `shares` IPT for at least `min_assets` USDC
+    pub fn vault_redeem(ctx: Context<UserWithdraw>, shares: u64, min_assets: u64) -> Result<()> {
+        instructions::vault_redeem::handler(ctx, shares, min_assets)
     }
 
-    /// Fee collector withdraws accumulated fees
-    pub fn fee_collector_withdraw(ctx: Context<FeeCollectorWithdraw>, amount: u64) -> Result<()> {
-        instructions::fee_collector_withdraw::handler(ctx, amount)
+    /// Commit to a large withdrawal as hash(pool, user, amount, min_usdc, salt)
+    pub fn commit_withdrawal(ctx: Context<CommitWithdrawal>, commitment: [u8; 32]) -> Result<()> {
+        instructions::commit_withdrawal::handler(ctx, commitment)
+    }
+
+    /// Reveal a committed withdrawal once its slot delay has passed and execute it
+    pub fn reveal_withdrawal(
+        ctx: Context<RevealWithdrawal>,
+        net_ipt_amount: u64,
+        min_usdc_amount: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_withdrawal::handler(ctx, net_ipt_amount, min_usdc_amount, salt)
+    }
+
+    /// Clear a queued withdrawal above the approval threshold for payout (compliance authority)
+    pub fn approve_withdrawal_request(
+        ctx: Context<ApproveWithdrawalRequest>,
+        user: Pubkey,
+    ) -> Result<()> {
+        instructions::approve_withdrawal_request::handler(ctx, user)
     }
 
-    /// Admin updates pool configuration
+    /// User moves a position between two pools sharing a USDC mint, fee-free
+    pub fn migrate_position(
+        ctx: Context<MigratePosition>,
+        net_ipt_amount: u64,
+        min_ipt_out: u64,
+    ) -> Result<()> {
+        instructions::migrate_position::handler(ctx, net_ipt_amount, min_ipt_out)
+    }
+
+    /// Treasurer deposits USDC to increase pool reserves
+    pub fn admin_deposit_usdc(
+        ctx: Context<AdminDepositUsdc>,
+        amount: u64,
+        reason: ReserveMovementReason,
+        memo: Option<String>,
+    ) -> Result<()> {
+        instructions::admin_deposit_usdc::handler(ctx, amount, reason, memo)
+    }
+
+    /// Treasurer deposits USDC and executes the queued withdrawals it covers
+    /// in the same instruction
+    pub fn admin_deposit_and_process<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AdminDepositAndProcess<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::admin_deposit_and_process::handler(ctx, amount)
+    }
+
+    /// Treasurer withdraws USDC from pool reserves
+    pub fn admin_withdraw_usdc(
+        ctx: Context<AdminWithdrawUsdc>,
+        amount: u64,
+        reason: ReserveMovementReason,
+        memo: Option<String>,
+    ) -> Result<()> {
+        instructions::admin_withdraw_usdc::handler(ctx, amount, reason, memo)
+    }
+
+    /// Fee collector withdraws accumulated fees from one fee stream
+    pub fn fee_collector_withdraw(
+        ctx: Context<FeeCollectorWithdraw>,
+        amount: u64,
+        bucket: FeeBucket,
+        memo: Option<String>,
+    ) -> Result<()> {
+        instructions::fee_collector_withdraw::handler(ctx, amount, bucket, memo)
+    }
+
+    /// Pay all accumulated fees to the configured fee recipients and the fee collector's ATA once they reach the sweep threshold (anyone)
+    pub fn sweep_fees<'info>(ctx: Context<'_, '_, 'info, 'info, SweepFees<'info>>) -> Result<()> {
+        instructions::sweep_fees::handler(ctx)
+    }
+
+    /// Spend accumulated fees to buy IPT from a holder at or below NAV and burn it (admin only)
+    pub fn buyback_ipt(
+        ctx: Context<BuybackIpt>,
+        ipt_amount: u64,
+        usdc_amount: u64,
+        bucket: FeeBucket,
+    ) -> Result<()> {
+        instructions::buyback_ipt::handler(ctx, ipt_amount, usdc_amount, bucket)
+    }
+
+    /// Burn IPT without redeeming it, raising NAV for remaining holders (any holder)
+    pub fn burn_ipt_donation(ctx: Context<BurnIptDonation>, ipt_amount: u64) -> Result<()> {
+        instructions::burn_ipt_donation::handler(ctx, ipt_amount)
+    }
+
+    /// Admin updates the settings that have no instruction of their own;
+    /// authorities, fees, the fee collector and supply and queue caps are
+    /// rejected here
     pub fn admin_update_config(
         ctx: Context<AdminUpdateConfig>,
         new_config: PoolConfig,
@@ -70,9 +232,306 @@ pub mod refi_ipt {
         instructions::admin_update_config::handler(ctx, new_config)
     }
 
-    /// Update exchange rate (oracle only)
-    pub fn update_exchange_rate(ctx: Context<UpdateExchangeRate>, new_rate: u64) -> Result<()> {
-        instructions::update_exchange_rate::handler(ctx, new_rate)
+    /// Admin sets the deposit, withdrawal and management fee rates
+    pub fn set_fees(
+        ctx: Context<AdminUpdateConfig>,
+        deposit_fee_bps: u16,
+        withdrawal_fee_bps: u16,
+        management_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_fees::handler(
+            ctx,
+            deposit_fee_bps,
+            withdrawal_fee_bps,
+            management_fee_bps,
+        )
+    }
+
+    /// Admin changes the fee collector
+    pub fn set_fee_collector(ctx: Context<AdminUpdateConfig>, fee_collector: Pubkey) -> Result<()> {
+        instructions::set_fee_collector::handler(ctx, fee_collector)
+    }
+
+    /// Admin changes the withdrawal queue cap
+    pub fn set_max_queue_size(ctx: Context<AdminUpdateConfig>, max_queue_size: u32) -> Result<()> {
+        instructions::set_max_queue_size::handler(ctx, max_queue_size)
+    }
+
+    /// Admin pauses or resumes one operation (deposits, withdrawals, queue, rate updates)
+    pub fn set_pause_flag(ctx: Context<SetPauseFlag>, flag: PauseFlag, paused: bool) -> Result<()> {
+        instructions::set_pause_flag::handler(ctx, flag, paused)
+    }
+
+    /// Commit the hash of the share register taken at a cutoff slot (admin only)
+    pub fn commit_share_register(
+        ctx: Context<CommitShareRegister>,
+        cutoff_slot: u64,
+        register_hash: [u8; 32],
+        holder_count: u32,
+        total_balance: u64,
+    ) -> Result<()> {
+        instructions::commit_share_register::handler(
+            ctx,
+            cutoff_slot,
+            register_hash,
+            holder_count,
+            total_balance,
+        )
+    }
+
+    /// Open a cost-basis ledger for tax lots or change its lot method
+    pub fn configure_cost_basis(ctx: Context<ConfigureCostBasis>, method: LotMethod) -> Result<()> {
+        instructions::configure_cost_basis::handler(ctx, method)
+    }
+
+    /// Admin or guardian halts or resumes batch queue processing
+    pub fn set_queue_processing_paused(
+        ctx: Context<SetQueueProcessingPaused>,
+        paused: bool,
+    ) -> Result<()> {
+        instructions::set_queue_processing_paused::handler(ctx, paused)
+    }
+
+    /// Admin or guardian pauses every operation; only the admin can unpause
+    pub fn emergency_pause(ctx: Context<EmergencyPause>) -> Result<()> {
+        instructions::emergency_pause::handler(ctx)
+    }
+
+    /// Admin sets the exchange rate and the full pause mask in one atomic step
+    pub fn admin_update_rate_and_state(
+        ctx: Context<AdminUpdateRateAndState>,
+        new_rate: u64,
+        observed_at: i64,
+        pause_flags: u8,
+    ) -> Result<()> {
+        instructions::admin_update_rate_and_state::handler(ctx, new_rate, observed_at, pause_flags)
+    }
+
+    /// Admin sets the IPT supply cap (0 = unlimited)
+    pub fn set_max_total_supply(
+        ctx: Context<SetMaxTotalSupply>,
+        new_max_total_supply: u64,
+    ) -> Result<()> {
+        instructions::set_max_total_supply::handler(ctx, new_max_total_supply)
+    }
+
+    /// Admin raises the withdrawal queue size, growing the pool account if
+    /// needed; capacity grows by at most `Pool::MAX_QUEUE_GROWTH` per call
+    pub fn expand_queue(ctx: Context<ExpandQueue>, new_max_queue_size: u32) -> Result<()> {
+        instructions::expand_queue::handler(ctx, new_max_queue_size)
+    }
+
+    /// Admin sizes the pool account to exactly fit its queue; migrates pools
+    /// created under older size math and reclaims rent from unused capacity
+    pub fn resize_pool(ctx: Context<ResizePool>) -> Result<()> {
+        instructions::resize_pool::handler(ctx)
+    }
+
+    /// Admin schedules an action behind the pool timelock
+    pub fn schedule_admin_action(
+        ctx: Context<ScheduleAdminAction>,
+        action: AdminAction,
+    ) -> Result<()> {
+        instructions::schedule_admin_action::handler(ctx, action)
+    }
+
+    /// Admin cancels a scheduled action
+    pub fn cancel_admin_action(ctx: Context<CancelAdminAction>) -> Result<()> {
+        instructions::cancel_admin_action::handler(ctx)
+    }
+
+    /// Admin puts the pool into Migration state (user flows halted)
+    pub fn begin_migration(ctx: Context<BeginMigration>, mint_cap: u64) -> Result<()> {
+        instructions::begin_migration::handler(ctx, mint_cap)
+    }
+
+    /// Admin returns the pool from Migration to Active
+    pub fn end_migration(ctx: Context<EndMigration>) -> Result<()> {
+        instructions::end_migration::handler(ctx)
+    }
+
+    /// Admin executes a scheduled migration mint
+    pub fn admin_mint_ipt(ctx: Context<AdminMintIpt>) -> Result<()> {
+        instructions::admin_mint_ipt::handler(ctx)
+    }
+
+    /// Admin executes a scheduled hand-over of the IPT mint authority
+    pub fn transfer_mint_authority(ctx: Context<TransferMintAuthority>) -> Result<()> {
+        instructions::transfer_mint_authority::handler(ctx)
+    }
+
+    /// Admin executes a scheduled change of the USDC-denominated TVL cap
+    pub fn set_tvl_cap(ctx: Context<SetTvlCap>) -> Result<()> {
+        instructions::set_tvl_cap::handler(ctx)
+    }
+
+    /// Admin executes a scheduled treasurer handover, co-signed by the
+    /// current treasurer
+    pub fn set_treasurer(ctx: Context<SetTreasurer>) -> Result<()> {
+        instructions::set_treasurer::handler(ctx)
+    }
+
+    /// Admin executes a scheduled change of the withdrawal co-signer and
+    /// threshold, co-signed by the current co-signer
+    pub fn set_withdrawal_cosigner(ctx: Context<SetWithdrawalCosigner>) -> Result<()> {
+        instructions::set_withdrawal_cosigner::handler(ctx)
+    }
+
+    /// Admin executes a scheduled change of the escheatment custody account
+    pub fn set_escheat_custody(ctx: Context<SetEscheatCustody>) -> Result<()> {
+        instructions::set_escheat_custody::handler(ctx)
+    }
+
+    /// Admin executes a scheduled change of the recovery, guardian or
+    /// compliance authority
+    pub fn set_role_authority(ctx: Context<SetRoleAuthority>) -> Result<()> {
+        instructions::set_role_authority::handler(ctx)
+    }
+
+    /// Admin executes a scheduled change of the idle-reserve sweep wrapper
+    pub fn set_sweep_wrapper(ctx: Context<SetSweepWrapper>) -> Result<()> {
+        instructions::set_sweep_wrapper::handler(ctx)
+    }
+
+    /// Update exchange rate and pending accrued yield, optionally citing the NAV
+    /// report behind them (oracle only)
+    pub fn update_exchange_rate(
+        ctx: Context<UpdateExchangeRate>,
+        new_rate: u64,
+        observed_at: i64,
+        accrued_yield: u64,
+        evidence: Option<RateEvidence>,
+    ) -> Result<()> {
+        instructions::update_exchange_rate::handler(
+            ctx,
+            new_rate,
+            observed_at,
+            accrued_yield,
+            evidence,
+        )
+    }
+
+    /// Relay an ed25519-signed rate from the pool's oracle publisher (anyone)
+    pub fn update_exchange_rate_signed(
+        ctx: Context<UpdateExchangeRateSigned>,
+        new_rate: u64,
+        timestamp: i64,
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::update_exchange_rate_signed::handler(ctx, new_rate, timestamp, nonce)
+    }
+
+    /// Set the Pyth feed the pool rate refreshes from; zero disables (admin only)
+    pub fn set_rate_feed(ctx: Context<SetRateFeed>, new_feed_id: [u8; 32]) -> Result<()> {
+        instructions::set_rate_feed::handler(ctx, new_feed_id)
+    }
+
+    /// Set the pool's display name and metadata URIs (admin only)
+    pub fn set_pool_metadata(
+        ctx: Context<SetPoolMetadata>,
+        name: String,
+        description_uri: String,
+        logo_uri: String,
+    ) -> Result<()> {
+        instructions::set_pool_metadata::handler(ctx, name, description_uri, logo_uri)
+    }
+
+    /// Set a pool's discovery category, risk tier, tags and verified flag (protocol admin only)
+    pub fn set_pool_registry_entry(
+        ctx: Context<SetPoolRegistryEntry>,
+        category: PoolCategory,
+        risk_tier: RiskTier,
+        tags: u32,
+        verified: bool,
+    ) -> Result<()> {
+        instructions::set_pool_registry_entry::handler(ctx, category, risk_tier, tags, verified)
+    }
+
+    /// Refresh the exchange rate from the configured price feed (anyone)
+    pub fn refresh_rate_from_oracle(ctx: Context<RefreshRateFromOracle>) -> Result<()> {
+        instructions::refresh_rate_from_oracle::handler(ctx)
+    }
+
+    /// Pause deposits if the oracle has missed its heartbeat (anyone)
+    pub fn check_heartbeat(ctx: Context<CheckHeartbeat>) -> Result<()> {
+        instructions::check_heartbeat::handler(ctx)
+    }
+
+    /// Freeze an abandoned pool to withdrawals only after long oracle silence (anyone)
+    pub fn trip_dead_man_switch(ctx: Context<TripDeadManSwitch>) -> Result<()> {
+        instructions::trip_dead_man_switch::handler(ctx)
+    }
+
+    /// Reopen a frozen pool once the rate is live again (admin only)
+    pub fn unfreeze_pool(ctx: Context<UnfreezePool>) -> Result<()> {
+        instructions::unfreeze_pool::handler(ctx)
+    }
+
+    /// Freeze an active pool to withdrawals only, or reopen a frozen one (admin only)
+    pub fn set_pool_state(ctx: Context<SetPoolState>, new_state: PoolState) -> Result<()> {
+        instructions::set_pool_state::handler(ctx, new_state)
+    }
+
+    /// Reverse a user's latest deposit within the refund window for a
+    /// recorded incident (admin and user)
+    pub fn refund_deposit(ctx: Context<RefundDeposit>, incident_hash: [u8; 32]) -> Result<()> {
+        instructions::refund_deposit::handler(ctx, incident_hash)
+    }
+
+    /// Close a due epoch: finalize NAV, accrue management fees, reset windows (anyone)
+    pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+        instructions::advance_epoch::handler(ctx)
+    }
+
+    /// Admin sets the key allowed to sign rate payloads (default key disables)
+    pub fn set_oracle_publisher(
+        ctx: Context<SetOraclePublisher>,
+        new_publisher: Pubkey,
+    ) -> Result<()> {
+        instructions::set_oracle_publisher::handler(ctx, new_publisher)
+    }
+
+    /// Propose a new oracle authority; it takes over once it accepts (admin only)
+    pub fn propose_oracle_authority(
+        ctx: Context<ProposeOracleAuthority>,
+        new_oracle: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_oracle_authority::handler(ctx, new_oracle)
+    }
+
+    /// Accept a proposed oracle rotation; the old key stays valid for the overlap window (new oracle)
+    pub fn accept_oracle_authority(ctx: Context<AcceptOracleAuthority>) -> Result<()> {
+        instructions::accept_oracle_authority::handler(ctx)
+    }
+
+    /// Propose a new admin authority; it takes over once it accepts (admin only)
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin::handler(ctx, new_admin)
+    }
+
+    /// Accept a proposed admin handover (new admin)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    /// Start replacing the admin, oracle and fee collector keys after the recovery delay (recovery authority)
+    pub fn initiate_recovery(
+        ctx: Context<InitiateRecovery>,
+        new_admin: Pubkey,
+        new_oracle: Pubkey,
+        new_fee_collector: Pubkey,
+    ) -> Result<()> {
+        instructions::initiate_recovery::handler(ctx, new_admin, new_oracle, new_fee_collector)
+    }
+
+    /// Call off a pending emergency recovery (admin or recovery authority)
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        instructions::cancel_recovery::handler(ctx)
+    }
+
+    /// Install the recovered keys once the recovery delay has passed (recovery authority)
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        instructions::execute_recovery::handler(ctx)
     }
 
     /// User creates withdrawal request
@@ -86,14 +545,233 @@ pub mod refi_ipt {
 
     /// Batch execute withdrawal requests from the queue
     pub fn batch_execute_withdraw<'info>(
-        ctx: Context<'_, '_, 'info, 'info, BatchExecuteWithdraw<'info>>,
+        mut ctx: Context<'_, '_, 'info, 'info, BatchExecuteWithdraw<'info>>,
         amounts: Vec<u64>,
     ) -> Result<()> {
-        instructions::process_queue::batch_execute_withdraw(ctx, amounts)
+        instructions::process_queue::batch_execute_withdraw(&mut ctx, amounts)
+    }
+
+    /// Permissionless queue crank for automation networks (Clockwork threads,
+    /// Tuktuk tasks): executes the head of the queue for the supplied user
+    /// token accounts, succeeds on an empty queue, and returns the users the
+    /// next crank should supply accounts for
+    pub fn crank_withdraw_queue<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchExecuteWithdraw<'info>>,
+    ) -> Result<Vec<Pubkey>> {
+        instructions::crank_withdraw_queue::handler(ctx)
     }
 
     /// User cancels their own withdrawal request
     pub fn cancel_withdrawal_request(ctx: Context<CancelWithdrawalRequest>) -> Result<()> {
         instructions::cancel_withdrawal::handler(ctx)
     }
+
+    /// User cancels part of their withdrawal request, keeping its place in the queue
+    pub fn shrink_withdrawal_request(
+        ctx: Context<ShrinkWithdrawalRequest>,
+        cancel_amount: u64,
+    ) -> Result<()> {
+        instructions::shrink_withdrawal::handler(ctx, cancel_amount)
+    }
+
+    /// Admin funds a USDC distribution claimable against a snapshot merkle root
+    pub fn create_distribution(
+        ctx: Context<CreateDistribution>,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        snapshot_slot: u64,
+    ) -> Result<()> {
+        instructions::create_distribution::handler(ctx, merkle_root, total_amount, snapshot_slot)
+    }
+
+    /// Holder claims their pro-rata share of a distribution (once)
+    pub fn claim_dividend(
+        ctx: Context<ClaimDividend>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_dividend::handler(ctx, amount, proof)
+    }
+
+    /// Rebasing pools: mint accrued yield as IPT and reset the rate to par (oracle only)
+    pub fn rebase(ctx: Context<Rebase>, merkle_root: [u8; 32], snapshot_slot: u64) -> Result<()> {
+        instructions::rebase::handler(ctx, merkle_root, snapshot_slot)
+    }
+
+    /// Holder mints their share of a rebase (once)
+    pub fn claim_rebase(
+        ctx: Context<ClaimRebase>,
+        ipt_amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_rebase::handler(ctx, ipt_amount, proof)
+    }
+
+    /// Commit a month's holder statements as a merkle root (admin or oracle)
+    pub fn commit_statement(
+        ctx: Context<CommitStatement>,
+        period: u32,
+        merkle_root: [u8; 32],
+        holder_count: u32,
+        total_balance: u64,
+    ) -> Result<()> {
+        instructions::commit_statement::handler(
+            ctx,
+            period,
+            merkle_root,
+            holder_count,
+            total_balance,
+        )
+    }
+
+    /// Close a fully claimed or expired distribution, returning unclaimed value to the pool (admin only)
+    pub fn close_distribution(ctx: Context<CloseDistribution>) -> Result<()> {
+        instructions::close_distribution::handler(ctx)
+    }
+
+    /// Refund a claim receipt's rent to its claimant once the distribution is closed (anyone)
+    pub fn close_claim_receipt(ctx: Context<CloseClaimReceipt>) -> Result<()> {
+        instructions::close_claim_receipt::handler(ctx)
+    }
+
+    /// Refund an unrevealed withdrawal commitment's rent (committer, or anyone once abandoned)
+    pub fn close_withdrawal_commitment(ctx: Context<CloseWithdrawalCommitment>) -> Result<()> {
+        instructions::close_withdrawal_commitment::handler(ctx)
+    }
+
+    /// Put up to 20 addresses on the allowlist or blocklist (admin only)
+    pub fn set_access_list<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetAccessList<'info>>,
+        status: AccessStatus,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_access_list::handler(ctx, status, addresses)
+    }
+
+    /// Take up to 20 addresses off the access list (admin only)
+    pub fn remove_from_access_list<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveFromAccessList<'info>>,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::remove_from_access_list::handler(ctx, addresses)
+    }
+
+    /// Assign up to 20 access-listed addresses an investor tier (admin only)
+    pub fn set_investor_tier<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetInvestorTier<'info>>,
+        tier: InvestorTier,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_investor_tier::handler(ctx, tier, addresses)
+    }
+
+    /// Open per-partner accounting for white-label deposits (admin only)
+    pub fn register_partner(ctx: Context<RegisterPartner>, partner_id: u64) -> Result<()> {
+        instructions::register_partner::handler(ctx, partner_id)
+    }
+
+    /// Flag a position idle for the dormancy period (anyone)
+    pub fn flag_dormant_position(ctx: Context<FlagDormantPosition>) -> Result<()> {
+        instructions::flag_dormant_position::handler(ctx)
+    }
+
+    /// Owner confirms their position is active, lifting any dormancy flag
+    pub fn record_position_activity(ctx: Context<RecordPositionActivity>) -> Result<()> {
+        instructions::record_position_activity::handler(ctx)
+    }
+
+    /// Execute a timelocked sweep of a dormant position's IPT to custody (admin only)
+    pub fn escheat_position(ctx: Context<EscheatPosition>) -> Result<()> {
+        instructions::escheat_position::handler(ctx)
+    }
+
+    /// Open a referral record so deposits can name the signer as referrer
+    pub fn register_referral(ctx: Context<RegisterReferral>) -> Result<()> {
+        instructions::register_referral::handler(ctx)
+    }
+
+    /// Referrer withdraws the referral earnings accrued on their deposits
+    pub fn claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+        instructions::claim_referral_earnings::handler(ctx)
+    }
+
+    /// Wrap unreserved USDC above the liquidity buffer into the configured
+    /// interest-bearing token (treasurer only)
+    pub fn sweep_idle_reserve<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepIdleReserve<'info>>,
+    ) -> Result<()> {
+        instructions::sweep_idle_reserve::handler(ctx)
+    }
+
+    /// Unwrap swept USDC back into the reserve (treasurer only)
+    pub fn unwrap_swept_reserve<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepIdleReserve<'info>>,
+        wrapped_amount: u64,
+    ) -> Result<()> {
+        instructions::unwrap_swept_reserve::handler(ctx, wrapped_amount)
+    }
+
+    /// Register a non-USDC reserve asset with its target weight (admin only)
+    pub fn add_reserve_asset(ctx: Context<AddReserveAsset>, target_weight_bps: u16) -> Result<()> {
+        instructions::add_reserve_asset::handler(ctx, target_weight_bps)
+    }
+
+    /// Change a reserve asset's target weight (admin only)
+    pub fn set_reserve_asset_weight(
+        ctx: Context<SetReserveAssetWeight>,
+        target_weight_bps: u16,
+    ) -> Result<()> {
+        instructions::set_reserve_asset_weight::handler(ctx, target_weight_bps)
+    }
+
+    /// Oracle posts a reserve asset's USDC price and revalues the holding
+    pub fn update_reserve_asset_price(
+        ctx: Context<UpdateReserveAssetPrice>,
+        new_price: u64,
+    ) -> Result<()> {
+        instructions::update_reserve_asset_price::handler(ctx, new_price)
+    }
+
+    /// User deposits a reserve asset at its oracle price and receives IPT
+    pub fn deposit_reserve_asset(
+        ctx: Context<DepositReserveAsset>,
+        asset_amount: u64,
+        min_ipt_amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_reserve_asset::handler(ctx, asset_amount, min_ipt_amount)
+    }
+
+    /// User redeems IPT in kind for a reserve asset at its oracle price
+    pub fn withdraw_reserve_asset(
+        ctx: Context<WithdrawReserveAsset>,
+        net_ipt_amount: u64,
+        min_asset_amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_reserve_asset::handler(ctx, net_ipt_amount, min_asset_amount)
+    }
+
+    /// Report a reserve asset that has drifted outside its target band (anyone)
+    pub fn check_reserve_drift(ctx: Context<CheckReserveDrift>) -> Result<()> {
+        instructions::check_reserve_drift::handler(ctx)
+    }
+
+    /// Start closing the pool; payouts then follow the wind-down waterfall (admin only)
+    pub fn begin_wind_down(ctx: Context<BeginWindDown>) -> Result<()> {
+        instructions::begin_wind_down::handler(ctx)
+    }
+
+    /// Move the wind-down waterfall to its next class once the current one is paid (anyone)
+    pub fn advance_wind_down(ctx: Context<AdvanceWindDown>) -> Result<()> {
+        instructions::advance_wind_down::handler(ctx)
+    }
+
+    /// Open a user's payout escrow for pull-mode payouts or ones their USDC account cannot receive (anyone)
+    pub fn open_payout_escrow(ctx: Context<OpenPayoutEscrow>) -> Result<()> {
+        instructions::open_payout_escrow::handler(ctx)
+    }
+
+    /// User claims batch payouts held in their payout escrow
+    pub fn claim_escrowed_payout(ctx: Context<ClaimEscrowedPayout>) -> Result<()> {
+        instructions::claim_escrowed_payout::handler(ctx)
+    }
 }
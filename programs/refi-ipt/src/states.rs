@@ -1,18 +1,35 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::errors::PoolError;
+use crate::utils::CalculationUtils;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::solana_program::hash::hashv;
 
 #[account]
+#[derive(InitSpace)]
 pub struct Pool {
+    /// Account layout this pool was written with; kept first so any program
+    /// version can read it before trusting the rest of the account
+    pub layout_version: u16,
     /// Pool authority (PDA)
     pub pool_authority: Pubkey,
     /// USDC mint address
     pub usdc_mint: Pubkey,
+    /// Distinguishes pools sharing the same USDC mint
+    pub pool_id: u64,
     /// IPT mint address
     pub ipt_mint: Pubkey,
     /// USDC reserve token account
     pub usdc_reserve: Pubkey,
+    /// Entries are sized separately from INIT_SPACE, see `Pool::space`
+    #[max_len(0)]
     pub pending_queue: Vec<PendingWithdraw>,
+    /// Entries before this index were consumed by batch execution and are
+    /// dropped on the next compaction; the live queue starts here
+    pub queue_head: u32,
+    /// Number of queue entries the account is currently sized for
+    pub queue_capacity: u32,
 
     /// Current exchange rate (IPT to USDC, scaled by 1e6)
     /// e.g., 1.02 USDC per IPT = 1_020_000
@@ -24,17 +41,154 @@ pub struct Pool {
     pub total_usdc_reserves: u64,
     /// Total accumulated fees
     pub total_accumulated_fees: u64,
+    /// Accumulated fees by stream; together they make up total_accumulated_fees
+    pub accumulated_deposit_fees: u64,
+    pub accumulated_withdrawal_fees: u64,
+    pub accumulated_management_fees: u64,
     /// Maximum total IPT supply allowed (0 = unlimited)
     pub max_total_supply: u64,
+    /// Largest NAV deposits may take the pool to, in USDC (0 = unlimited);
+    /// changed only through the timelocked SetTvlCap action
+    pub tvl_cap: u64,
 
     /// Pool configuration
     pub config: PoolConfig,
 
     /// Pool state
     pub pool_state: PoolState,
+    /// Independent pause switches (bitmask of PauseFlag)
+    pub pause_flags: u8,
+
+    /// Mint decimals used for pricing
+    pub usdc_decimals: u8,
+    pub ipt_decimals: u8,
+
+    /// Id of the next timelocked admin action
+    pub next_timelock_id: u64,
+    /// Id of the next dividend distribution
+    pub next_distribution_id: u64,
+
+    /// IPT that may be minted by admin_mint_ipt during the current migration
+    pub migration_mint_cap: u64,
+    /// IPT minted by admin_mint_ipt during the current migration
+    pub migration_minted: u64,
+
+    /// Pool accounting epoch, advanced by advance_epoch
+    pub current_epoch: u64,
+    /// When the current accounting epoch opened
+    pub epoch_started_at: i64,
+    /// NAV finalized when the previous epoch closed
+    pub last_epoch_nav: u64,
+
+    /// Epoch of the open redemption window (u64::MAX = none opened yet)
+    pub redemption_epoch: u64,
+    /// USDC that may leave the pool during the open window
+    pub epoch_redemption_limit: u64,
+    /// Gross USDC redeemed during the open window
+    pub epoch_redeemed_usdc: u64,
+
+    /// Epoch `epoch_admin_withdrawn` counts admin_withdraw_usdc calls for
+    pub admin_withdrawal_epoch: u64,
+    /// USDC the treasurer has withdrawn during `admin_withdrawal_epoch`
+    pub epoch_admin_withdrawn: u64,
+
+    /// Key whose ed25519-signed rate payloads anyone may relay (default = disabled)
+    pub oracle_publisher: Pubkey,
+    /// Nonce of the last accepted signed rate payload
+    pub last_oracle_nonce: u64,
+    /// Pyth feed id refresh_rate_from_oracle reads the rate from (zero = disabled)
+    pub rate_feed_id: [u8; 32],
+
+    /// Admin key proposed by the current admin, awaiting accept_admin (default = none)
+    pub pending_admin: Pubkey,
+    /// Oracle key proposed by the admin, awaiting accept_oracle_authority (default = none)
+    pub pending_oracle_authority: Pubkey,
+    /// Rotated-out oracle key, still accepted until `oracle_overlap_ends_at`
+    pub previous_oracle_authority: Pubkey,
+    pub oracle_overlap_ends_at: i64,
+
+    /// IPT bought back and burned by buyback_ipt, and the fee USDC spent on it
+    pub total_buyback_ipt: u64,
+    pub total_buyback_usdc: u64,
+
+    /// USDC set aside in the reserve for deposit promotions, funded with
+    /// admin_deposit_usdc (ReserveMovementReason::Promotion)
+    pub promo_budget: u64,
+    /// Promotion budget spent on bonus IPT and waived deposit fees
+    pub promo_spent: u64,
+
+    /// Depositor ordinals handed out so far
+    pub depositor_count: u64,
+
+    /// Deposit certificate numbers handed out so far; each deposit takes
+    /// the next one, starting at 1
+    pub certificates_issued: u64,
+
+    /// Referral earnings accrued but not yet claimed; held in the reserve
+    pub referral_payable: u64,
+
+    /// Addresses currently on the pool's blocklist
+    pub blocked_count: u64,
+
+    /// Keys the recovery authority has moved to install, executable from
+    /// `recovery_executable_at` (admin default = no recovery pending; oracle
+    /// and fee collector default = keep the current key)
+    pub pending_recovery_admin: Pubkey,
+    pub pending_recovery_oracle: Pubkey,
+    pub pending_recovery_fee_collector: Pubkey,
+    pub recovery_executable_at: i64,
+
+    /// Hash of the NAV report behind the current rate (zero = none posted)
+    pub rate_evidence_hash: [u8; 32],
+
+    /// Yield in USDC the oracle reports as accrued but not yet realized in
+    /// the current rate; shown as pending yield, not part of NAV
+    pub accrued_yield: u64,
+
+    /// Rate user flows price at within `quote_rate_slot`, pinned by the
+    /// slot's first read while `slot_stable_quotes` is on
+    pub quote_rate_slot: u64,
+    pub quote_rate: u64,
+
+    /// Non-USDC reserve assets registered with add_reserve_asset
+    pub reserve_asset_count: u8,
+    /// USDC value of all non-USDC reserve assets at their last oracle prices
+    pub asset_reserves_value: u64,
+
+    /// Index into the wind-down waterfall of the class being paid, and
+    /// when begin_wind_down ran (0 = never)
+    pub wind_down_stage: u8,
+    pub wind_down_started_at: i64,
+
+    /// Executed redemptions held in users' payout escrows awaiting
+    /// claim_escrowed_payout
+    pub escrowed_payouts: u64,
+
+    /// Whether the queue sits past an alert threshold; QueueDepthAlert fires
+    /// when this changes
+    pub queue_alert_raised: bool,
+
+    /// USDC in the reserve earmarked for queued withdrawals, funded with
+    /// admin_deposit_usdc (ReserveMovementReason::QueueFunding); instant
+    /// withdrawals cannot use it while the queue is non-empty
+    pub queue_reserved_usdc: u64,
+
+    /// USDC principal swept into the configured wrapper token; still part
+    /// of the reserve, but not available until unwrapped
+    pub swept_usdc: u64,
+
+    /// Display metadata for explorers and aggregators (set_pool_metadata)
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(200)]
+    pub description_uri: String,
+    #[max_len(200)]
+    pub logo_uri: String,
 
     /// Timestamps
     pub last_rate_update: i64,
+    /// Oracle observation time of the current exchange rate
+    pub last_rate_observed_at: i64,
     pub created_at: i64,
 
     /// PDA bump
@@ -44,39 +198,565 @@ pub struct Pool {
 impl Pool {
     pub const SEED_PREFIX: &'static [u8] = b"pool";
     
-    // Maximum queue size for account allocation
-    // Each PendingWithdraw = 32 (Pubkey) + 8 (u64) + 8 (u64) = 48 bytes
+    /// Per-user USDC token account (owned by the pool authority) holding
+    /// batch payouts that could not be delivered
+    pub const PAYOUT_ESCROW_SEED: &'static [u8] = b"payout_escrow";
+
+    /// Wrapper token account (owned by the pool authority) holding swept USDC
+    pub const SWEEP_VAULT_SEED: &'static [u8] = b"sweep_vault";
+
+    /// Layout this program reads and writes; bumped by each release that
+    /// changes the Pool layout, which also adds a migrate_pool step
+    pub const LAYOUT_VERSION: u16 = 1;
+
+    /// Size of every layout 0 pool account: written before layout versions,
+    /// with a fixed 20-entry queue of 48-byte requests
+    pub const V0_LEN: usize = 8 + 4 * 32 + 4 + 20 * 48 + 5 * 8 + 122 + 1 + 8 + 8 + 1;
+
+    /// `pool_id` of pools created before pool ids, whose PDA was derived
+    /// from the prefix and USDC mint alone; never assigned to a new pool
+    pub const LEGACY_POOL_ID: u64 = u64::MAX;
+
+    // Queue size allocated at pool creation (grow with expand_queue)
     pub const MAX_QUEUE_SIZE: usize = 20;
-    pub const PENDING_WITHDRAW_SIZE: usize = 32 + 8 + 8; // 48 bytes
 
-    pub const LEN: usize = 8 + // discriminator
-        32 + // pool_authority
-        32 + // usdc_mint
-        32 + // ipt_mint
-        32 + // usdc_reserve
-        4 +  // pending_queue vec length prefix
-        (Self::MAX_QUEUE_SIZE * Self::PENDING_WITHDRAW_SIZE) + // pending_queue data: MAX_QUEUE_SIZE items × 48 bytes
-        8 +  // current_exchange_rate
-        8 +  // total_ipt_supply
-        8 +  // total_usdc_reserves
-        8 +  // total_accumulated_fees
-        8 +  // max_total_supply
-        PoolConfig::LEN + // config
-        1 +  // pool_state
-        8 +  // last_rate_update
-        8 +  // created_at
-        1;   // bump
+    // Upper bound for expand_queue, keeps queue scans within compute limits
+    pub const MAX_QUEUE_CAPACITY: usize = 200;
+
+    // Most queue entries one expand_queue call can add; an account grows by
+    // at most MAX_PERMITTED_DATA_INCREASE bytes per instruction, so larger
+    // expansions take several calls
+    pub const MAX_QUEUE_GROWTH: usize = MAX_PERMITTED_DATA_INCREASE / PendingWithdraw::INIT_SPACE;
+
+    pub const LEN: usize = Self::space(Self::MAX_QUEUE_SIZE);
+
+    // Oldest signed rate payload update_exchange_rate_signed accepts
+    pub const MAX_SIGNED_RATE_AGE: i64 = 5 * 60; // 5 minutes
+
+    // Oldest feed price and widest confidence interval refresh_rate_from_oracle accepts
+    pub const MAX_FEED_PRICE_AGE: i64 = 5 * 60; // 5 minutes
+    pub const MAX_FEED_CONF_BPS: u16 = 100; // 1% of price
+
+    // Display metadata limits, in bytes; must match the fields' max_len
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_URI_LEN: usize = 200;
+
+    /// Account size for a pool whose queue holds `queue_capacity` entries
+    pub const fn space(queue_capacity: usize) -> usize {
+        8 + Self::INIT_SPACE + queue_capacity * PendingWithdraw::INIT_SPACE
+    }
+
+    /// Smallest queue capacity that still fits the configured cap and every
+    /// queued entry
+    pub fn required_queue_capacity(&self) -> u32 {
+        self.config.max_queue_size.max(self.pending_queue.len() as u32)
+    }
+
+    /// Withdrawals still waiting, in priority order
+    pub fn queued(&self) -> &[PendingWithdraw] {
+        &self.pending_queue[self.queue_head as usize..]
+    }
+
+    pub fn queued_mut(&mut self) -> &mut [PendingWithdraw] {
+        let head = self.queue_head as usize;
+        &mut self.pending_queue[head..]
+    }
+
+    /// Drop consumed entries from the front of the queue
+    pub fn compact_queue(&mut self) {
+        if self.queue_head > 0 {
+            self.pending_queue.drain(..self.queue_head as usize);
+            self.queue_head = 0;
+        }
+    }
+
+    /// Consume the settled entries among the first `settled.len()` live ones:
+    /// kept entries slide to the back of that window, keeping their order,
+    /// and the head moves past the rest. Costs O(batch), not O(queue depth).
+    pub fn consume_queue_window(&mut self, settled: &[bool]) {
+        let head = self.queue_head as usize;
+        let mut write = head + settled.len();
+        for read in (head..head + settled.len()).rev() {
+            if !settled[read - head] {
+                write -= 1;
+                self.pending_queue.swap(read, write);
+            }
+        }
+        self.queue_head = write as u32;
+    }
+
+    /// Whether the admin has paused the given operation
+    pub fn is_paused(&self, flag: PauseFlag) -> bool {
+        self.pause_flags & flag.mask() != 0
+    }
+
+    /// Whether the oracle has missed its heartbeat as of `now`
+    pub fn is_oracle_stale(&self, now: i64) -> bool {
+        self.config.max_heartbeat_secs > 0
+            && now.saturating_sub(self.last_rate_update) > self.config.max_heartbeat_secs
+    }
+
+    /// Payout class the wind-down waterfall is currently paying, if winding down
+    pub fn wind_down_class(&self) -> Option<PayoutClass> {
+        if self.pool_state != PoolState::WindDown {
+            return None;
+        }
+        self.config
+            .wind_down_order()
+            .get(self.wind_down_stage as usize)
+            .copied()
+    }
+
+    /// Whether every claim in the current waterfall class has been paid
+    pub fn wind_down_stage_settled(&self) -> bool {
+        match self.wind_down_class() {
+            Some(PayoutClass::QueuedWithdrawals) => self.queued().is_empty(),
+            Some(PayoutClass::Holders) => self.total_ipt_supply == 0,
+            Some(PayoutClass::Fees) => {
+                self.total_accumulated_fees == 0 && self.referral_payable == 0
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the oracle has been silent long enough to trip the dead-man switch
+    pub fn dead_man_switch_due(&self, now: i64) -> bool {
+        self.config.dead_man_switch_secs > 0
+            && now.saturating_sub(self.last_rate_update) > self.config.dead_man_switch_secs
+    }
+
+    /// Exit adjustment owed by a withdrawal requested now
+    pub fn exit_adjustment_bps(&self) -> u16 {
+        let depth = self.config.queue_pressure_depth;
+        if depth > 0 && self.queued().len() > depth as usize {
+            self.config.queue_pressure_bps
+        } else {
+            0
+        }
+    }
+
+    /// USDC owed to queued withdrawals at the current rate, after fees
+    pub fn queued_usdc_obligation(&self) -> Result<u64> {
+        self.queued().iter().try_fold(0u64, |total, pending| {
+            let (net_usdc, _) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+                pending.amount,
+                self.current_exchange_rate,
+                self.config.withdrawal_fee_bps_for(pending.tier),
+                self.usdc_decimals,
+                self.ipt_decimals,
+            )?;
+            total
+                .checked_add(net_usdc)
+                .ok_or(PoolError::MathematicalOverflow.into())
+        })
+    }
+
+    /// How many entries from the head of the queue `reserve` USDC covers at
+    /// the current rate, stopping at the first that does not fit
+    pub fn queued_entries_covered(&self, reserve: u64) -> Result<usize> {
+        let mut remaining = reserve;
+        for (covered, pending) in self.queued().iter().enumerate() {
+            let (net_usdc, fee) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+                pending.amount,
+                self.current_exchange_rate,
+                self.config.withdrawal_fee_bps_for(pending.tier),
+                self.usdc_decimals,
+                self.ipt_decimals,
+            )?;
+            let gross_usdc = net_usdc
+                .checked_add(fee)
+                .ok_or(PoolError::MathematicalOverflow)?;
+            match remaining.checked_sub(gross_usdc) {
+                Some(left) => remaining = left,
+                None => return Ok(covered),
+            }
+        }
+        Ok(self.queued().len())
+    }
+
+    /// Part of a `reserve_balance` USDC reserve instant withdrawals may use
+    pub fn unreserved_liquidity(&self, reserve_balance: u64) -> u64 {
+        if self.queued().is_empty() {
+            return reserve_balance;
+        }
+        reserve_balance.saturating_sub(self.queue_reserved_usdc)
+    }
+
+    /// Unreserved USDC above the liquidity buffer, which sweep_idle_reserve
+    /// may wrap
+    pub fn sweepable_usdc(&self, reserve_balance: u64) -> u64 {
+        self.unreserved_liquidity(reserve_balance)
+            .saturating_sub(self.config.liquidity_buffer_usdc)
+    }
+
+    /// Swept principal backing `wrapped_amount` of the `wrapped_balance`
+    /// the pool holds; the rest of what unwrapping returns is yield
+    pub fn swept_principal(&self, wrapped_amount: u64, wrapped_balance: u64) -> Result<u64> {
+        require!(
+            wrapped_amount <= wrapped_balance,
+            PoolError::InsufficientReserves
+        );
+        let principal = (self.swept_usdc as u128)
+            .checked_mul(wrapped_amount as u128)
+            .and_then(|v| v.checked_div(wrapped_balance as u128))
+            .ok_or(PoolError::MathematicalOverflow)?;
+        Ok(principal as u64)
+    }
+
+    /// Whether the queue has reached either alert threshold
+    pub fn queue_alert_breached(&self, queued_usdc: u64) -> bool {
+        let depth = self.config.queue_alert_depth;
+        let usdc = self.config.queue_alert_usdc;
+        (depth > 0 && self.queued().len() >= depth as usize) || (usdc > 0 && queued_usdc >= usdc)
+    }
+
+    /// Number the next deposit's certificate
+    pub fn issue_certificate(&mut self) -> Result<u64> {
+        self.certificates_issued = self
+            .certificates_issued
+            .checked_add(1)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        Ok(self.certificates_issued)
+    }
+
+    /// Whether the IPT supply sits exactly at a non-zero cap
+    pub fn is_at_supply_cap(&self) -> bool {
+        self.max_total_supply > 0 && self.total_ipt_supply == self.max_total_supply
+    }
+
+    /// Close subscriptions once the supply cap is reached; returns true on transition
+    pub fn close_subscriptions_if_full(&mut self) -> bool {
+        if !self.is_at_supply_cap() || self.is_paused(PauseFlag::Deposits) {
+            return false;
+        }
+        self.pause_flags |= PauseFlag::Deposits.mask();
+        true
+    }
+
+    /// Priority of a queue entry under the configured weights, lowest served
+    /// first; ties fall back to request time. Every entry ages at the same
+    /// rate, so the ordering never changes over time and the queue can be
+    /// kept sorted on insert.
+    pub fn queue_priority(&self, pending: &PendingWithdraw) -> (u128, i64) {
+        let whole_ipt = pending.amount / 10u64.pow(self.ipt_decimals as u32);
+        let cost = (self.config.queue_age_weight as u128) * (pending.requested_at.max(0) as u128)
+            + (self.config.queue_size_weight as u128) * (whole_ipt as u128);
+        (cost, pending.requested_at)
+    }
+
+    /// Queue `pending` behind every entry with the same or better priority;
+    /// returns its position
+    pub fn enqueue_withdrawal(&mut self, pending: PendingWithdraw) -> usize {
+        // Inserting shifts the queue anyway, so compact in the same pass
+        self.compact_queue();
+        let priority = self.queue_priority(&pending);
+        let position = self
+            .pending_queue
+            .iter()
+            .position(|queued| self.queue_priority(queued) > priority)
+            .unwrap_or(self.pending_queue.len());
+        self.pending_queue.insert(position, pending);
+        position
+    }
+
+    /// Whether deposits must present the depositor's access list entry
+    pub fn access_list_enforced(&self) -> bool {
+        self.config.allowlist_only || self.blocked_count > 0
+    }
+
+    /// Re-sort the queue after the priority weights change
+    pub fn reprioritize_queue(&mut self) {
+        self.compact_queue();
+        let mut queue = std::mem::take(&mut self.pending_queue);
+        queue.sort_by_key(|pending| self.queue_priority(pending));
+        self.pending_queue = queue;
+    }
+
+    /// Fee waived and bonus USDC owed on a deposit of `net_usdc_amount` at
+    /// `now`, capped by what is left of the promotion budget (waiver first)
+    pub fn promotion_for_deposit(
+        &self,
+        net_usdc_amount: u64,
+        deposit_fee: u64,
+        now: i64,
+    ) -> Result<(u64, u64)> {
+        if !self.config.promotion_active(now) {
+            return Ok((0, 0));
+        }
+
+        let mut budget = self.promo_budget;
+        let fee_waived = if self.config.promo_waives_deposit_fee {
+            deposit_fee.min(budget)
+        } else {
+            0
+        };
+        budget -= fee_waived;
+
+        let bonus_usdc = CalculationUtils::calculate_fee(net_usdc_amount, self.config.promo_bonus_bps)?
+            .min(budget);
+
+        Ok((fee_waived, bonus_usdc))
+    }
+
+    /// Draw `amount` from the promotion budget
+    pub fn spend_promo_budget(&mut self, amount: u64) -> Result<()> {
+        self.promo_budget = self
+            .promo_budget
+            .checked_sub(amount)
+            .ok_or(PoolError::MathematicalUnderflow)?;
+        self.promo_spent = self
+            .promo_spent
+            .checked_add(amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        Ok(())
+    }
+
+    /// Return promotion budget spent by a refunded deposit
+    pub fn restore_promo_budget(&mut self, amount: u64) -> Result<()> {
+        self.promo_spent = self
+            .promo_spent
+            .checked_sub(amount)
+            .ok_or(PoolError::MathematicalUnderflow)?;
+        self.promo_budget = self
+            .promo_budget
+            .checked_add(amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        Ok(())
+    }
+
+    /// USDC value of the whole IPT supply at the current rate
+    pub fn nav(&self) -> Result<u64> {
+        let (nav, _) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+            self.total_ipt_supply,
+            self.current_exchange_rate,
+            0,
+            self.usdc_decimals,
+            self.ipt_decimals,
+        )?;
+        Ok(nav)
+    }
+
+    /// Exchange rate user flows price at in `slot`; with slot-stable quotes
+    /// the first read in a slot pins the rate for the rest of it
+    pub fn pin_quote_rate(&mut self, slot: u64) -> u64 {
+        if !self.config.slot_stable_quotes {
+            return self.current_exchange_rate;
+        }
+        if self.quote_rate_slot != slot || self.quote_rate == 0 {
+            self.quote_rate_slot = slot;
+            self.quote_rate = self.current_exchange_rate;
+        }
+        self.quote_rate
+    }
+
+    /// Rate `pin_quote_rate` would return in `slot`, without pinning it
+    pub fn quote_rate_at(&self, slot: u64) -> u64 {
+        if self.config.slot_stable_quotes && self.quote_rate_slot == slot && self.quote_rate > 0 {
+            self.quote_rate
+        } else {
+            self.current_exchange_rate
+        }
+    }
+
+    /// USDC value of every reserve: the USDC reserve, swept USDC and
+    /// priced assets
+    pub fn total_reserve_value(&self) -> Result<u64> {
+        self.total_usdc_reserves
+            .checked_add(self.asset_reserves_value)
+            .and_then(|v| v.checked_add(self.swept_usdc))
+            .ok_or(PoolError::MathematicalOverflow.into())
+    }
+
+    /// Move the aggregate asset value from an asset's old valuation to its new one
+    pub fn revalue_reserve_asset(&mut self, old_value: u64, new_value: u64) -> Result<()> {
+        self.asset_reserves_value = self
+            .asset_reserves_value
+            .checked_sub(old_value)
+            .and_then(|v| v.checked_add(new_value))
+            .ok_or(PoolError::MathematicalOverflow)?;
+        Ok(())
+    }
+
+    /// Open a new redemption window sized from current NAV when the pool epoch changes
+    pub fn roll_redemption_epoch(&mut self) -> Result<()> {
+        if self.current_epoch == self.redemption_epoch {
+            return Ok(());
+        }
+
+        let nav = self.nav()?;
+
+        self.redemption_epoch = self.current_epoch;
+        self.epoch_redemption_limit =
+            CalculationUtils::calculate_fee(nav, self.config.max_redemption_bps_per_epoch)?;
+        self.epoch_redeemed_usdc = 0;
+        Ok(())
+    }
+
+    /// Whether `gross_usdc` more fits under this epoch's redemption gate
+    pub fn redemption_fits(&self, gross_usdc: u64) -> bool {
+        self.config.max_redemption_bps_per_epoch == 0
+            || self
+                .epoch_redeemed_usdc
+                .checked_add(gross_usdc)
+                .is_some_and(|total| total <= self.epoch_redemption_limit)
+    }
+
+    /// Whether `key` may publish rates as of `now`: the current oracle, or
+    /// the previous one during a rotation's overlap window
+    pub fn is_oracle(&self, key: &Pubkey, now: i64) -> bool {
+        *key == self.config.oracle_authority
+            || (*key == self.previous_oracle_authority && now < self.oracle_overlap_ends_at)
+    }
+
+    /// Count an admin withdrawal of `amount` against the current epoch and
+    /// return the epoch total, which decides whether the co-signer must sign
+    pub fn record_admin_withdrawal(&mut self, amount: u64) -> Result<u64> {
+        if self.admin_withdrawal_epoch != self.current_epoch {
+            self.admin_withdrawal_epoch = self.current_epoch;
+            self.epoch_admin_withdrawn = 0;
+        }
+        self.epoch_admin_withdrawn = self
+            .epoch_admin_withdrawn
+            .checked_add(amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        Ok(self.epoch_admin_withdrawn)
+    }
+
+    /// Count `gross_usdc` against this epoch's redemption gate
+    pub fn record_redemption(&mut self, gross_usdc: u64) -> Result<()> {
+        self.epoch_redeemed_usdc = self
+            .epoch_redeemed_usdc
+            .checked_add(gross_usdc)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        Ok(())
+    }
+
+    fn fee_bucket_mut(&mut self, bucket: FeeBucket) -> &mut u64 {
+        match bucket {
+            FeeBucket::Deposit => &mut self.accumulated_deposit_fees,
+            FeeBucket::Withdrawal => &mut self.accumulated_withdrawal_fees,
+            FeeBucket::Management => &mut self.accumulated_management_fees,
+        }
+    }
+
+    /// Accumulated fees in `bucket`
+    pub fn fee_balance(&self, bucket: FeeBucket) -> u64 {
+        match bucket {
+            FeeBucket::Deposit => self.accumulated_deposit_fees,
+            FeeBucket::Withdrawal => self.accumulated_withdrawal_fees,
+            FeeBucket::Management => self.accumulated_management_fees,
+        }
+    }
+
+    /// Book `amount` of USDC fees to `bucket`
+    pub fn credit_fees(&mut self, bucket: FeeBucket, amount: u64) -> Result<()> {
+        let balance = self.fee_bucket_mut(bucket);
+        *balance = balance
+            .checked_add(amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        self.total_accumulated_fees = self
+            .total_accumulated_fees
+            .checked_add(amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        Ok(())
+    }
 
-    pub fn authority_seeds(&self) -> [&[u8]; 3] {
+    /// Spend `amount` of USDC fees from `bucket`
+    pub fn debit_fees(&mut self, bucket: FeeBucket, amount: u64) -> Result<()> {
+        let balance = self.fee_bucket_mut(bucket);
+        *balance = balance
+            .checked_sub(amount)
+            .ok_or(PoolError::InsufficientAccumulatedFees)?;
+        self.total_accumulated_fees = self
+            .total_accumulated_fees
+            .checked_sub(amount)
+            .ok_or(PoolError::InsufficientAccumulatedFees)?;
+        Ok(())
+    }
+
+    /// Layout version of raw pool account data, read without deserializing
+    /// the (possibly outdated) rest of the account
+    pub fn read_layout_version(data: &[u8]) -> Option<u16> {
+        if data.len() < 10 || data[..8] != <Self as anchor_lang::Discriminator>::DISCRIMINATOR {
+            return None;
+        }
+        // Layout 0 has no version field, but is the only one of its size
+        if data.len() == Self::V0_LEN {
+            return Some(0);
+        }
+        Some(u16::from_le_bytes([data[8], data[9]]))
+    }
+
+    /// Address of `user`'s payout escrow in the pool at `pool`
+    pub fn payout_escrow_address(pool: &Pubkey, user: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[Self::PAYOUT_ESCROW_SEED, pool.as_ref(), user.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Pool id seed of the pool PDA: `pool_id.to_le_bytes()`, or nothing for
+    /// a legacy pool, whose address was derived before pools had ids
+    pub fn id_seed(&self) -> Vec<u8> {
+        if self.pool_id == Self::LEGACY_POOL_ID {
+            Vec::new()
+        } else {
+            self.pool_id.to_le_bytes().to_vec()
+        }
+    }
+
+    /// Signer seeds for the pool PDA; `pool_id_seed` is `id_seed()`
+    pub fn authority_seeds<'a>(&'a self, pool_id_seed: &'a [u8]) -> [&'a [u8]; 4] {
         [
             Self::SEED_PREFIX,
             self.usdc_mint.as_ref(),
+            pool_id_seed,
             std::slice::from_ref(&self.bump),
         ]
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+/// Program-wide configuration shared by every pool
+#[account]
+pub struct ProtocolConfig {
+    /// Protocol admin (governs all pools above individual pool admins)
+    pub protocol_admin: Pubkey,
+
+    /// Upper bounds for pool fee rates in basis points
+    pub max_deposit_fee_bps: u16,
+    pub max_withdrawal_fee_bps: u16,
+    pub max_management_fee_bps: u16,
+
+    /// Emergency pause flag for the whole protocol
+    pub is_paused: bool,
+
+    /// Subsystems switched off program-wide (bitmask of Feature)
+    pub disabled_features: u32,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"config";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // protocol_admin
+        2 +  // max_deposit_fee_bps
+        2 +  // max_withdrawal_fee_bps
+        2 +  // max_management_fee_bps
+        1 +  // is_paused
+        4 +  // disabled_features
+        1;   // bump
+
+    /// Whether `feature` is switched on program-wide
+    pub fn feature_enabled(&self, feature: Feature) -> bool {
+        self.disabled_features & feature.mask() == 0
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct PoolConfig {
     /// Admin authority
     pub admin_authority: Pubkey,
@@ -84,6 +764,8 @@ pub struct PoolConfig {
     pub oracle_authority: Pubkey,
     /// Fee collector address
     pub fee_collector: Pubkey,
+    /// Moves USDC in and out of the reserve (admin_deposit_usdc / admin_withdraw_usdc)
+    pub treasurer_authority: Pubkey,
 
     /// Fee rates in basis points (100 = 1%)
     pub deposit_fee_bps: u16,
@@ -98,27 +780,498 @@ pub struct PoolConfig {
 
     /// Maximum withdrawal queue size
     pub max_queue_size: u32,
+
+    /// Delay in seconds before a scheduled admin action can execute
+    pub timelock_delay: i64,
+
+    /// Lowest exchange rate the oracle may post (scaled by 1e6, 0 = no floor)
+    pub rate_floor: u64,
+    /// Highest exchange rate the oracle may post (scaled by 1e6, 0 = no ceiling)
+    pub rate_ceiling: u64,
+
+    /// Longest the oracle may stay silent before deposits pause (0 = no watchdog)
+    pub max_heartbeat_secs: i64,
+    /// Oracle silence after which anyone may freeze the pool to
+    /// withdrawals-only with trip_dead_man_switch (0 = disabled)
+    pub dead_man_switch_secs: i64,
+    /// Shortest gap between oracle rate updates (0 = no minimum)
+    pub min_rate_update_interval_secs: i64,
+
+    /// Share of NAV redeemable instantly per epoch, in basis points (0 = no gate)
+    pub max_redemption_bps_per_epoch: u16,
+
+    /// Queue depth beyond which new withdrawals pay an exit adjustment (0 = off)
+    pub queue_pressure_depth: u32,
+    /// Exit adjustment in basis points, left in the pool for remaining holders
+    pub queue_pressure_bps: u16,
+
+    /// Distribute yield by minting IPT to holders instead of raising the rate
+    /// (fixed at pool creation)
+    pub rebasing: bool,
+
+    /// Minimum length of a pool accounting epoch
+    pub epoch_duration_secs: i64,
+
+    /// Queue priority weights: an entry's cost is age_weight × requested_at
+    /// + size_weight × whole IPT requested, lowest first (both 0 = FIFO)
+    pub queue_age_weight: u32,
+    pub queue_size_weight: u32,
+
+    /// Largest share of a batch's reserves one request may take; the rest
+    /// stays queued (0 = no cap)
+    pub max_batch_share_bps: u16,
+
+    /// Consecutive same-reason batch skips before a queued withdrawal fails
+    /// terminally (1 = drop on first skip)
+    pub max_skip_attempts: u8,
+
+    /// Review window between a withdrawal request and its payout; while
+    /// non-zero every withdrawal is queued (0 = pay as soon as possible)
+    pub min_execution_delay_secs: i64,
+
+    /// Charged in IPT on cancel_withdrawal_request to discourage squatting
+    /// queue slots (0 = free)
+    pub cancellation_fee_bps: u16,
+    /// Requests waiting at least this long cancel free (0 = never waived)
+    pub cancellation_grace_secs: i64,
+
+    /// Withdrawals of at least this much IPT must be committed with
+    /// commit_withdrawal and revealed after `reveal_delay_slots` (0 = never)
+    pub commit_reveal_threshold: u64,
+    pub reveal_delay_slots: u64,
+
+    /// Queued withdrawals of at least this much IPT are only paid once the
+    /// compliance authority approves them (0 = no approval needed)
+    pub approval_threshold: u64,
+    pub compliance_authority: Pubkey,
+    /// Unapproved requests drop out of the queue after this long (0 = never)
+    pub approval_expiry_secs: i64,
+
+    /// Once admin_withdraw_usdc calls in an epoch total this much USDC, each
+    /// further call must also be signed by `withdrawal_cosigner` (0 =
+    /// treasurer alone); both change only through set_withdrawal_cosigner
+    pub large_withdrawal_threshold: u64,
+    pub withdrawal_cosigner: Pubkey,
+
+    /// Most accumulated-fee USDC one buyback_ipt may spend (0 = no buybacks)
+    pub max_buyback_usdc: u64,
+
+    /// Anyone may sweep all accumulated fees to the fee collector's ATA once
+    /// they reach this amount (0 = no sweeping)
+    pub fee_sweep_threshold: u64,
+    /// Registered USDC token accounts paid their share of every sweep; the
+    /// fee collector keeps whatever the shares leave (empty = all to it)
+    #[max_len(4)]
+    pub fee_recipients: Vec<FeeRecipient>,
+
+    /// How long the old oracle key keeps publishing after a rotation is
+    /// accepted (0 = cut over immediately)
+    pub oracle_overlap_secs: i64,
+
+    /// Deposits between these timestamps get `promo_bonus_bps` extra IPT
+    /// and, if `promo_waives_deposit_fee`, pay no deposit fee; both are
+    /// paid from the pool's promotion budget while it lasts
+    pub promo_start_at: i64,
+    pub promo_end_at: i64,
+    pub promo_bonus_bps: u16,
+    pub promo_waives_deposit_fee: bool,
+
+    /// The first `early_supporter_slots` depositors to open a position pay
+    /// `early_supporter_discount_bps` less deposit fee for good (0 = off)
+    pub early_supporter_slots: u64,
+    pub early_supporter_discount_bps: u16,
+
+    /// Share of a referred deposit's fee accrued to the referrer's
+    /// claimable balance (0 = no referral rewards)
+    pub referral_fee_share_bps: u16,
+
+    /// Only addresses on the pool's allowlist may deposit
+    pub allowlist_only: bool,
+
+    /// Break-glass key that may replace the admin, oracle and fee collector
+    /// after RECOVERY_DELAY if the admin key is lost (default = disabled)
+    pub recovery_authority: Pubkey,
+
+    /// Positions idle this long may be flagged dormant and, under timelock,
+    /// swept to `escheat_custody` (0 = no escheatment)
+    pub dormancy_period_secs: i64,
+    /// IPT token account that receives escheated positions
+    pub escheat_custody: Pubkey,
+
+    /// Order claims are paid in once the pool winds down; each class must
+    /// appear once (empty = queued withdrawals, holders, then fees)
+    #[max_len(3)]
+    pub wind_down_waterfall: Vec<PayoutClass>,
+
+    /// Batch execution credits each user's payout escrow for them to pull
+    /// with claim_escrowed_payout, rather than paying their USDC account
+    pub pull_payouts: bool,
+
+    /// Key that may halt and resume batch queue processing and trigger
+    /// emergency_pause alongside the admin, e.g. a monitoring service
+    /// (default = admin only)
+    pub guardian_authority: Pubkey,
+
+    /// Queue depth and queued USDC at which QueueDepthAlert is raised so
+    /// treasury can replenish reserves (0 = no alert)
+    pub queue_alert_depth: u32,
+    pub queue_alert_usdc: u64,
+
+    /// Deposit cap and fees for each investor tier, indexed by
+    /// InvestorTier; access list entries assign the tier (empty = no tiers)
+    #[max_len(3)]
+    pub tier_terms: Vec<TierTerms>,
+
+    /// Withdrawal requests one user may queue per day, tracked on their
+    /// position so slots cannot be churned by queueing and cancelling
+    /// (0 = no limit)
+    pub max_queue_entries_per_day: u8,
+
+    /// How long after a deposit the admin may reverse it with
+    /// refund_deposit to fix a verified incident (0 = no refunds)
+    pub refund_window_secs: i64,
+
+    /// Price user flows at the rate first read in each slot, so a quote
+    /// simulated in a slot matches execution even if the oracle updates
+    /// later in that slot
+    pub slot_stable_quotes: bool,
+
+    /// Program whose interest-bearing token idle USDC is swept into with
+    /// sweep_idle_reserve and unwrapped from with unwrap_swept_reserve, and
+    /// the mint it issues (default = no sweeping)
+    pub sweep_wrapper_program: Pubkey,
+    pub sweep_wrapped_mint: Pubkey,
+    /// Unreserved USDC kept liquid for instant withdrawals and owed fees;
+    /// only the excess is swept
+    pub liquidity_buffer_usdc: u64,
+
+    /// How far a reserve asset's weight may drift from its target before
+    /// ReserveDriftExceeded is raised; deposits may fill up to target plus
+    /// this band (0 = target is a hard cap)
+    pub reserve_drift_tolerance_bps: u16,
+
+    /// Reject configs where the fee collector is also the admin
+    pub segregate_roles: bool,
+
+    /// Require user_withdraw and batch_execute_withdraw to be the only
+    /// non-compute-budget instruction in their transaction
+    pub isolated_exits: bool,
 }
 
 impl PoolConfig {
-    pub const LEN: usize = 32 + // admin_authority
-        32 + // oracle_authority
-        32 + // fee_collector
-        2 +  // deposit_fee_bps
-        2 +  // withdrawal_fee_bps
-        2 +  // management_fee_bps
-        8 +  // initial_exchange_rate
-        8 +  // max_total_supply
-        4; // max_queue_size
+    pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60; // 30 days
+    pub const MAX_EPOCH_DURATION: i64 = 365 * 24 * 60 * 60; // 1 year
+    pub const MAX_EXECUTION_DELAY: i64 = 7 * 24 * 60 * 60; // 7 days
+    pub const MAX_CANCELLATION_FEE_BPS: u16 = 500; // 5%
+    pub const MAX_REVEAL_DELAY_SLOTS: u64 = 9_000; // ~1 hour
+    pub const MAX_APPROVAL_EXPIRY: i64 = 30 * 24 * 60 * 60; // 30 days
+    pub const MAX_ORACLE_OVERLAP: i64 = 7 * 24 * 60 * 60; // 7 days
+    pub const MAX_FEE_RECIPIENTS: usize = 4;
+    pub const RECOVERY_DELAY: i64 = 14 * 24 * 60 * 60; // 14 days
+    pub const MAX_PROMO_BONUS_BPS: u16 = 1_000; // 10%
+    pub const MAX_COMBINED_FEE_BPS: u16 = 2_000; // 20%
+    pub const MAX_REFUND_WINDOW: i64 = 7 * 24 * 60 * 60; // 7 days
+
+    /// Cancellation fee owed on `ipt_amount` for a request made at
+    /// `requested_at`, as of `now`
+    pub fn cancellation_fee(&self, ipt_amount: u64, requested_at: i64, now: i64) -> Result<u64> {
+        let waived = self.cancellation_grace_secs > 0
+            && now.saturating_sub(requested_at) >= self.cancellation_grace_secs;
+        if waived {
+            return Ok(0);
+        }
+        CalculationUtils::calculate_fee(ipt_amount, self.cancellation_fee_bps)
+    }
+
+    /// Whether a withdrawal of `net_ipt_amount` must go through commit-reveal
+    pub fn requires_commitment(&self, net_ipt_amount: u64) -> bool {
+        self.commit_reveal_threshold > 0 && net_ipt_amount >= self.commit_reveal_threshold
+    }
+
+    /// The wind-down payout order in effect
+    pub fn wind_down_order(&self) -> Vec<PayoutClass> {
+        if self.wind_down_waterfall.is_empty() {
+            PayoutClass::DEFAULT_ORDER.to_vec()
+        } else {
+            self.wind_down_waterfall.clone()
+        }
+    }
+
+    /// Whether a withdrawal of `net_ipt_amount` needs compliance approval
+    pub fn requires_approval(&self, net_ipt_amount: u64) -> bool {
+        self.approval_threshold > 0 && net_ipt_amount >= self.approval_threshold
+    }
+
+    /// Deposit fee rate charged to the depositor with `ordinal` (0 = none yet)
+    pub fn deposit_fee_bps_for(&self, ordinal: u64) -> u16 {
+        if ordinal > 0 && ordinal <= self.early_supporter_slots {
+            self.deposit_fee_bps
+                .saturating_sub(self.early_supporter_discount_bps)
+        } else {
+            self.deposit_fee_bps
+        }
+    }
+
+    /// Terms for `tier`, if the pool has investor tiers
+    pub fn terms_for(&self, tier: InvestorTier) -> Option<&TierTerms> {
+        self.tier_terms.get(tier as usize)
+    }
+
+    /// Deposit fee rate charged to a `tier` depositor with `ordinal`; the
+    /// early-supporter discount applies on top of the tier's fee
+    pub fn tier_deposit_fee_bps(&self, tier: InvestorTier, ordinal: u64) -> u16 {
+        let discount = self.deposit_fee_bps - self.deposit_fee_bps_for(ordinal);
+        match self.terms_for(tier) {
+            Some(terms) => terms.deposit_fee_bps.saturating_sub(discount),
+            None => self.deposit_fee_bps - discount,
+        }
+    }
+
+    /// Withdrawal fee rate charged to a `tier` holder
+    pub fn withdrawal_fee_bps_for(&self, tier: InvestorTier) -> u16 {
+        self.terms_for(tier)
+            .map_or(self.withdrawal_fee_bps, |terms| terms.withdrawal_fee_bps)
+    }
+
+    /// Whether any setting holds withdrawals in the queue regardless of
+    /// reserves (review window, compliance approval or redemption gate)
+    pub fn queues_withdrawals(&self) -> bool {
+        self.min_execution_delay_secs > 0
+            || self.approval_threshold > 0
+            || self.max_redemption_bps_per_epoch > 0
+    }
+
+    /// Whether a deposit promotion is running at `now`
+    pub fn promotion_active(&self, now: i64) -> bool {
+        self.promo_start_at <= now && now < self.promo_end_at
+    }
+
+    /// Each fee recipient's share of a sweep of `amount`, in
+    /// `fee_recipients` order
+    pub fn fee_split(&self, amount: u64) -> Result<Vec<u64>> {
+        self.fee_recipients
+            .iter()
+            .map(|recipient| CalculationUtils::calculate_fee(amount, recipient.share_bps))
+            .collect()
+    }
+
+    /// Whether an admin withdrawal bringing the epoch's total to
+    /// `epoch_total` USDC needs the co-signer
+    pub fn requires_cosigner(&self, epoch_total: u64) -> bool {
+        self.large_withdrawal_threshold > 0 && epoch_total >= self.large_withdrawal_threshold
+    }
+
+    /// Whether `key` may pause or resume batch queue processing
+    pub fn can_pause_queue(&self, key: &Pubkey) -> bool {
+        *key == self.admin_authority
+            || (self.guardian_authority != Pubkey::default() && *key == self.guardian_authority)
+    }
+
+    /// Whether `key` may halt every operation with emergency_pause; only the
+    /// admin can lift it again
+    pub fn can_emergency_pause(&self, key: &Pubkey) -> bool {
+        self.can_pause_queue(key)
+    }
+
+    /// Whether idle reserve USDC is swept into a wrapper token
+    pub fn sweeps_idle_reserve(&self) -> bool {
+        self.sweep_wrapper_program != Pubkey::default()
+    }
+
+    /// Whether `rate` lies within the configured floor and ceiling
+    pub fn rate_in_bounds(&self, rate: u64) -> bool {
+        rate >= self.rate_floor && (self.rate_ceiling == 0 || rate <= self.rate_ceiling)
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+/// Pool lifecycle; day-to-day pausing uses `pause_flags`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 pub enum PoolState {
     Active,
-    Paused,
+    /// Withdrawals only, entered when the dead-man switch trips or the
+    /// admin freezes the pool
     Frozen,
-    DepositOnly,
-    WithdrawOnly,
+    /// User flows halted while balances are recreated with admin_mint_ipt
+    Migration,
+    /// Closing: claims are paid class by class in the wind-down waterfall
+    WindDown,
+}
+
+impl PoolState {
+    /// Whether set_pool_state may move a pool from `self` to `to`; migration
+    /// and wind-down carry their own bookkeeping and keep their dedicated
+    /// instructions
+    pub fn admin_can_set(self, to: PoolState) -> bool {
+        matches!(
+            (self, to),
+            (PoolState::Active, PoolState::Frozen) | (PoolState::Frozen, PoolState::Active)
+        )
+    }
+}
+
+/// Investor classification assigned on the access list; with tiers
+/// configured, addresses without an entry are Retail
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub enum InvestorTier {
+    #[default]
+    Retail,
+    Professional,
+    Institutional,
+}
+
+impl InvestorTier {
+    pub const ALL: [InvestorTier; 3] = [
+        InvestorTier::Retail,
+        InvestorTier::Professional,
+        InvestorTier::Institutional,
+    ];
+}
+
+/// Deposit cap and fee schedule for one investor tier
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct TierTerms {
+    /// Most USDC one depositor may have deposited and not withdrawn, tracked
+    /// on their position (0 = uncapped)
+    pub max_deposit_usdc: u64,
+    pub deposit_fee_bps: u16,
+    pub withdrawal_fee_bps: u16,
+}
+
+/// Claim class in the wind-down payout waterfall
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PayoutClass {
+    /// Withdrawals already waiting in the queue, paid by the queue crank
+    QueuedWithdrawals,
+    /// Remaining IPT holders, who redeem with user_withdraw
+    Holders,
+    /// Accumulated fees and referral earnings
+    Fees,
+}
+
+impl PayoutClass {
+    pub const DEFAULT_ORDER: [PayoutClass; 3] = [
+        PayoutClass::QueuedWithdrawals,
+        PayoutClass::Holders,
+        PayoutClass::Fees,
+    ];
+}
+
+/// Operations that can be paused independently of each other
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PauseFlag {
+    Deposits,
+    Withdrawals,
+    /// Batch queue processing only; requests and cancellations stay open
+    Queue,
+    RateUpdates,
+}
+
+impl PauseFlag {
+    /// Every defined flag set
+    pub const ALL: u8 = 0b1111;
+
+    pub fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Subsystems the protocol admin can switch off for every pool without a
+/// redeploy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// deposit_reserve_asset
+    ReserveAssetDeposits,
+    /// withdraw_reserve_asset
+    InKindRedemptions,
+    /// Referral fee accrual on user_deposit
+    ReferralRewards,
+    /// Batch payouts into payout escrows
+    PayoutEscrows,
+}
+
+impl Feature {
+    /// Every defined feature bit
+    pub const ALL: u32 = 0b1111;
+
+    /// Features that start switched off on a new protocol config
+    pub const SHIPPED_DARK: u32 = (1 << Feature::ReserveAssetDeposits as u32)
+        | (1 << Feature::InKindRedemptions as u32);
+
+    pub fn mask(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// Fee stream accumulated fees are booked to and withdrawn from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeBucket {
+    Deposit,
+    /// Withdrawal fees, including early cancellation fees
+    Withdrawal,
+    Management,
+}
+
+/// A party paid a fixed share of swept fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct FeeRecipient {
+    /// USDC token account the share is sent to
+    pub token_account: Pubkey,
+    pub share_bps: u16,
+}
+
+/// One pool's share of a deposit split by route_deposit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RouteLeg {
+    /// Share of the routed USDC sent to this pool (bps)
+    pub weight_bps: u16,
+    /// Slippage floor for the IPT this leg mints
+    pub min_ipt_amount: u64,
+}
+
+impl RouteLeg {
+    /// Most pools one routed deposit may split across
+    pub const MAX_LEGS: usize = 4;
+}
+
+/// Why the treasurer moved USDC in or out of the reserve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReserveMovementReason {
+    /// Topping up or trimming the liquid buffer
+    Rebalance,
+    /// Funds sent to or returned from the off-chain strategy
+    StrategyDeployment,
+    /// Making users whole after an error
+    Refund,
+    /// Incident response
+    Emergency,
+    /// Funding or reclaiming the deposit promotion budget
+    Promotion,
+    /// Earmarking liquidity for queued withdrawals, or releasing it
+    QueueFunding,
+}
+
+/// User flow turned away by a soft business check
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RejectedOperation {
+    Deposit,
+    Withdrawal,
+}
+
+/// Why a user flow was turned away
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RejectionReason {
+    /// Protocol-wide emergency pause
+    ProtocolPaused,
+    /// Pool frozen, migrating, or the operation paused
+    OperationDisabled,
+    /// Oracle missed its heartbeat
+    OracleStale,
+    /// Deposit would exceed max_total_supply
+    SupplyCapReached,
+    /// Deposit would take NAV past the TVL cap
+    TvlCapReached,
+    /// Withdrawal queue has no free slot
+    QueueFull,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -126,9 +1279,953 @@ pub struct LockState {
    pub is_locked: bool
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct PendingWithdraw {
     pub user: Pubkey,
     pub amount: u64,
     pub min_usdc_amount: u64,
-}
\ No newline at end of file
+    /// Queue-pressure exit adjustment locked in when the request was queued
+    pub exit_adjustment_bps: u16,
+    pub requested_at: i64,
+    /// Batch execution leaves the request queued until this time
+    pub earliest_execution: i64,
+    /// Cleared for payout by the compliance authority (or below the threshold)
+    pub approved: bool,
+    /// Requester's investor tier, which sets the withdrawal fee
+    pub tier: InvestorTier,
+    /// Consecutive batch skips for `last_skip_reason`
+    pub skip_count: u8,
+    pub last_skip_reason: Option<SkipReason>,
+}
+
+impl PendingWithdraw {
+    /// Count a skip; returns true once `max_attempts` consecutive skips share
+    /// the same reason
+    pub fn record_skip(&mut self, reason: SkipReason, max_attempts: u8) -> bool {
+        self.skip_count = if self.last_skip_reason == Some(reason) {
+            self.skip_count.saturating_add(1)
+        } else {
+            1
+        };
+        self.last_skip_reason = Some(reason);
+        self.skip_count >= max_attempts
+    }
+
+    /// Whether an unapproved request has waited past `expiry_secs` as of `now`
+    pub fn approval_expired(&self, expiry_secs: i64, now: i64) -> bool {
+        !self.approved && expiry_secs > 0 && now.saturating_sub(self.requested_at) >= expiry_secs
+    }
+}
+
+/// Why batch execution passed over a queued withdrawal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum SkipReason {
+    InsufficientBalance,
+    SlippageExceeded,
+    DelegationMissing,
+}
+
+impl SkipReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkipReason::InsufficientBalance => "Insufficient IPT balance",
+            SkipReason::SlippageExceeded => "Slippage protection exceeded",
+            SkipReason::DelegationMissing => "IPT not delegated to pool",
+        }
+    }
+}
+
+/// USDC payout to holders, entitlements committed as a merkle root over a balance snapshot
+#[account]
+pub struct Distribution {
+    /// Pool the distribution belongs to
+    pub pool: Pubkey,
+    /// Sequential id within the pool
+    pub id: u64,
+    /// What holders receive when claiming
+    pub kind: DistributionKind,
+    /// Vault holding the USDC still to be claimed (unused for rebases)
+    pub payout_vault: Pubkey,
+    /// Root over (distribution, holder, amount) leaves
+    pub merkle_root: [u8; 32],
+    /// Slot the holder balances were snapshotted at
+    pub snapshot_slot: u64,
+    /// USDC funded and USDC claimed so far
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub created_at: i64,
+    /// Paid the account rent; refunded when the distribution is closed
+    pub payer: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Distribution {
+    pub const SEED_PREFIX: &'static [u8] = b"distribution";
+    pub const VAULT_SEED_PREFIX: &'static [u8] = b"distribution_vault";
+
+    // Unclaimed entitlements may be swept back to the pool after this long
+    pub const CLAIM_WINDOW: i64 = 365 * 24 * 60 * 60; // 1 year
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        8 +  // id
+        1 +  // kind
+        32 + // payout_vault
+        32 + // merkle_root
+        8 +  // snapshot_slot
+        8 +  // total_amount
+        8 +  // claimed_amount
+        8 +  // created_at
+        32 + // payer
+        1;   // bump
+
+    /// Whether the distribution may be closed as of `now`: fully claimed,
+    /// or past its claim window
+    pub fn closable(&self, now: i64) -> bool {
+        self.claimed_amount >= self.total_amount
+            || now.saturating_sub(self.created_at) >= Self::CLAIM_WINDOW
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DistributionKind {
+    /// USDC paid from the payout vault
+    Usdc,
+    /// IPT minted to holders by a rebase
+    IptRebase,
+}
+
+/// A month's holder statements committed as a merkle root; written once and
+/// never changed, so it stays a fixed reference for holders and auditors
+#[account]
+pub struct StatementCommitment {
+    pub pool: Pubkey,
+    /// Statement month as YYYYMM
+    pub period: u32,
+    /// Root over (statement, holder, balance, yield) leaves
+    pub merkle_root: [u8; 32],
+    pub holder_count: u32,
+    /// IPT held across all statements at period end
+    pub total_balance: u64,
+    /// Admin or oracle that committed the statement
+    pub committed_by: Pubkey,
+    pub committed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl StatementCommitment {
+    pub const SEED_PREFIX: &'static [u8] = b"statement";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        4 +  // period
+        32 + // merkle_root
+        4 +  // holder_count
+        8 +  // total_balance
+        32 + // committed_by
+        8 +  // committed_at
+        1;   // bump
+
+    /// Whether `period` is a YYYYMM month
+    pub fn is_valid_period(period: u32) -> bool {
+        (1..=12).contains(&(period % 100)) && period / 100 >= 2000
+    }
+}
+
+/// The pool's share register (holders and balances) at a cutoff slot,
+/// committed by hash for transfer-agent records; written once per cutoff
+#[account]
+pub struct ShareRegisterCommitment {
+    pub pool: Pubkey,
+    /// Slot the register was taken at
+    pub cutoff_slot: u64,
+    /// Hash of the exported register
+    pub register_hash: [u8; 32],
+    pub holder_count: u32,
+    /// IPT held across all holders at the cutoff
+    pub total_balance: u64,
+    pub committed_by: Pubkey,
+    pub committed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ShareRegisterCommitment {
+    pub const SEED_PREFIX: &'static [u8] = b"share_register";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        8 +  // cutoff_slot
+        32 + // register_hash
+        4 +  // holder_count
+        8 +  // total_balance
+        32 + // committed_by
+        8 +  // committed_at
+        1;   // bump
+}
+
+/// A non-USDC token the pool holds in reserve (e.g. a tokenized T-bill),
+/// priced in USDC by the oracle
+#[account]
+pub struct ReserveAsset {
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    /// Token account holding the asset, owned by the pool authority
+    pub vault: Pubkey,
+    pub decimals: u8,
+    /// USDC per whole asset token, scaled like the exchange rate
+    pub price: u64,
+    pub price_updated_at: i64,
+    /// Asset balance held in the vault
+    pub balance: u64,
+    /// USDC value of `balance` at `price`, as counted in the pool aggregate
+    pub value: u64,
+    /// Share of total reserve value the asset should make up (bps);
+    /// deposits past it plus the pool's drift tolerance are refused
+    pub target_weight_bps: u16,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ReserveAsset {
+    pub const SEED_PREFIX: &'static [u8] = b"reserve_asset";
+    pub const VAULT_SEED_PREFIX: &'static [u8] = b"reserve_vault";
+
+    /// Reserve assets a pool may register besides USDC
+    pub const MAX_ASSETS: u8 = 4;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // mint
+        32 + // vault
+        1 +  // decimals
+        8 +  // price
+        8 +  // price_updated_at
+        8 +  // balance
+        8 +  // value
+        2 +  // target_weight_bps
+        1;   // bump
+
+    /// Whether the price is too old to trade against; shares the pool's
+    /// rate heartbeat
+    pub fn is_price_stale(&self, max_age_secs: i64, now: i64) -> bool {
+        self.price == 0
+            || (max_age_secs > 0 && now.saturating_sub(self.price_updated_at) > max_age_secs)
+    }
+
+    /// Share of `total_value` that `value` of this asset makes up, in bps
+    pub fn weight_bps(value: u64, total_value: u64) -> u16 {
+        if total_value == 0 {
+            return 0;
+        }
+        ((value as u128) * 10_000 / total_value as u128).min(10_000) as u16
+    }
+
+    /// Whether holding `value` of this asset out of `total_value` of
+    /// reserves stays within the target weight plus `tolerance_bps`
+    pub fn within_target(&self, value: u64, total_value: u64, tolerance_bps: u16) -> bool {
+        let cap = (self.target_weight_bps as u128 + tolerance_bps as u128).min(10_000);
+        (value as u128) * 10_000 <= (total_value as u128) * cap
+    }
+
+    /// Current weight if it sits more than `tolerance_bps` off target
+    pub fn drift(&self, total_value: u64, tolerance_bps: u16) -> Option<u16> {
+        let weight = Self::weight_bps(self.value, total_value);
+        (weight.abs_diff(self.target_weight_bps) > tolerance_bps).then_some(weight)
+    }
+}
+
+/// Marks a holder's dividend as claimed; its existence blocks a second claim
+#[account]
+pub struct ClaimReceipt {
+    pub distribution: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ClaimReceipt {
+    pub const SEED_PREFIX: &'static [u8] = b"claim";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // distribution
+        32 + // claimant
+        8 +  // amount
+        8 +  // claimed_at
+        1;   // bump
+}
+
+/// Admin action scheduled behind the pool timelock
+#[account]
+pub struct TimelockOperation {
+    /// Pool the action applies to
+    pub pool: Pubkey,
+    /// Sequential id within the pool
+    pub id: u64,
+    /// Action to execute once the delay has passed
+    pub action: AdminAction,
+    /// Timestamps
+    pub scheduled_at: i64,
+    pub executable_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TimelockOperation {
+    pub const SEED_PREFIX: &'static [u8] = b"timelock";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        8 +  // id
+        AdminAction::MAX_LEN + // action
+        8 +  // scheduled_at
+        8 +  // executable_at
+        1;   // bump
+}
+
+/// Config roles the admin hands over through a timelocked SetRoleAuthority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AuthorityRole {
+    /// `recovery_authority`
+    Recovery,
+    /// `guardian_authority`
+    Guardian,
+    /// `compliance_authority`
+    Compliance,
+}
+
+impl AuthorityRole {
+    /// The config field holding this role's key
+    pub fn authority_mut(self, config: &mut PoolConfig) -> &mut Pubkey {
+        match self {
+            AuthorityRole::Recovery => &mut config.recovery_authority,
+            AuthorityRole::Guardian => &mut config.guardian_authority,
+            AuthorityRole::Compliance => &mut config.compliance_authority,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum AdminAction {
+    /// Mint IPT to a migrated user (pool must be in Migration state)
+    MintIpt {
+        beneficiary: Pubkey,
+        amount: u64,
+        reason: String,
+    },
+    /// Hand the IPT mint authority to a successor program's pool PDA
+    TransferMintAuthority { successor_program: Pubkey },
+    /// Change the USDC-denominated TVL cap (0 = unlimited)
+    SetTvlCap { tvl_cap: u64 },
+    /// Move a dormant holder's IPT to `custody`, the escheatment custody
+    /// account when the action was scheduled
+    EscheatPosition {
+        owner: Pubkey,
+        amount: u64,
+        custody: Pubkey,
+    },
+    /// Hand the treasurer role to a new key; the current treasurer co-signs
+    SetTreasurer { treasurer: Pubkey },
+    /// Change the admin withdrawal co-signer and threshold; the current
+    /// co-signer, if any, co-signs
+    SetWithdrawalCosigner { cosigner: Pubkey, threshold: u64 },
+    /// Change the token account escheated positions are moved to
+    SetEscheatCustody { custody: Pubkey },
+    /// Hand a config role to a new key (default = role disabled)
+    SetRoleAuthority { role: AuthorityRole, authority: Pubkey },
+    /// Change the wrapper idle USDC is swept into (defaults = no sweeping);
+    /// executes only once no swept USDC is outstanding
+    SetSweepWrapper { program: Pubkey, mint: Pubkey },
+}
+
+impl AdminAction {
+    pub const MAX_REASON_LEN: usize = 64;
+
+    pub const MAX_LEN: usize = 1 + // variant
+        32 + // beneficiary
+        8 +  // amount
+        4 + Self::MAX_REASON_LEN; // reason
+}
+
+/// One day of a pool's rate history in open/high/low/close form, written by
+/// update_exchange_rate
+#[account]
+pub struct NavSummary {
+    pub pool: Pubkey,
+    /// Days since the Unix epoch
+    pub period: u64,
+    /// Rate in force when the day's first update arrived
+    pub open_rate: u64,
+    pub high_rate: u64,
+    pub low_rate: u64,
+    pub close_rate: u64,
+    /// NAV after the day's last update, in USDC
+    pub close_nav: u64,
+    /// Rate updates recorded this day
+    pub updates: u32,
+    pub first_update_at: i64,
+    pub last_update_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl NavSummary {
+    pub const SEED_PREFIX: &'static [u8] = b"nav_summary";
+    pub const PERIOD_SECS: i64 = 24 * 60 * 60;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        8 +  // period
+        8 +  // open_rate
+        8 +  // high_rate
+        8 +  // low_rate
+        8 +  // close_rate
+        8 +  // close_nav
+        4 +  // updates
+        8 +  // first_update_at
+        8 +  // last_update_at
+        1;   // bump
+
+    /// Period `now` falls in
+    pub fn period_at(now: i64) -> u64 {
+        now.div_euclid(Self::PERIOD_SECS) as u64
+    }
+
+    /// Fold a rate change from `old_rate` to `new_rate` into the day
+    pub fn record(&mut self, old_rate: u64, new_rate: u64, nav: u64, now: i64) -> Result<()> {
+        if self.updates == 0 {
+            self.open_rate = old_rate;
+            self.high_rate = old_rate;
+            self.low_rate = old_rate;
+            self.first_update_at = now;
+        }
+        self.high_rate = self.high_rate.max(new_rate);
+        self.low_rate = self.low_rate.min(new_rate);
+        self.close_rate = new_rate;
+        self.close_nav = nav;
+        self.updates = self
+            .updates
+            .checked_add(1)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        self.last_update_at = now;
+        Ok(())
+    }
+}
+
+/// Supporting documentation for a posted rate: the NAV report's hash and
+/// where to fetch it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RateEvidence {
+    pub report_hash: [u8; 32],
+    pub uri: String,
+}
+
+impl RateEvidence {
+    pub const MAX_URI_LEN: usize = 200;
+}
+
+/// Hash of a large withdrawal's terms, revealed after a slot delay
+#[account]
+pub struct WithdrawalCommitment {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    /// hash(pool, user, net_ipt_amount, min_usdc_amount, salt)
+    pub commitment: [u8; 32],
+    /// Slot the commitment was made in
+    pub committed_slot: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl WithdrawalCommitment {
+    pub const SEED_PREFIX: &'static [u8] = b"withdrawal_commitment";
+
+    // Unrevealed commitments may be closed by anyone after this long
+    pub const ABANDONED_AFTER_SLOTS: u64 = 6_480_000; // ~30 days
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // user
+        32 + // commitment
+        8 +  // committed_slot
+        1;   // bump
+
+    /// Commitment over the withdrawal terms; the salt keeps the amount hidden
+    pub fn digest(
+        pool: &Pubkey,
+        user: &Pubkey,
+        net_ipt_amount: u64,
+        min_usdc_amount: u64,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        hashv(&[
+            pool.as_ref(),
+            user.as_ref(),
+            &net_ipt_amount.to_le_bytes(),
+            &min_usdc_amount.to_le_bytes(),
+            salt,
+        ])
+        .to_bytes()
+    }
+}
+
+/// Curation entry describing a pool for discovery, maintained by the
+/// protocol admin
+#[account]
+pub struct PoolRegistryEntry {
+    pub pool: Pubkey,
+    pub category: PoolCategory,
+    pub risk_tier: RiskTier,
+    /// Free-form discovery tags (bitmask, meanings published off-chain)
+    pub tags: u32,
+    /// Reviewed and endorsed by the protocol admin
+    pub verified: bool,
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PoolRegistryEntry {
+    pub const SEED_PREFIX: &'static [u8] = b"registry";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        1 +  // category
+        1 +  // risk_tier
+        4 +  // tags
+        1 +  // verified
+        8 +  // updated_at
+        1;   // bump
+}
+
+/// What a pool invests in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PoolCategory {
+    TreasuryBills,
+    MoneyMarket,
+    PrivateCredit,
+    RealEstate,
+    Other,
+}
+
+/// Risk rating shown alongside a pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RiskTier {
+    Low,
+    Medium,
+    High,
+}
+
+/// A depositor's standing in a pool, created on their first deposit
+#[account]
+pub struct Position {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    /// Order of the owner's first deposit among the pool's depositors (from 1)
+    pub depositor_ordinal: u64,
+    pub first_deposit_at: i64,
+    /// Last deposit or activity confirmation by the owner
+    pub last_activity_at: i64,
+    /// When the position was flagged dormant (0 = not flagged)
+    pub dormant_flagged_at: i64,
+    /// IPT deposited through the position and not yet withdrawn through it
+    pub tracked_ipt: u64,
+    /// Exchange rate averaged over tracked_ipt, weighted by IPT minted
+    pub avg_entry_rate: u64,
+    /// USDC deposited for tracked_ipt, counted against the owner's tier
+    /// deposit cap; withdrawals release it pro rata
+    pub deposited_usdc: u64,
+    /// Certificate number of the owner's latest deposit (0 = none)
+    pub last_certificate_number: u64,
+    /// Day of the owner's latest queued withdrawal request, in
+    /// QUEUE_DAY_SECS since the epoch
+    pub queue_day: u64,
+    /// Withdrawal requests the owner has queued on `queue_day`
+    pub queue_entries_today: u8,
+    /// Owner's latest deposit, kept so refund_deposit can reverse it:
+    /// when it was made, IPT minted, USDC paid and fee credited to the pool
+    pub last_deposit_at: i64,
+    pub last_deposit_ipt: u64,
+    pub last_deposit_usdc: u64,
+    pub last_deposit_fee: u64,
+    /// Net USDC the deposit was sized at, before fees
+    pub last_deposit_net_usdc: u64,
+    /// Referrer credited a share of the fee, and that share (default = none)
+    pub last_deposit_referrer: Pubkey,
+    pub last_deposit_referral_fee: u64,
+    /// Partner account the deposit was attributed to (default = none)
+    pub last_deposit_partner: Pubkey,
+    /// Promotion budget the deposit spent on a waived fee and bonus IPT
+    pub last_deposit_promo_usdc: u64,
+    /// Whether the deposit opened a lot in the owner's cost-basis ledger
+    pub last_deposit_lot: bool,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SEED_PREFIX: &'static [u8] = b"position";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // owner
+        8 +  // depositor_ordinal
+        8 +  // first_deposit_at
+        8 +  // last_activity_at
+        8 +  // dormant_flagged_at
+        8 +  // tracked_ipt
+        8 +  // avg_entry_rate
+        8 +  // deposited_usdc
+        8 +  // last_certificate_number
+        8 +  // queue_day
+        1 +  // queue_entries_today
+        8 +  // last_deposit_at
+        8 +  // last_deposit_ipt
+        8 +  // last_deposit_usdc
+        8 +  // last_deposit_fee
+        8 +  // last_deposit_net_usdc
+        32 + // last_deposit_referrer
+        8 +  // last_deposit_referral_fee
+        32 + // last_deposit_partner
+        8 +  // last_deposit_promo_usdc
+        1 +  // last_deposit_lot
+        1;   // bump
+
+    /// Length of the window the daily queue entry limit counts over
+    pub const QUEUE_DAY_SECS: i64 = 86_400;
+
+    /// Whether the owner has been inactive for `period_secs` as of `now`
+    pub fn is_dormant(&self, period_secs: i64, now: i64) -> bool {
+        period_secs > 0 && now.saturating_sub(self.last_activity_at) >= period_secs
+    }
+
+    /// Set up a position created by `owner`'s first deposit into the pool
+    /// at `pool_key`, numbering it in deposit order; an existing position
+    /// is left as is
+    pub fn open(
+        &mut self,
+        pool: &mut Pool,
+        pool_key: Pubkey,
+        owner: Pubkey,
+        now: i64,
+        bump: u8,
+    ) -> Result<()> {
+        if self.depositor_ordinal == 0 {
+            pool.depositor_count = pool
+                .depositor_count
+                .checked_add(1)
+                .ok_or(PoolError::MathematicalOverflow)?;
+            self.pool = pool_key;
+            self.owner = owner;
+            self.depositor_ordinal = pool.depositor_count;
+            self.first_deposit_at = now;
+            self.bump = bump;
+        }
+        Ok(())
+    }
+
+    /// Record owner activity, lifting any dormancy flag
+    pub fn touch(&mut self, now: i64) {
+        self.last_activity_at = now;
+        self.dormant_flagged_at = 0;
+    }
+
+    /// Fold `ipt_amount` minted at `rate` for `usdc_amount` into the
+    /// average entry rate and the deposited total
+    pub fn record_entry(&mut self, ipt_amount: u64, rate: u64, usdc_amount: u64) -> Result<()> {
+        self.deposited_usdc = self
+            .deposited_usdc
+            .checked_add(usdc_amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        let tracked_ipt = self
+            .tracked_ipt
+            .checked_add(ipt_amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        if tracked_ipt == 0 {
+            return Ok(());
+        }
+        let weighted = self.tracked_ipt as u128 * self.avg_entry_rate as u128
+            + ipt_amount as u128 * rate as u128;
+        self.avg_entry_rate = (weighted / tracked_ipt as u128) as u64;
+        self.tracked_ipt = tracked_ipt;
+        Ok(())
+    }
+
+    /// Whether the latest deposit can still be refunded at `now` under a
+    /// `window_secs` refund window
+    pub fn deposit_refundable(&self, window_secs: i64, now: i64) -> bool {
+        window_secs > 0
+            && self.last_deposit_ipt > 0
+            && now.saturating_sub(self.last_deposit_at) <= window_secs
+    }
+
+    /// Count a queued withdrawal request at `now` against the owner's daily
+    /// `limit` (0 = no limit)
+    pub fn record_queue_entry(&mut self, now: i64, limit: u8) -> Result<()> {
+        let day = now.div_euclid(Self::QUEUE_DAY_SECS) as u64;
+        if day != self.queue_day {
+            self.queue_day = day;
+            self.queue_entries_today = 0;
+        }
+        require!(
+            limit == 0 || self.queue_entries_today < limit,
+            PoolError::QueueEntryLimitReached
+        );
+        self.queue_entries_today = self.queue_entries_today.saturating_add(1);
+        Ok(())
+    }
+
+    /// Release `ipt_amount` withdrawn from the tracked holding, with its
+    /// share of the deposited total; the average entry rate of what remains
+    /// is unchanged
+    pub fn record_exit(&mut self, ipt_amount: u64) {
+        let exited = ipt_amount.min(self.tracked_ipt);
+        if exited > 0 {
+            let released =
+                self.deposited_usdc as u128 * exited as u128 / self.tracked_ipt as u128;
+            self.deposited_usdc -= released as u64;
+        }
+        self.tracked_ipt -= exited;
+        if self.tracked_ipt == 0 {
+            self.avg_entry_rate = 0;
+        }
+    }
+
+    /// Undo a refunded deposit of `ipt_amount` that added `usdc_amount` to
+    /// the deposited total
+    pub fn reverse_entry(&mut self, ipt_amount: u64, usdc_amount: u64) {
+        self.deposited_usdc = self.deposited_usdc.saturating_sub(usdc_amount);
+        self.tracked_ipt = self.tracked_ipt.saturating_sub(ipt_amount);
+        if self.tracked_ipt == 0 {
+            self.avg_entry_rate = 0;
+            self.deposited_usdc = 0;
+        }
+    }
+}
+
+/// Order in which a withdrawal draws down a holder's cost-basis lots
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LotMethod {
+    /// Oldest lot first
+    Fifo,
+    /// Newest lot first
+    Lifo,
+}
+
+/// IPT acquired by one deposit and what it cost
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CostBasisLot {
+    pub ipt_amount: u64,
+    /// USDC paid for the lot, including the deposit fee
+    pub cost_usdc: u64,
+    /// Exchange rate the lot was minted at
+    pub exchange_rate: u64,
+    pub acquired_at: i64,
+}
+
+impl CostBasisLot {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+}
+
+/// Part of a lot disposed of by a withdrawal, reported in
+/// UserWithdrawalExecuted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RealizedLot {
+    pub acquired_at: i64,
+    pub ipt_amount: u64,
+    pub cost_usdc: u64,
+    pub proceeds_usdc: u64,
+    /// proceeds_usdc - cost_usdc
+    pub realized_gain: i64,
+}
+
+/// A holder's open deposit lots in a pool, kept for tax reporting; deposits
+/// that pass it add a lot and direct withdrawals consume them
+#[account]
+pub struct CostBasisLedger {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub method: LotMethod,
+    /// Open lots, oldest first
+    pub lots: Vec<CostBasisLot>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl CostBasisLedger {
+    pub const SEED_PREFIX: &'static [u8] = b"cost_basis";
+
+    pub const MAX_LOTS: usize = 32;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // owner
+        1 +  // method
+        4 + Self::MAX_LOTS * CostBasisLot::LEN + // lots
+        1;   // bump
+
+    /// Open a lot for a deposit
+    pub fn record_lot(&mut self, lot: CostBasisLot) -> Result<()> {
+        require!(
+            self.lots.len() < Self::MAX_LOTS,
+            PoolError::CostBasisLedgerFull
+        );
+        self.lots.push(lot);
+        Ok(())
+    }
+
+    /// Drop what is left of the newest lot acquired at `acquired_at`, for a
+    /// refunded deposit; IPT already disposed of stays reported
+    pub fn reverse_lot(&mut self, acquired_at: i64) {
+        if let Some(index) = self
+            .lots
+            .iter()
+            .rposition(|lot| lot.acquired_at == acquired_at)
+        {
+            self.lots.remove(index);
+        }
+    }
+
+    /// Consume `ipt_amount` of lots in the ledger's order for a withdrawal
+    /// paying `proceeds_usdc`, splitting the proceeds across lots by IPT.
+    /// IPT beyond the open lots (e.g. received by transfer) has no recorded
+    /// basis and is not reported.
+    pub fn dispose(&mut self, ipt_amount: u64, proceeds_usdc: u64) -> Result<Vec<RealizedLot>> {
+        let mut realized = Vec::new();
+        let mut remaining = ipt_amount;
+        let mut proceeds_left = proceeds_usdc;
+        while remaining > 0 && !self.lots.is_empty() {
+            let index = match self.method {
+                LotMethod::Fifo => 0,
+                LotMethod::Lifo => self.lots.len() - 1,
+            };
+            let lot = &mut self.lots[index];
+            let taken = remaining.min(lot.ipt_amount);
+
+            let cost_usdc = (lot.cost_usdc as u128 * taken as u128 / lot.ipt_amount as u128) as u64;
+            let proceeds = if taken == remaining {
+                proceeds_left
+            } else {
+                (proceeds_usdc as u128 * taken as u128 / ipt_amount as u128) as u64
+            };
+            let realized_gain = i64::try_from(proceeds as i128 - cost_usdc as i128)
+                .map_err(|_| PoolError::MathematicalOverflow)?;
+            realized.push(RealizedLot {
+                acquired_at: lot.acquired_at,
+                ipt_amount: taken,
+                cost_usdc,
+                proceeds_usdc: proceeds,
+                realized_gain,
+            });
+
+            lot.ipt_amount -= taken;
+            lot.cost_usdc -= cost_usdc;
+            if lot.ipt_amount == 0 {
+                self.lots.remove(index);
+            }
+            remaining -= taken;
+            proceeds_left -= proceeds;
+        }
+        Ok(realized)
+    }
+}
+
+/// A referrer's record in a pool; deposits naming it accrue a share of their
+/// fee here until claim_referral_earnings pays it out
+#[account]
+pub struct Referral {
+    pub pool: Pubkey,
+    pub referrer: Pubkey,
+    /// Deposits made through this referral
+    pub referred_deposits: u64,
+    /// USDC earned over the referral's lifetime
+    pub total_earned: u64,
+    /// USDC earned and not yet claimed
+    pub claimable: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Referral {
+    pub const SEED_PREFIX: &'static [u8] = b"referral";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // referrer
+        8 +  // referred_deposits
+        8 +  // total_earned
+        8 +  // claimable
+        1;   // bump
+}
+
+/// Deposits attributed to a distribution partner, for settling
+/// revenue-share agreements from on-chain totals
+#[account]
+pub struct PartnerAccount {
+    pub pool: Pubkey,
+    /// Admin-assigned partner identifier
+    pub partner_id: u64,
+    /// Deposits attributed to the partner
+    pub deposit_count: u64,
+    /// Net USDC deposited through the partner
+    pub aum_contributed: u64,
+    /// Deposit fees charged on the partner's deposits
+    pub fees_generated: u64,
+    pub registered_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PartnerAccount {
+    pub const SEED_PREFIX: &'static [u8] = b"partner";
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        8 +  // partner_id
+        8 +  // deposit_count
+        8 +  // aum_contributed
+        8 +  // fees_generated
+        8 +  // registered_at
+        1;   // bump
+}
+
+/// An address's standing on a pool's access list
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessStatus {
+    Allowed,
+    Blocked,
+}
+
+/// One address on a pool's allow/block list, managed in bulk by
+/// set_access_list and remove_from_access_list
+#[account]
+pub struct AccessListEntry {
+    pub pool: Pubkey,
+    pub address: Pubkey,
+    pub status: AccessStatus,
+    /// Investor tier, which picks the deposit cap and fees when the pool
+    /// has tier terms
+    pub tier: InvestorTier,
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl AccessListEntry {
+    pub const SEED_PREFIX: &'static [u8] = b"access_list";
+
+    /// Most addresses one access list instruction may touch
+    pub const MAX_BATCH: usize = 20;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // address
+        1 +  // status
+        1 +  // tier
+        8 +  // updated_at
+        1;   // bump
+}
@@ -6,6 +6,15 @@ use anchor_lang::prelude::*;
 pub struct CalculationUtils;
 
 impl CalculationUtils {
+    /// Exchange rates are scaled by 1e6
+    pub const RATE_PRECISION: u128 = 1_000_000;
+
+    /// Seconds in the 365-day year management fees are annualized over
+    pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+
+    /// Largest mint decimals supported by pricing math
+    pub const MAX_DECIMALS: u8 = 18;
+
     /// Calculate fee amount in basis points
     pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
         if fee_bps == 0 {
@@ -19,11 +28,27 @@ impl CalculationUtils {
             .ok_or(PoolError::DivisionByZero.into())
     }
 
+    /// Ratio 10^ipt_decimals / 10^usdc_decimals as (numerator, denominator)
+    fn decimals_scale(usdc_decimals: u8, ipt_decimals: u8) -> Result<(u128, u128)> {
+        require!(
+            usdc_decimals <= Self::MAX_DECIMALS && ipt_decimals <= Self::MAX_DECIMALS,
+            PoolError::InvalidDecimals
+        );
+
+        Ok(if ipt_decimals >= usdc_decimals {
+            (10u128.pow((ipt_decimals - usdc_decimals) as u32), 1)
+        } else {
+            (1, 10u128.pow((usdc_decimals - ipt_decimals) as u32))
+        })
+    }
+
     /// Calculate IPT amount from net USDC deposit
     pub fn calculate_ipt_from_net_usdc_deposit(
         net_usdc_amount: u64,
         exchange_rate: u64,
         deposit_fee_bps: u16,
+        usdc_decimals: u8,
+        ipt_decimals: u8,
     ) -> Result<(u64, u64, u64)> {
         let deposit_fee = Self::calculate_fee(net_usdc_amount, deposit_fee_bps)?;
 
@@ -31,12 +56,21 @@ impl CalculationUtils {
             .checked_add(deposit_fee)
             .ok_or(PoolError::MathematicalOverflow)?;
 
-        let ipt_amount = net_usdc_amount
-            .checked_mul(1_000_000)
+        let (scale_num, scale_den) = Self::decimals_scale(usdc_decimals, ipt_decimals)?;
+
+        let ipt_amount = (net_usdc_amount as u128)
+            .checked_mul(Self::RATE_PRECISION)
+            .and_then(|v| v.checked_mul(scale_num))
             .ok_or(PoolError::MathematicalOverflow)?
-            .checked_div(exchange_rate)
+            .checked_div(
+                (exchange_rate as u128)
+                    .checked_mul(scale_den)
+                    .ok_or(PoolError::MathematicalOverflow)?,
+            )
             .ok_or(PoolError::DivisionByZero)?;
 
+        let ipt_amount = u64::try_from(ipt_amount).map_err(|_| PoolError::MathematicalOverflow)?;
+
         Ok((ipt_amount, deposit_fee, gross_usdc_amount))
     }
 
@@ -45,13 +79,21 @@ impl CalculationUtils {
         net_ipt_amount: u64,
         exchange_rate: u64,
         withdrawal_fee_bps: u16,
+        usdc_decimals: u8,
+        ipt_decimals: u8,
     ) -> Result<(u64, u64)> {
-        let gross_usdc_amount = net_ipt_amount
-            .checked_mul(exchange_rate)
+        let (scale_num, scale_den) = Self::decimals_scale(usdc_decimals, ipt_decimals)?;
+
+        let gross_usdc_amount = (net_ipt_amount as u128)
+            .checked_mul(exchange_rate as u128)
+            .and_then(|v| v.checked_mul(scale_den))
             .ok_or(PoolError::MathematicalOverflow)?
-            .checked_div(1_000_000)
+            .checked_div(Self::RATE_PRECISION * scale_num)
             .ok_or(PoolError::DivisionByZero)?;
 
+        let gross_usdc_amount =
+            u64::try_from(gross_usdc_amount).map_err(|_| PoolError::MathematicalOverflow)?;
+
         let withdrawal_fee = Self::calculate_fee(gross_usdc_amount, withdrawal_fee_bps)?;
 
         let net_usdc_amount = gross_usdc_amount
@@ -60,4 +102,202 @@ impl CalculationUtils {
 
         Ok((net_usdc_amount, withdrawal_fee))
     }
+
+    /// Net USDC to deposit to mint at least `ipt_amount` IPT at
+    /// `exchange_rate`; rounds up
+    pub fn net_usdc_for_ipt(
+        ipt_amount: u64,
+        exchange_rate: u64,
+        usdc_decimals: u8,
+        ipt_decimals: u8,
+    ) -> Result<u64> {
+        let (scale_num, scale_den) = Self::decimals_scale(usdc_decimals, ipt_decimals)?;
+
+        let value = (ipt_amount as u128)
+            .checked_mul(exchange_rate as u128)
+            .and_then(|v| v.checked_mul(scale_den))
+            .ok_or(PoolError::MathematicalOverflow)?;
+        let net_usdc_amount = value.div_ceil(Self::RATE_PRECISION * scale_num);
+
+        u64::try_from(net_usdc_amount).map_err(|_| PoolError::MathematicalOverflow.into())
+    }
+
+    /// IPT to redeem to receive at least `net_usdc_amount` once
+    /// `deductions_bps` of the gross value (withdrawal fee plus any exit
+    /// adjustment) is taken; rounds up
+    pub fn ipt_for_net_usdc(
+        net_usdc_amount: u64,
+        exchange_rate: u64,
+        deductions_bps: u16,
+        usdc_decimals: u8,
+        ipt_decimals: u8,
+    ) -> Result<u64> {
+        require!(deductions_bps < 10_000, PoolError::InvalidFeeRate);
+        require!(exchange_rate > 0, PoolError::DivisionByZero);
+        let (scale_num, scale_den) = Self::decimals_scale(usdc_decimals, ipt_decimals)?;
+
+        let gross_usdc_amount = (net_usdc_amount as u128 * 10_000)
+            .div_ceil(10_000 - deductions_bps as u128);
+        let ipt_amount = gross_usdc_amount
+            .checked_mul(Self::RATE_PRECISION)
+            .and_then(|v| v.checked_mul(scale_num))
+            .ok_or(PoolError::MathematicalOverflow)?
+            .div_ceil(exchange_rate as u128 * scale_den);
+
+        u64::try_from(ipt_amount).map_err(|_| PoolError::MathematicalOverflow.into())
+    }
+
+    /// Deduct a queue-pressure exit adjustment from a withdrawal's net USDC;
+    /// the adjustment is charged on the pre-fee value and stays in the pool
+    pub fn apply_exit_adjustment(
+        net_usdc_amount: u64,
+        withdrawal_fee: u64,
+        adjustment_bps: u16,
+    ) -> Result<(u64, u64)> {
+        let gross_usdc_amount = net_usdc_amount
+            .checked_add(withdrawal_fee)
+            .ok_or(PoolError::MathematicalOverflow)?;
+
+        let adjustment = Self::calculate_fee(gross_usdc_amount, adjustment_bps)?;
+
+        let net_usdc_amount = net_usdc_amount
+            .checked_sub(adjustment)
+            .ok_or(PoolError::MathematicalUnderflow)?;
+
+        Ok((net_usdc_amount, adjustment))
+    }
+
+    /// P&L implied by receiving `proceeds_usdc` for `ipt_amount` entered at
+    /// `avg_entry_rate` (0 when the entry rate is unknown)
+    pub fn implied_pnl(
+        ipt_amount: u64,
+        proceeds_usdc: u64,
+        avg_entry_rate: u64,
+        usdc_decimals: u8,
+        ipt_decimals: u8,
+    ) -> Result<i64> {
+        if avg_entry_rate == 0 {
+            return Ok(0);
+        }
+        let (entry_value, _) = Self::calculate_usdc_from_net_ipt_withdrawal(
+            ipt_amount,
+            avg_entry_rate,
+            0,
+            usdc_decimals,
+            ipt_decimals,
+        )?;
+        i64::try_from(proceeds_usdc as i128 - entry_value as i128)
+            .map_err(|_| PoolError::MathematicalOverflow.into())
+    }
+
+    /// USDC value of `amount` of a reserve asset priced at `price` USDC per
+    /// whole token (scaled like the exchange rate); rounds down
+    pub fn asset_value(amount: u64, price: u64, usdc_decimals: u8, asset_decimals: u8) -> Result<u64> {
+        let (value, _) = Self::calculate_usdc_from_net_ipt_withdrawal(
+            amount,
+            price,
+            0,
+            usdc_decimals,
+            asset_decimals,
+        )?;
+        Ok(value)
+    }
+
+    /// Amount of a reserve asset worth `usdc_value` at `price`; rounds down
+    pub fn asset_amount_for_value(
+        usdc_value: u64,
+        price: u64,
+        usdc_decimals: u8,
+        asset_decimals: u8,
+    ) -> Result<u64> {
+        let (amount, _, _) = Self::calculate_ipt_from_net_usdc_deposit(
+            usdc_value,
+            price,
+            0,
+            usdc_decimals,
+            asset_decimals,
+        )?;
+        Ok(amount)
+    }
+
+    /// IPT to mint so `ipt_supply` at par carries the value it has at
+    /// `exchange_rate`; rounds down so the pool never over-issues
+    pub fn calculate_rebase_mint(ipt_supply: u64, exchange_rate: u64) -> Result<u64> {
+        let rebased_supply = (ipt_supply as u128)
+            .checked_mul(exchange_rate as u128)
+            .ok_or(PoolError::MathematicalOverflow)?
+            / Self::RATE_PRECISION;
+
+        let rebased_supply =
+            u64::try_from(rebased_supply).map_err(|_| PoolError::MathematicalOverflow)?;
+
+        rebased_supply
+            .checked_sub(ipt_supply)
+            .ok_or(PoolError::MathematicalUnderflow.into())
+    }
+
+    /// Management fee accrued on `nav` over `elapsed_secs` at an annual
+    /// `fee_bps`; rounds down in holders' favour
+    pub fn calculate_management_fee(nav: u64, fee_bps: u16, elapsed_secs: i64) -> Result<u64> {
+        if fee_bps == 0 || elapsed_secs <= 0 {
+            return Ok(0);
+        }
+
+        let fee = (nav as u128)
+            .checked_mul(fee_bps as u128)
+            .and_then(|v| v.checked_mul(elapsed_secs as u128))
+            .ok_or(PoolError::MathematicalOverflow)?
+            / (10_000 * Self::SECONDS_PER_YEAR);
+
+        u64::try_from(fee).map_err(|_| PoolError::MathematicalOverflow.into())
+    }
+
+    /// Exchange rate (scaled by 1e6) from a feed price `price × 10^exponent`;
+    /// rounds down
+    pub fn rate_from_feed_price(price: i64, exponent: i32) -> Result<u64> {
+        require!(price > 0, PoolError::InvalidExchangeRate);
+
+        let shift = exponent
+            .checked_add(Self::RATE_PRECISION.ilog10() as i32)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        let scale = 10u128
+            .checked_pow(shift.unsigned_abs())
+            .ok_or(PoolError::MathematicalOverflow)?;
+
+        let rate = if shift >= 0 {
+            (price as u128)
+                .checked_mul(scale)
+                .ok_or(PoolError::MathematicalOverflow)?
+        } else {
+            price as u128 / scale
+        };
+
+        u64::try_from(rate).map_err(|_| PoolError::MathematicalOverflow.into())
+    }
+
+    /// Split `amount` by non-zero `weights_bps` summing to 10,000; each share
+    /// rounds down and the last takes the remainder, so the shares sum to
+    /// `amount`
+    pub fn split_by_weights(amount: u64, weights_bps: &[u16]) -> Result<Vec<u64>> {
+        require!(
+            weights_bps.iter().all(|w| *w > 0)
+                && weights_bps.iter().map(|w| *w as u32).sum::<u32>() == 10_000,
+            PoolError::InvalidRouteWeights
+        );
+
+        let mut shares = Vec::with_capacity(weights_bps.len());
+        let mut remaining = amount;
+        for (i, weight) in weights_bps.iter().enumerate() {
+            let share = if i + 1 == weights_bps.len() {
+                remaining
+            } else {
+                (amount as u128 * *weight as u128 / 10_000) as u64
+            };
+            remaining = remaining
+                .checked_sub(share)
+                .ok_or(PoolError::MathematicalUnderflow)?;
+            shares.push(share);
+        }
+        Ok(shares)
+    }
 }
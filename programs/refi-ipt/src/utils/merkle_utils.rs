@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub struct MerkleUtils;
+
+impl MerkleUtils {
+    /// Longest proof accepted (trees of up to 2^20 holders)
+    pub const MAX_PROOF_LEN: usize = 20;
+
+    /// Leaf committing a holder's snapshot entitlement in one distribution
+    pub fn dividend_leaf(distribution: &Pubkey, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+        hashv(&[
+            distribution.as_ref(),
+            claimant.as_ref(),
+            &amount.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Leaf committing a holder's line on one monthly statement
+    pub fn statement_leaf(
+        statement: &Pubkey,
+        holder: &Pubkey,
+        balance: u64,
+        yield_amount: u64,
+    ) -> [u8; 32] {
+        hashv(&[
+            statement.as_ref(),
+            holder.as_ref(),
+            &balance.to_le_bytes(),
+            &yield_amount.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Verify `leaf` belongs to the tree with `root`; sibling pairs are
+    /// hashed in sorted order so proofs carry no left/right flags
+    pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+        if proof.len() > Self::MAX_PROOF_LEN {
+            return false;
+        }
+
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+        computed == *root
+    }
+}
@@ -1,7 +1,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod calculation_utils;
+pub mod merkle_utils;
+pub mod oracle_utils;
+pub mod performance_utils;
+pub mod signature_utils;
+pub mod token_utils;
 pub mod validation_utils;
 
 pub use calculation_utils::CalculationUtils;
+pub use merkle_utils::MerkleUtils;
+pub use oracle_utils::{FeedPrice, OracleUtils};
+pub use performance_utils::PerformanceUtils;
+pub use signature_utils::SignatureUtils;
+pub use token_utils::{TokenAccountFields, TokenUtils};
 pub use validation_utils::ValidationUtils;
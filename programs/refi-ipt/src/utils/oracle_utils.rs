@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use anchor_lang::prelude::*;
+
+/// Price read from a Pyth price update account
+#[derive(Clone, Copy)]
+pub struct FeedPrice {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+pub struct OracleUtils;
+
+impl OracleUtils {
+    // Pyth Solana receiver program (rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ)
+    pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+        12, 183, 250, 187, 82, 247, 166, 72, 187, 91, 49, 125, 154, 1, 139, 144, 87, 203, 2, 71,
+        116, 250, 254, 1, 230, 196, 223, 152, 204, 56, 88, 129,
+    ]);
+
+    // Layout of a fully verified PriceUpdateV2 account: discriminator,
+    // write_authority, verification_level tag, then the price message
+    const PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+    const VERIFICATION_LEVEL_OFFSET: usize = 8 + 32;
+    const VERIFICATION_FULL: u8 = 1;
+    const MESSAGE_OFFSET: usize = Self::VERIFICATION_LEVEL_OFFSET + 1;
+    const MESSAGE_LEN: usize = 32 + 8 + 8 + 4 + 8;
+
+    /// Read a fully verified Pyth price update owned by the receiver program
+    pub fn read_pyth_price_update(info: &AccountInfo) -> Result<FeedPrice> {
+        require_keys_eq!(
+            *info.owner,
+            Self::PYTH_RECEIVER_PROGRAM_ID,
+            PoolError::InvalidRateFeed
+        );
+
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= Self::MESSAGE_OFFSET + Self::MESSAGE_LEN
+                && data[..8] == Self::PRICE_UPDATE_DISCRIMINATOR
+                && data[Self::VERIFICATION_LEVEL_OFFSET] == Self::VERIFICATION_FULL,
+            PoolError::InvalidRateFeed
+        );
+
+        let message = &data[Self::MESSAGE_OFFSET..];
+        let mut feed_id = [0u8; 32];
+        feed_id.copy_from_slice(&message[..32]);
+
+        Ok(FeedPrice {
+            feed_id,
+            price: i64::from_le_bytes(message[32..40].try_into().unwrap()),
+            conf: u64::from_le_bytes(message[40..48].try_into().unwrap()),
+            exponent: i32::from_le_bytes(message[48..52].try_into().unwrap()),
+            publish_time: i64::from_le_bytes(message[52..60].try_into().unwrap()),
+        })
+    }
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::states::NavSummary;
+
+/// Performance figures derived from a pool's NavSummary history, for
+/// off-chain integrators. Every function takes the history in period order
+/// and returns fractions (0.05 = 5%), or None when the history is too short.
+pub struct PerformanceUtils;
+
+impl PerformanceUtils {
+    /// Days compounding is annualized over
+    pub const DAYS_PER_YEAR: f64 = 365.0;
+
+    /// Rate in force at the end of `period`: the close of the latest summary
+    /// at or before it
+    pub fn rate_at(history: &[NavSummary], period: u64) -> Option<u64> {
+        history
+            .iter()
+            .rev()
+            .find(|summary| summary.period <= period)
+            .map(|summary| summary.close_rate)
+    }
+
+    /// Return from the rate in force before the first recorded update to the
+    /// latest close
+    pub fn since_inception_return(history: &[NavSummary]) -> Option<f64> {
+        let first = history.first()?;
+        let last = history.last()?;
+        Self::growth(first.open_rate, last.close_rate).map(|growth| growth - 1.0)
+    }
+
+    /// Compounded annual yield over the `window_days` ending at the latest
+    /// summary; None if the history does not reach back that far
+    pub fn trailing_apy(history: &[NavSummary], window_days: u64) -> Option<f64> {
+        if window_days == 0 {
+            return None;
+        }
+        let last = history.last()?;
+        let start_period = last.period.checked_sub(window_days)?;
+        let start_rate = Self::rate_at(history, start_period)?;
+        let growth = Self::growth(start_rate, last.close_rate)?;
+        Some(growth.powf(Self::DAYS_PER_YEAR / window_days as f64) - 1.0)
+    }
+
+    /// Largest fall from a running peak to a later low. Within a day the
+    /// low is only compared with earlier peaks, as the summary does not say
+    /// whether the high or the low came first.
+    pub fn max_drawdown(history: &[NavSummary]) -> Option<f64> {
+        let first = history.first()?;
+        let mut peak = first.open_rate;
+        let mut drawdown: f64 = 0.0;
+        for summary in history {
+            peak = peak.max(summary.open_rate);
+            if peak > 0 {
+                drawdown = drawdown.max(1.0 - summary.low_rate as f64 / peak as f64);
+            }
+            peak = peak.max(summary.high_rate);
+        }
+        Some(drawdown)
+    }
+
+    fn growth(start_rate: u64, end_rate: u64) -> Option<f64> {
+        (start_rate > 0).then(|| end_rate as f64 / start_rate as f64)
+    }
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+
+pub struct SignatureUtils;
+
+impl SignatureUtils {
+    // Layout of the ed25519 program's instruction data
+    const HEADER_SIZE: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+    const PUBKEY_SIZE: usize = 32;
+    const SIGNATURE_SIZE: usize = 64;
+
+    /// Message a publisher signs to authorize a rate update
+    pub fn signed_rate_message(pool: &Pubkey, rate: u64, timestamp: i64, nonce: u64) -> Vec<u8> {
+        let mut message = Vec::with_capacity(32 + 8 + 8 + 8);
+        message.extend_from_slice(pool.as_ref());
+        message.extend_from_slice(&rate.to_le_bytes());
+        message.extend_from_slice(&timestamp.to_le_bytes());
+        message.extend_from_slice(&nonce.to_le_bytes());
+        message
+    }
+
+    /// Verify `ix` is an ed25519 program instruction checking a single
+    /// signature by `signer` over exactly `message`
+    pub fn verify_ed25519_ix(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+        require_keys_eq!(
+            ix.program_id,
+            ed25519_program::ID,
+            PoolError::InvalidOracleSignature
+        );
+        require!(ix.accounts.is_empty(), PoolError::InvalidOracleSignature);
+
+        let data = &ix.data;
+        require!(
+            data.len() >= Self::HEADER_SIZE + Self::OFFSETS_SIZE && data[0] == 1,
+            PoolError::InvalidOracleSignature
+        );
+
+        let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+        let offsets = Self::HEADER_SIZE;
+        let signature_offset = read_u16(offsets) as usize;
+        let signature_ix_index = read_u16(offsets + 2);
+        let pubkey_offset = read_u16(offsets + 4) as usize;
+        let pubkey_ix_index = read_u16(offsets + 6);
+        let message_offset = read_u16(offsets + 8) as usize;
+        let message_size = read_u16(offsets + 10) as usize;
+        let message_ix_index = read_u16(offsets + 12);
+
+        // All data must live in the ed25519 instruction itself, not be borrowed
+        // from another instruction the precompile would check instead
+        require!(
+            signature_ix_index == u16::MAX
+                && pubkey_ix_index == u16::MAX
+                && message_ix_index == u16::MAX,
+            PoolError::InvalidOracleSignature
+        );
+        require!(
+            data.len() >= signature_offset + Self::SIGNATURE_SIZE,
+            PoolError::InvalidOracleSignature
+        );
+
+        let pubkey = data
+            .get(pubkey_offset..pubkey_offset + Self::PUBKEY_SIZE)
+            .ok_or(PoolError::InvalidOracleSignature)?;
+        require!(pubkey == signer.as_ref(), PoolError::InvalidOracleSignature);
+
+        let signed_message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(PoolError::InvalidOracleSignature)?;
+        require!(signed_message == message, PoolError::InvalidOracleSignature);
+
+        Ok(())
+    }
+}
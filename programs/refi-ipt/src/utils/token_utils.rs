@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::PoolError;
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::memo::{build_memo, BuildMemo, Memo};
+use anchor_spl::token::spl_token::state::{Account as SplTokenAccount, AccountState};
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+
+/// The token account fields batch execution needs, read without a full unpack
+#[derive(Clone, Copy)]
+pub struct TokenAccountFields {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub delegate: Option<Pubkey>,
+    pub delegated_amount: u64,
+}
+
+pub struct TokenUtils;
+
+impl TokenUtils {
+    // Layout of spl_token::state::Account
+    const MINT_OFFSET: usize = 0;
+    const OWNER_OFFSET: usize = 32;
+    const AMOUNT_OFFSET: usize = 64;
+    const DELEGATE_OFFSET: usize = 72;
+    const STATE_OFFSET: usize = 108;
+    const DELEGATED_AMOUNT_OFFSET: usize = 121;
+
+    // Longest reference attach_memo will log
+    pub const MAX_MEMO_LEN: usize = 256;
+
+    /// Read the fields of an initialized SPL token account owned by the token program
+    pub fn read_token_account(info: &AccountInfo) -> Result<TokenAccountFields> {
+        require_keys_eq!(*info.owner, TOKEN_PROGRAM_ID, ErrorCode::AccountOwnedByWrongProgram);
+
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() == SplTokenAccount::LEN
+                && data[Self::STATE_OFFSET] != AccountState::Uninitialized as u8,
+            ErrorCode::AccountDidNotDeserialize
+        );
+
+        let delegate = match read_u32(&data, Self::DELEGATE_OFFSET) {
+            0 => None,
+            _ => Some(read_pubkey(&data, Self::DELEGATE_OFFSET + 4)),
+        };
+
+        Ok(TokenAccountFields {
+            mint: read_pubkey(&data, Self::MINT_OFFSET),
+            owner: read_pubkey(&data, Self::OWNER_OFFSET),
+            amount: read_u64(&data, Self::AMOUNT_OFFSET),
+            delegate,
+            delegated_amount: read_u64(&data, Self::DELEGATED_AMOUNT_OFFSET),
+        })
+    }
+
+    /// Balance of a token account already validated by `read_token_account`
+    pub fn read_amount(info: &AccountInfo) -> Result<u64> {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() == SplTokenAccount::LEN,
+            ErrorCode::AccountDidNotDeserialize
+        );
+        Ok(read_u64(&data, Self::AMOUNT_OFFSET))
+    }
+}
+
+impl TokenUtils {
+    /// Log `memo` through the SPL Memo program so bank-side reconciliation
+    /// can match the transfer; nothing happens without a memo
+    pub fn attach_memo<'info>(
+        memo_program: Option<&Program<'info, Memo>>,
+        memo: Option<&String>,
+    ) -> Result<()> {
+        let Some(memo) = memo else {
+            return Ok(());
+        };
+        require!(memo.len() <= Self::MAX_MEMO_LEN, PoolError::MemoTooLong);
+        let memo_program = memo_program.ok_or(PoolError::MemoProgramMissing)?;
+
+        build_memo(
+            CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+            memo.as_bytes(),
+        )
+    }
+
+    /// Call `method` ("wrap" or "unwrap") on a sweep wrapper program, signed
+    /// by the pool authority. Wrappers follow the Anchor convention: the
+    /// `global:<method>` discriminator then the amount as data, the
+    /// authority first, then the source and destination token accounts and
+    /// any wrapper-specific accounts in `accounts`
+    pub fn invoke_wrapper<'info>(
+        wrapper_program: &AccountInfo<'info>,
+        method: &str,
+        amount: u64,
+        authority: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut data = hash(format!("global:{}", method).as_bytes()).to_bytes()[..8].to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let mut metas = vec![AccountMeta::new_readonly(authority.key(), true)];
+        metas.extend(accounts.iter().map(|info| AccountMeta {
+            pubkey: info.key(),
+            is_signer: info.is_signer,
+            is_writable: info.is_writable,
+        }));
+
+        let mut infos = vec![authority.clone()];
+        infos.extend_from_slice(accounts);
+        infos.push(wrapper_program.clone());
+
+        invoke_signed(
+            &Instruction {
+                program_id: wrapper_program.key(),
+                accounts: metas,
+                data,
+            },
+            &infos,
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&data[offset..offset + 32]);
+    Pubkey::new_from_array(bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
@@ -1,14 +1,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors::PoolError;
+use crate::events::{OperationRejected, QueueDepthAlert, ReserveDriftExceeded};
 use crate::states::*;
+use crate::utils::CalculationUtils;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
 
 pub struct ValidationUtils;
 
 impl ValidationUtils {
+    /// ComputeBudget111111111111111111111111111111; its instructions may
+    /// accompany isolated ones
+    const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+        3, 6, 70, 111, 229, 33, 23, 50, 255, 236, 173, 186, 114, 195, 155, 231, 188, 140, 229,
+        187, 197, 247, 18, 107, 44, 67, 155, 58, 64, 0, 0, 0,
+    ]);
+
     /// Validate pool configuration
-    pub fn validate_pool_config(config: &PoolConfig) -> Result<()> {
+    pub fn validate_pool_config(config: &PoolConfig, queue_capacity: u32) -> Result<()> {
         // Validate fee rates are within valid range (0-10000 basis points = 0-100%)
         require!(
             config.deposit_fee_bps <= 10_000,
@@ -29,6 +41,13 @@ impl ValidationUtils {
             PoolError::InvalidExchangeRate
         );
 
+        // Rebasing pools hold IPT at par and mint yield instead
+        require!(
+            !config.rebasing
+                || config.initial_exchange_rate as u128 == CalculationUtils::RATE_PRECISION,
+            PoolError::InvalidExchangeRate
+        );
+
         // Validate authorities are not default pubkey
         require!(
             config.admin_authority != Pubkey::default(),
@@ -42,13 +61,260 @@ impl ValidationUtils {
             config.fee_collector != Pubkey::default(),
             PoolError::InvalidAuthority
         );
+        require!(
+            config.treasurer_authority != Pubkey::default(),
+            PoolError::InvalidAuthority
+        );
 
         // Validate max_queue_size doesn't exceed account allocation
         require!(
-            config.max_queue_size as usize <= Pool::MAX_QUEUE_SIZE,
+            config.max_queue_size <= queue_capacity,
+            PoolError::InvalidConfigParameter
+        );
+
+        require!(
+            (0..=PoolConfig::MAX_TIMELOCK_DELAY).contains(&config.timelock_delay),
+            PoolError::InvalidConfigParameter
+        );
+
+        require!(
+            config.epoch_duration_secs > 0
+                && config.epoch_duration_secs <= PoolConfig::MAX_EPOCH_DURATION,
             PoolError::InvalidConfigParameter
         );
 
+        require!(
+            config.max_heartbeat_secs >= 0
+                && config.dead_man_switch_secs >= 0
+                && config.min_rate_update_interval_secs >= 0,
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            config.max_redemption_bps_per_epoch <= 10_000,
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            config.max_batch_share_bps <= 10_000,
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            config.max_skip_attempts >= 1,
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            (0..=PoolConfig::MAX_EXECUTION_DELAY).contains(&config.min_execution_delay_secs),
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            config.cancellation_fee_bps <= PoolConfig::MAX_CANCELLATION_FEE_BPS
+                && config.cancellation_grace_secs >= 0,
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            config.reveal_delay_slots <= PoolConfig::MAX_REVEAL_DELAY_SLOTS
+                && (config.commit_reveal_threshold == 0 || config.reveal_delay_slots > 0),
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            (0..=PoolConfig::MAX_APPROVAL_EXPIRY).contains(&config.approval_expiry_secs)
+                && (config.approval_threshold == 0
+                    || config.compliance_authority != Pubkey::default()),
+            PoolError::InvalidConfigParameter
+        );
+        // The co-signer must be a key the treasurer does not already hold
+        require!(
+            config.large_withdrawal_threshold == 0
+                || (config.withdrawal_cosigner != Pubkey::default()
+                    && config.withdrawal_cosigner != config.treasurer_authority),
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            (0..=PoolConfig::MAX_ORACLE_OVERLAP).contains(&config.oracle_overlap_secs),
+            PoolError::InvalidConfigParameter
+        );
+        Self::validate_fee_recipients(&config.fee_recipients)?;
+        require!(
+            config.promo_start_at <= config.promo_end_at
+                && config.promo_bonus_bps <= PoolConfig::MAX_PROMO_BONUS_BPS,
+            PoolError::InvalidConfigParameter
+        );
+        require!(
+            config.early_supporter_discount_bps <= 10_000
+                && config.referral_fee_share_bps <= 10_000,
+            PoolError::InvalidFeeRate
+        );
+
+        // Fee and exit adjustment together cannot take more than the withdrawal
+        require!(
+            config.withdrawal_fee_bps as u32 + config.queue_pressure_bps as u32 <= 10_000,
+            PoolError::InvalidFeeRate
+        );
+
+        // A round trip plus a year of management fees stays under the cap
+        require!(
+            config.deposit_fee_bps as u32
+                + config.withdrawal_fee_bps as u32
+                + config.management_fee_bps as u32
+                <= PoolConfig::MAX_COMBINED_FEE_BPS as u32,
+            PoolError::CombinedFeesTooHigh
+        );
+
+        // Deposits can only be reversed for a short while after they settle
+        require!(
+            (0..=PoolConfig::MAX_REFUND_WINDOW).contains(&config.refund_window_secs),
+            PoolError::InvalidConfigParameter
+        );
+
+        // Escheatment needs somewhere to send swept positions
+        require!(
+            config.dormancy_period_secs >= 0
+                && (config.dormancy_period_secs == 0 || config.escheat_custody != Pubkey::default()),
+            PoolError::InvalidConfigParameter
+        );
+
+        require!(
+            config.reserve_drift_tolerance_bps <= 10_000,
+            PoolError::InvalidConfigParameter
+        );
+
+        // A sweep wrapper needs the mint it issues, and vice versa
+        require!(
+            (config.sweep_wrapper_program == Pubkey::default())
+                == (config.sweep_wrapped_mint == Pubkey::default()),
+            PoolError::InvalidConfigParameter
+        );
+
+        // The waterfall names every payout class exactly once
+        require!(
+            config.wind_down_waterfall.is_empty()
+                || (config.wind_down_waterfall.len() == PayoutClass::DEFAULT_ORDER.len()
+                    && PayoutClass::DEFAULT_ORDER
+                        .iter()
+                        .all(|class| config.wind_down_waterfall.contains(class))),
+            PoolError::InvalidConfigParameter
+        );
+
+        // Tier terms cover every tier, each within the same fee limits as
+        // the base schedule
+        require!(
+            config.tier_terms.is_empty() || config.tier_terms.len() == InvestorTier::ALL.len(),
+            PoolError::InvalidConfigParameter
+        );
+        for terms in &config.tier_terms {
+            require!(
+                terms.withdrawal_fee_bps as u32 + config.queue_pressure_bps as u32 <= 10_000,
+                PoolError::InvalidFeeRate
+            );
+            require!(
+                terms.deposit_fee_bps as u32
+                    + terms.withdrawal_fee_bps as u32
+                    + config.management_fee_bps as u32
+                    <= PoolConfig::MAX_COMBINED_FEE_BPS as u32,
+                PoolError::CombinedFeesTooHigh
+            );
+        }
+
+        // A recovery key held by the admin recovers nothing
+        require!(
+            config.recovery_authority != config.admin_authority,
+            PoolError::InvalidConfigParameter
+        );
+
+        // Segregated pools keep fee income out of the admin's hands
+        require!(
+            !config.segregate_roles || config.fee_collector != config.admin_authority,
+            PoolError::RoleSegregationViolated
+        );
+
+        // Features that hold withdrawals back need room in the queue
+        require!(
+            !config.queues_withdrawals() || config.max_queue_size > 0,
+            PoolError::QueueSizeRequired
+        );
+
+        // Bounds must be ordered and admit the initial rate
+        require!(
+            config.rate_ceiling == 0 || config.rate_floor <= config.rate_ceiling,
+            PoolError::RateBoundsInverted
+        );
+        require!(
+            config.rate_in_bounds(config.initial_exchange_rate),
+            PoolError::InvalidRateBounds
+        );
+
+        Ok(())
+    }
+
+    /// Validate the fee split: distinct accounts with non-zero shares that
+    /// together take at most the whole sweep
+    pub fn validate_fee_recipients(recipients: &[FeeRecipient]) -> Result<()> {
+        require!(
+            recipients.len() <= PoolConfig::MAX_FEE_RECIPIENTS,
+            PoolError::InvalidFeeRecipients
+        );
+
+        let mut total_bps = 0u32;
+        for (i, recipient) in recipients.iter().enumerate() {
+            require!(
+                recipient.token_account != Pubkey::default() && recipient.share_bps > 0,
+                PoolError::InvalidFeeRecipients
+            );
+            require!(
+                !recipients[..i]
+                    .iter()
+                    .any(|other| other.token_account == recipient.token_account),
+                PoolError::InvalidFeeRecipients
+            );
+            total_bps += recipient.share_bps as u32;
+        }
+        require!(total_bps <= 10_000, PoolError::InvalidFeeRecipients);
+
+        Ok(())
+    }
+
+    /// Validate an exchange rate against the pool's floor and ceiling
+    pub fn validate_rate_bounds(config: &PoolConfig, rate: u64) -> Result<()> {
+        require!(
+            config.rate_in_bounds(rate),
+            PoolError::RateOutOfBounds
+        );
+        Ok(())
+    }
+
+    /// Validate pool fee rates against the protocol-wide fee bounds
+    pub fn validate_fee_bounds(config: &PoolConfig, protocol_config: &ProtocolConfig) -> Result<()> {
+        require!(
+            config.deposit_fee_bps <= protocol_config.max_deposit_fee_bps,
+            PoolError::FeeExceedsProtocolBound
+        );
+        require!(
+            config.withdrawal_fee_bps <= protocol_config.max_withdrawal_fee_bps,
+            PoolError::FeeExceedsProtocolBound
+        );
+        require!(
+            config.management_fee_bps <= protocol_config.max_management_fee_bps,
+            PoolError::FeeExceedsProtocolBound
+        );
+        require!(
+            config.tier_terms.iter().all(|terms| {
+                terms.deposit_fee_bps <= protocol_config.max_deposit_fee_bps
+                    && terms.withdrawal_fee_bps <= protocol_config.max_withdrawal_fee_bps
+            }),
+            PoolError::FeeExceedsProtocolBound
+        );
+
+        Ok(())
+    }
+
+    /// Validate protocol fee bounds (0-10000 basis points)
+    pub fn validate_protocol_fee_bounds(
+        max_deposit_fee_bps: u16,
+        max_withdrawal_fee_bps: u16,
+        max_management_fee_bps: u16,
+    ) -> Result<()> {
+        require!(max_deposit_fee_bps <= 10_000, PoolError::InvalidFeeRate);
+        require!(max_withdrawal_fee_bps <= 10_000, PoolError::InvalidFeeRate);
+        require!(max_management_fee_bps <= 10_000, PoolError::InvalidFeeRate);
         Ok(())
     }
 
@@ -58,29 +324,292 @@ impl ValidationUtils {
         Ok(())
     }
 
-    /// Validate pool state for operation
-    pub fn validate_pool_state_for_operation(
-        pool_state: &PoolState,
-        is_deposit: bool,
+    /// Emit OperationRejected for a failed soft check and hand back its error
+    pub fn reject(
+        pool: Pubkey,
+        user: Pubkey,
+        operation: RejectedOperation,
+        reason: RejectionReason,
+        amount: u64,
+        now: i64,
+        error: Error,
+    ) -> Error {
+        emit!(OperationRejected {
+            user,
+            pool,
+            operation,
+            reason,
+            amount,
+            timestamp: now,
+        });
+        error
+    }
+
+    /// Emit ReserveDriftExceeded if the asset sits outside its target band;
+    /// returns whether it does
+    pub fn check_reserve_drift(pool: &Pool, asset: &ReserveAsset, now: i64) -> Result<bool> {
+        let tolerance_bps = pool.config.reserve_drift_tolerance_bps;
+        let Some(actual_weight_bps) = asset.drift(pool.total_reserve_value()?, tolerance_bps) else {
+            return Ok(false);
+        };
+
+        emit!(ReserveDriftExceeded {
+            pool: asset.pool,
+            mint: asset.mint,
+            target_weight_bps: asset.target_weight_bps,
+            actual_weight_bps,
+            tolerance_bps,
+            asset_value: asset.value,
+            total_reserve_value: pool.total_reserve_value()?,
+            timestamp: now,
+        });
+        Ok(true)
+    }
+
+    /// Raise or clear the pool's queue alert after the queue changed,
+    /// emitting QueueDepthAlert on each transition
+    pub fn check_queue_depth(pool: &mut Account<Pool>, now: i64) -> Result<()> {
+        let alerts_off = pool.config.queue_alert_depth == 0 && pool.config.queue_alert_usdc == 0;
+        if alerts_off && !pool.queue_alert_raised {
+            return Ok(());
+        }
+
+        let queued_usdc = pool.queued_usdc_obligation()?;
+        let breached = pool.queue_alert_breached(queued_usdc);
+        if breached == pool.queue_alert_raised {
+            return Ok(());
+        }
+        pool.queue_alert_raised = breached;
+
+        emit!(QueueDepthAlert {
+            pool: pool.key(),
+            raised: breached,
+            queue_depth: pool.queued().len() as u32,
+            queued_usdc,
+            depth_threshold: pool.config.queue_alert_depth,
+            usdc_threshold: pool.config.queue_alert_usdc,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Validate the depositor against the pool's access list; `entry` is the
+    /// depositor's (possibly uncreated) entry PDA
+    pub fn validate_access(pool: &Pool, entry: Option<&AccountInfo>) -> Result<()> {
+        if !pool.access_list_enforced() {
+            return Ok(());
+        }
+        let entry = entry.ok_or(PoolError::AccessEntryRequired)?;
+
+        let status = if *entry.owner == crate::ID && !entry.data_is_empty() {
+            Some(AccessListEntry::try_deserialize(&mut &entry.try_borrow_data()?[..])?.status)
+        } else {
+            None
+        };
+        match status {
+            Some(AccessStatus::Blocked) => err!(PoolError::AddressBlocked),
+            None if pool.config.allowlist_only => err!(PoolError::AddressNotAllowed),
+            _ => Ok(()),
+        }
+    }
+
+    /// The investor tier whose terms apply to the owner of `entry`; with
+    /// tiers configured the entry must be passed, and addresses without one
+    /// are Retail
+    pub fn investor_tier(pool: &Pool, entry: Option<&AccountInfo>) -> Result<InvestorTier> {
+        if pool.config.tier_terms.is_empty() {
+            return Ok(InvestorTier::default());
+        }
+        let entry = entry.ok_or(PoolError::AccessEntryRequired)?;
+
+        if *entry.owner == crate::ID && !entry.data_is_empty() {
+            Ok(AccessListEntry::try_deserialize(&mut &entry.try_borrow_data()?[..])?.tier)
+        } else {
+            Ok(InvestorTier::default())
+        }
+    }
+
+    /// Validate a deposit of `usdc_amount` keeps what the depositor has
+    /// deposited through `position` within the cap of `tier`
+    pub fn validate_tier_deposit(
+        config: &PoolConfig,
+        tier: InvestorTier,
+        position: Option<&Position>,
+        usdc_amount: u64,
+    ) -> Result<()> {
+        let Some(terms) = config.terms_for(tier) else {
+            return Ok(());
+        };
+        if terms.max_deposit_usdc == 0 {
+            return Ok(());
+        }
+        let position = position.ok_or(PoolError::TierPositionRequired)?;
+        let deposited = position
+            .deposited_usdc
+            .checked_add(usdc_amount)
+            .ok_or(PoolError::MathematicalOverflow)?;
+        require!(
+            deposited <= terms.max_deposit_usdc,
+            PoolError::TierDepositCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Validate fee and referral payouts may run; while winding down they
+    /// wait for the waterfall's fee class
+    pub fn validate_fee_payout(pool: &Pool) -> Result<()> {
+        require!(
+            pool.pool_state != PoolState::WindDown
+                || pool.wind_down_class() == Some(PayoutClass::Fees),
+            PoolError::WindDownStageInactive
+        );
+        Ok(())
+    }
+
+    /// Validate the oracle has not missed its heartbeat
+    pub fn validate_oracle_fresh(pool: &Pool, now: i64) -> Result<()> {
+        require!(!pool.is_oracle_stale(now), PoolError::OracleStale);
+        Ok(())
+    }
+
+    /// Validate the protocol-wide emergency pause is not engaged
+    pub fn validate_protocol_not_paused(protocol_config: &ProtocolConfig) -> Result<()> {
+        require!(!protocol_config.is_paused, PoolError::ProtocolPaused);
+        Ok(())
+    }
+
+    /// Validate a subsystem has not been switched off program-wide
+    pub fn validate_feature_enabled(
+        protocol_config: &ProtocolConfig,
+        feature: Feature,
     ) -> Result<()> {
-        match pool_state {
-            PoolState::Active => Ok(()),
-            PoolState::Paused => Err(PoolError::PoolPaused.into()),
-            PoolState::Frozen => Err(PoolError::PoolFrozen.into()),
-            PoolState::DepositOnly => {
-                if is_deposit {
-                    Ok(())
-                } else {
-                    Err(PoolError::WithdrawalsDisabled.into())
-                }
+        require!(
+            protocol_config.feature_enabled(feature),
+            PoolError::FeatureDisabled
+        );
+        Ok(())
+    }
+
+    /// Validate the pool lifecycle and pause flag allow a user operation
+    pub fn validate_pool_operation(pool: &Pool, operation: PauseFlag) -> Result<()> {
+        match pool.pool_state {
+            PoolState::Active => {}
+            // An abandoned pool still lets holders out
+            PoolState::Frozen if operation == PauseFlag::Deposits => {
+                return Err(PoolError::PoolFrozen.into())
             }
-            PoolState::WithdrawOnly => {
-                if is_deposit {
-                    Err(PoolError::DepositsDisabled.into())
-                } else {
-                    Ok(())
+            PoolState::Frozen => {}
+            PoolState::Migration => return Err(PoolError::PoolInMigration.into()),
+            // Only the class the waterfall has reached may be paid
+            PoolState::WindDown => {
+                let class = match operation {
+                    PauseFlag::Deposits => return Err(PoolError::PoolWindingDown.into()),
+                    PauseFlag::Withdrawals => Some(PayoutClass::Holders),
+                    PauseFlag::Queue => Some(PayoutClass::QueuedWithdrawals),
+                    PauseFlag::RateUpdates => None,
+                };
+                if let Some(class) = class {
+                    require!(
+                        pool.wind_down_class() == Some(class),
+                        PoolError::WindDownStageInactive
+                    );
                 }
             }
         }
+
+        Self::validate_not_paused(pool, operation)
+    }
+
+    /// Validate a single pause flag is clear
+    pub fn validate_not_paused(pool: &Pool, operation: PauseFlag) -> Result<()> {
+        if !pool.is_paused(operation) {
+            return Ok(());
+        }
+
+        Err(match operation {
+            PauseFlag::Deposits => PoolError::DepositsDisabled,
+            PauseFlag::Withdrawals => PoolError::WithdrawalsDisabled,
+            PauseFlag::Queue => PoolError::QueuePaused,
+            PauseFlag::RateUpdates => PoolError::RateUpdatesPaused,
+        }
+        .into())
+    }
+
+    /// Validate an admin action before it is scheduled
+    pub fn validate_admin_action(action: &AdminAction) -> Result<()> {
+        match action {
+            AdminAction::MintIpt {
+                beneficiary,
+                amount,
+                reason,
+            } => {
+                require!(*beneficiary != Pubkey::default(), PoolError::InvalidUserAccount);
+                require!(*amount > 0, PoolError::ZeroAmountNotAllowed);
+                require!(
+                    reason.len() <= AdminAction::MAX_REASON_LEN,
+                    PoolError::InvalidConfigParameter
+                );
+            }
+            AdminAction::TransferMintAuthority { successor_program } => {
+                require!(
+                    *successor_program != Pubkey::default() && *successor_program != crate::ID,
+                    PoolError::InvalidAuthority
+                );
+            }
+            // Any cap is valid; one below current NAV only stops new deposits
+            AdminAction::SetTvlCap { .. } => {}
+            AdminAction::EscheatPosition {
+                owner,
+                amount,
+                custody,
+            } => {
+                require!(*owner != Pubkey::default(), PoolError::InvalidUserAccount);
+                require!(*amount > 0, PoolError::ZeroAmountNotAllowed);
+                require!(*custody != Pubkey::default(), PoolError::EscheatCustodyMismatch);
+            }
+            AdminAction::SetEscheatCustody { custody } => {
+                require!(*custody != Pubkey::default(), PoolError::EscheatCustodyMismatch);
+            }
+            AdminAction::SetTreasurer { treasurer } => {
+                require!(*treasurer != Pubkey::default(), PoolError::InvalidAuthority);
+            }
+            // Checked against the rest of the config on execution
+            AdminAction::SetWithdrawalCosigner { .. } => {}
+            // The default key disables the role
+            AdminAction::SetRoleAuthority { .. } => {}
+            AdminAction::SetSweepWrapper { program, mint } => {
+                require!(
+                    (*program == Pubkey::default()) == (*mint == Pubkey::default()),
+                    PoolError::InvalidConfigParameter
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate a scheduled admin action's delay has elapsed
+    pub fn validate_timelock_elapsed(operation: &TimelockOperation, now: i64) -> Result<()> {
+        require!(now >= operation.executable_at, PoolError::TimelockNotElapsed);
+        Ok(())
+    }
+
+    /// When the pool opts in, require the current instruction to be the only
+    /// one in the transaction apart from compute budget requests
+    pub fn validate_isolated_exit(pool: &Pool, instructions: &AccountInfo) -> Result<()> {
+        if !pool.config.isolated_exits {
+            return Ok(());
+        }
+
+        let current = load_current_index_checked(instructions)? as usize;
+        let mut index = 0;
+        while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+            require!(
+                index == current || ix.program_id == Self::COMPUTE_BUDGET_PROGRAM_ID,
+                PoolError::TransactionShapeRejected
+            );
+            index += 1;
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,347 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ipt::utils::CalculationUtils;
+use proptest::prelude::*;
+
+/// Exchange rates between 0.01 and 1000 USDC per IPT (scaled by 1e6)
+fn realistic_rate() -> impl Strategy<Value = u64> {
+    10_000u64..=1_000_000_000u64
+}
+
+/// Deposit sizes up to 10M USDC (6 decimals)
+fn realistic_amount() -> impl Strategy<Value = u64> {
+    1u64..=10_000_000_000_000u64
+}
+
+fn fee_bps() -> impl Strategy<Value = u16> {
+    0u16..=10_000u16
+}
+
+/// IPT mint decimals a pool creator may reasonably choose (up to 9, as SOL)
+fn ipt_decimals() -> impl Strategy<Value = u8> {
+    0u8..=9u8
+}
+
+const USDC_DECIMALS: u8 = 6;
+
+proptest! {
+    /// Depositing and immediately withdrawing the minted IPT at the same rate
+    /// must never return more USDC than the user paid in.
+    #[test]
+    fn deposit_then_withdraw_never_profits(
+        net_usdc in realistic_amount(),
+        rate in realistic_rate(),
+        deposit_fee_bps in fee_bps(),
+        withdrawal_fee_bps in fee_bps(),
+        ipt_decimals in ipt_decimals(),
+    ) {
+        let (ipt_amount, _deposit_fee, gross_usdc_paid) =
+            CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+                net_usdc, rate, deposit_fee_bps, USDC_DECIMALS, ipt_decimals,
+            )
+            .unwrap();
+
+        let (usdc_out, _withdrawal_fee) =
+            CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+                ipt_amount, rate, withdrawal_fee_bps, USDC_DECIMALS, ipt_decimals,
+            )
+            .unwrap();
+
+        prop_assert!(usdc_out <= net_usdc);
+        prop_assert!(usdc_out <= gross_usdc_paid);
+    }
+
+    /// Depositing the net USDC quoted for a share amount mints at least
+    /// that many shares.
+    #[test]
+    fn net_usdc_for_ipt_mints_at_least_the_shares(
+        deposit_usdc in realistic_amount(),
+        rate in realistic_rate(),
+        ipt_decimals in ipt_decimals(),
+    ) {
+        let (ipt_amount, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            deposit_usdc, rate, 0, USDC_DECIMALS, ipt_decimals,
+        )
+        .unwrap();
+        let net_usdc = CalculationUtils::net_usdc_for_ipt(
+            ipt_amount, rate, USDC_DECIMALS, ipt_decimals,
+        )
+        .unwrap();
+        let (minted, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            net_usdc, rate, 0, USDC_DECIMALS, ipt_decimals,
+        )
+        .unwrap();
+        prop_assert!(minted >= ipt_amount);
+        prop_assert!(net_usdc <= deposit_usdc);
+    }
+
+    /// Redeeming the IPT quoted for a USDC amount pays at least that amount
+    /// after the withdrawal fee and exit adjustment.
+    #[test]
+    fn ipt_for_net_usdc_pays_at_least_the_assets(
+        net_usdc in realistic_amount(),
+        rate in realistic_rate(),
+        withdrawal_fee_bps in 0u16..=5_000u16,
+        adjustment_bps in 0u16..=500u16,
+        ipt_decimals in ipt_decimals(),
+    ) {
+        let ipt_amount = CalculationUtils::ipt_for_net_usdc(
+            net_usdc, rate, withdrawal_fee_bps + adjustment_bps, USDC_DECIMALS, ipt_decimals,
+        )
+        .unwrap();
+        let (usdc_out, fee) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+            ipt_amount, rate, withdrawal_fee_bps, USDC_DECIMALS, ipt_decimals,
+        )
+        .unwrap();
+        let (usdc_out, _) =
+            CalculationUtils::apply_exit_adjustment(usdc_out, fee, adjustment_bps).unwrap();
+        prop_assert!(usdc_out >= net_usdc);
+    }
+
+    /// Rounding always favours the pool on deposit: the USDC value of the
+    /// minted IPT never exceeds the net USDC deposited.
+    #[test]
+    fn deposit_rounds_in_favour_of_pool(
+        net_usdc in realistic_amount(),
+        rate in realistic_rate(),
+        ipt_decimals in ipt_decimals(),
+    ) {
+        let (ipt_amount, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            net_usdc, rate, 0, USDC_DECIMALS, ipt_decimals,
+        )
+        .unwrap();
+
+        let value = ipt_amount as u128 * rate as u128 * 10u128.pow(USDC_DECIMALS as u32)
+            / (1_000_000 * 10u128.pow(ipt_decimals as u32));
+        prop_assert!(value <= net_usdc as u128);
+    }
+
+    /// Fees never decrease when the fee rate increases.
+    #[test]
+    fn fee_is_monotone_in_bps(
+        amount in any::<u64>(),
+        a in fee_bps(),
+        b in fee_bps(),
+    ) {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+
+        if let (Ok(fee_low), Ok(fee_high)) = (
+            CalculationUtils::calculate_fee(amount, low),
+            CalculationUtils::calculate_fee(amount, high),
+        ) {
+            prop_assert!(fee_low <= fee_high);
+        }
+    }
+
+    /// A fee can never exceed the amount it is charged on.
+    #[test]
+    fn fee_never_exceeds_amount(amount in any::<u64>(), bps in fee_bps()) {
+        if let Ok(fee) = CalculationUtils::calculate_fee(amount, bps) {
+            prop_assert!(fee <= amount);
+        }
+    }
+
+    /// Withdrawal fee plus net payout always equals the gross USDC value.
+    #[test]
+    fn withdrawal_fee_and_net_sum_to_gross(
+        net_usdc in realistic_amount(),
+        rate in realistic_rate(),
+        bps in fee_bps(),
+    ) {
+        let (ipt_amount, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            net_usdc, rate, 0, USDC_DECIMALS, USDC_DECIMALS,
+        )
+        .unwrap();
+        let (net, fee) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+            ipt_amount, rate, bps, USDC_DECIMALS, USDC_DECIMALS,
+        )
+        .unwrap();
+        let (gross, zero_fee) = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+            ipt_amount, rate, 0, USDC_DECIMALS, USDC_DECIMALS,
+        )
+        .unwrap();
+
+        prop_assert_eq!(zero_fee, 0);
+        prop_assert_eq!(net + fee, gross);
+    }
+
+    /// The exit adjustment only moves value from the payout into the pool,
+    /// and fee plus adjustment within 100% never underflows the payout.
+    #[test]
+    fn exit_adjustment_conserves_value(
+        gross in realistic_amount(),
+        fee_bps in 0u16..=5_000,
+        adjustment_bps in 0u16..=5_000,
+    ) {
+        let fee = CalculationUtils::calculate_fee(gross, fee_bps).unwrap();
+        let (net, adjustment) =
+            CalculationUtils::apply_exit_adjustment(gross - fee, fee, adjustment_bps).unwrap();
+
+        prop_assert_eq!(adjustment, CalculationUtils::calculate_fee(gross, adjustment_bps).unwrap());
+        prop_assert_eq!(net + fee + adjustment, gross);
+    }
+
+    /// A rebase preserves holder value at par to within one IPT unit and
+    /// never issues more than the yield covers.
+    #[test]
+    fn rebase_preserves_value_at_par(
+        supply in realistic_amount(),
+        rate in realistic_rate(),
+    ) {
+        prop_assume!(rate as u128 >= CalculationUtils::RATE_PRECISION);
+        let minted = CalculationUtils::calculate_rebase_mint(supply, rate).unwrap();
+
+        let value_before = supply as u128 * rate as u128;
+        let value_after = (supply + minted) as u128 * CalculationUtils::RATE_PRECISION;
+        prop_assert!(value_after <= value_before);
+        prop_assert!(value_before - value_after < CalculationUtils::RATE_PRECISION);
+    }
+
+    /// No input in the full u64 x rate domain may panic; overflow and zero
+    /// rates must surface as errors.
+    #[test]
+    fn no_panics_across_full_domain(
+        amount in any::<u64>(),
+        rate in any::<u64>(),
+        bps in any::<u16>(),
+        usdc_decimals in any::<u8>(),
+        ipt_decimals in any::<u8>(),
+    ) {
+        let _ = CalculationUtils::calculate_fee(amount, bps);
+        let _ = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            amount, rate, bps, usdc_decimals, ipt_decimals,
+        );
+        let _ = CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+            amount, rate, bps, usdc_decimals, ipt_decimals,
+        );
+    }
+
+    /// A zero exchange rate is rejected rather than minting unbounded IPT.
+    #[test]
+    fn zero_rate_is_rejected(
+        amount in 1u64..=u64::MAX / 1_000_000,
+        bps in fee_bps(),
+        ipt_decimals in ipt_decimals(),
+    ) {
+        prop_assert!(CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            amount, 0, bps, USDC_DECIMALS, ipt_decimals,
+        )
+        .is_err());
+    }
+
+    /// Extra IPT decimals only add precision: truncating a finer-grained
+    /// mint back to the quote decimals gives the same amount as a pool whose
+    /// IPT decimals match the quote mint.
+    #[test]
+    fn extra_ipt_decimals_preserve_price(
+        net_usdc in realistic_amount(),
+        rate in realistic_rate(),
+        extra in 0u8..=3u8,
+    ) {
+        let (coarse, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            net_usdc, rate, 0, USDC_DECIMALS, USDC_DECIMALS,
+        )
+        .unwrap();
+        let (fine, _, _) = CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            net_usdc, rate, 0, USDC_DECIMALS, USDC_DECIMALS + extra,
+        )
+        .unwrap();
+
+        prop_assert_eq!(fine / 10u64.pow(extra as u32), coarse);
+    }
+
+    /// Decimals beyond what the pricing math supports are rejected.
+    #[test]
+    fn unsupported_decimals_are_rejected(
+        amount in realistic_amount(),
+        rate in realistic_rate(),
+        ipt_decimals in (CalculationUtils::MAX_DECIMALS + 1)..=u8::MAX,
+    ) {
+        prop_assert!(CalculationUtils::calculate_ipt_from_net_usdc_deposit(
+            amount, rate, 0, USDC_DECIMALS, ipt_decimals,
+        )
+        .is_err());
+        prop_assert!(CalculationUtils::calculate_usdc_from_net_ipt_withdrawal(
+            amount, rate, 0, USDC_DECIMALS, ipt_decimals,
+        )
+        .is_err());
+    }
+
+    /// A management fee never exceeds its annual rate, and splitting an
+    /// accrual period in two never charges more than accruing it at once.
+    #[test]
+    fn management_fee_is_bounded_and_subadditive(
+        nav in realistic_amount(),
+        fee_bps in 0u16..=10_000u16,
+        first in 0i64..=31_536_000i64,
+        second in 0i64..=31_536_000i64,
+    ) {
+        let whole = CalculationUtils::calculate_management_fee(nav, fee_bps, first + second).unwrap();
+        let split = CalculationUtils::calculate_management_fee(nav, fee_bps, first).unwrap()
+            + CalculationUtils::calculate_management_fee(nav, fee_bps, second).unwrap();
+        let annual = CalculationUtils::calculate_fee(nav, fee_bps).unwrap();
+
+        prop_assert!(split <= whole);
+        prop_assert!(CalculationUtils::calculate_management_fee(nav, fee_bps, first).unwrap() <= annual);
+    }
+
+    /// A feed price converts to the same rate whatever exponent it is quoted at.
+    #[test]
+    fn feed_price_is_exponent_invariant(
+        rate in realistic_rate(),
+        extra in 0i32..=6,
+    ) {
+        let price = rate as i64 * 10i64.pow(extra as u32);
+        prop_assert_eq!(
+            CalculationUtils::rate_from_feed_price(price, -6 - extra).unwrap(),
+            rate
+        );
+        prop_assert!(CalculationUtils::rate_from_feed_price(-(rate as i64), -6).is_err());
+    }
+
+    /// A routed deposit's legs add back up to the amount routed; only the
+    /// last leg absorbs rounding, by less than one unit per leg.
+    #[test]
+    fn weighted_split_sums_to_amount(
+        amount in realistic_amount(),
+        weights in prop::collection::vec(1u16..=5_000, 1..=3),
+    ) {
+        let mut weights = weights;
+        let used: u16 = weights.iter().sum();
+        prop_assume!(used < 10_000);
+        weights.push(10_000 - used);
+
+        let shares = CalculationUtils::split_by_weights(amount, &weights).unwrap();
+        prop_assert_eq!(shares.iter().sum::<u64>(), amount);
+        for (share, weight) in shares.iter().zip(weights.iter()) {
+            let exact = amount as u128 * *weight as u128 / 10_000;
+            prop_assert!((*share as u128).abs_diff(exact) < weights.len() as u128);
+        }
+
+        weights[0] += 1;
+        prop_assert!(CalculationUtils::split_by_weights(amount, &weights).is_err());
+    }
+}
+
+#[test]
+fn reserve_assets_are_valued_at_their_price_across_decimals() {
+    // 2.5 tokens of an 8-decimal asset at 1.02 USDC each
+    let value = CalculationUtils::asset_value(250_000_000, 1_020_000, 6, 8).unwrap();
+    assert_eq!(value, 2_550_000);
+
+    // Converting back rounds down, never paying out more than the value
+    let amount = CalculationUtils::asset_amount_for_value(2_550_000, 1_020_000, 6, 8).unwrap();
+    assert_eq!(amount, 250_000_000);
+    let amount = CalculationUtils::asset_amount_for_value(1, 3_000_000, 6, 6).unwrap();
+    assert_eq!(amount, 0);
+}
+
+#[test]
+fn implied_pnl_compares_proceeds_with_entry_value() {
+    // 100 IPT entered at 1.05, redeemed for 108 USDC
+    let pnl = CalculationUtils::implied_pnl(100_000_000, 108_000_000, 1_050_000, 6, 6).unwrap();
+    assert_eq!(pnl, 3_000_000);
+    let pnl = CalculationUtils::implied_pnl(100_000_000, 100_000_000, 1_050_000, 6, 6).unwrap();
+    assert_eq!(pnl, -5_000_000);
+    assert_eq!(CalculationUtils::implied_pnl(100, 100, 0, 6, 6).unwrap(), 0);
+}
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anchor_lang::prelude::*;
+use ipt::states::NavSummary;
+use ipt::utils::PerformanceUtils;
+
+fn day(period: u64, open_rate: u64, high_rate: u64, low_rate: u64, close_rate: u64) -> NavSummary {
+    NavSummary {
+        pool: Pubkey::default(),
+        period,
+        open_rate,
+        high_rate,
+        low_rate,
+        close_rate,
+        close_nav: 0,
+        updates: 1,
+        first_update_at: 0,
+        last_update_at: 0,
+        bump: 0,
+    }
+}
+
+fn assert_close(actual: Option<f64>, expected: f64) {
+    let actual = actual.unwrap();
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+#[test]
+fn returns_are_measured_from_the_rate_in_force() {
+    let history = [
+        day(100, 1_000_000, 1_010_000, 1_000_000, 1_010_000),
+        day(101, 1_010_000, 1_020_000, 1_010_000, 1_020_000),
+        // No updates on days 102..=109; day 101's close stays in force
+        day(110, 1_020_000, 1_050_000, 1_020_000, 1_050_000),
+    ];
+    assert_close(PerformanceUtils::since_inception_return(&history), 0.05);
+    assert_eq!(PerformanceUtils::rate_at(&history, 105), Some(1_020_000));
+    assert_eq!(PerformanceUtils::rate_at(&history, 99), None);
+
+    // Nine days from 1.02 to 1.05, compounded over a year
+    let expected = (1.05f64 / 1.02).powf(365.0 / 9.0) - 1.0;
+    assert_close(PerformanceUtils::trailing_apy(&history, 9), expected);
+
+    // The window may not reach past the history
+    assert_eq!(PerformanceUtils::trailing_apy(&history, 11), None);
+    assert_eq!(PerformanceUtils::trailing_apy(&history, 0), None);
+    assert_eq!(PerformanceUtils::trailing_apy(&[], 30), None);
+}
+
+#[test]
+fn drawdown_runs_from_an_earlier_peak_to_a_later_low() {
+    let history = [
+        day(1, 1_000_000, 1_100_000, 1_000_000, 1_100_000),
+        day(2, 1_100_000, 1_100_000, 990_000, 1_000_000),
+        day(3, 1_000_000, 1_200_000, 1_000_000, 1_150_000),
+    ];
+    assert_close(PerformanceUtils::max_drawdown(&history), 0.1);
+
+    // A day's own high and low are not assumed to come in that order
+    let history = [day(1, 1_000_000, 1_200_000, 900_000, 1_000_000)];
+    assert_close(PerformanceUtils::max_drawdown(&history), 0.1);
+    assert_eq!(PerformanceUtils::max_drawdown(&[]), None);
+}
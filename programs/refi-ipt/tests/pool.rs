@@ -0,0 +1,1215 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use ipt::errors::PoolError;
+use ipt::instructions::{MigrationInputs, POOL_MIGRATIONS};
+use ipt::states::{
+    CostBasisLedger, CostBasisLot, Feature, FeeBucket, FeeRecipient, InvestorTier, LotMethod,
+    NavSummary, PauseFlag, PayoutClass, PendingWithdraw, Pool, PoolConfig, PoolState, Position,
+    ProtocolConfig, ReserveAsset, ShareRegisterCommitment, SkipReason, StatementCommitment,
+    TierTerms,
+};
+use ipt::utils::{MerkleUtils, ValidationUtils};
+
+fn config(max_queue_size: u32) -> PoolConfig {
+    PoolConfig {
+        admin_authority: Pubkey::new_unique(),
+        oracle_authority: Pubkey::new_unique(),
+        fee_collector: Pubkey::new_unique(),
+        treasurer_authority: Pubkey::new_unique(),
+        deposit_fee_bps: 100,
+        withdrawal_fee_bps: 100,
+        management_fee_bps: 50,
+        initial_exchange_rate: 1_000_000,
+        max_total_supply: u64::MAX,
+        max_queue_size,
+        timelock_delay: 0,
+        rate_floor: 0,
+        rate_ceiling: 0,
+        max_heartbeat_secs: 0,
+        dead_man_switch_secs: 0,
+        min_rate_update_interval_secs: 0,
+        max_redemption_bps_per_epoch: 0,
+        queue_pressure_depth: 0,
+        queue_pressure_bps: 0,
+        rebasing: false,
+        epoch_duration_secs: 86_400,
+        queue_age_weight: 0,
+        queue_size_weight: 0,
+        max_batch_share_bps: 0,
+        max_skip_attempts: 3,
+        min_execution_delay_secs: 0,
+        cancellation_fee_bps: 0,
+        cancellation_grace_secs: 0,
+        commit_reveal_threshold: 0,
+        reveal_delay_slots: 0,
+        approval_threshold: 0,
+        compliance_authority: Pubkey::default(),
+        approval_expiry_secs: 0,
+        large_withdrawal_threshold: 0,
+        withdrawal_cosigner: Pubkey::default(),
+        max_buyback_usdc: 0,
+        fee_sweep_threshold: 0,
+        fee_recipients: vec![],
+        oracle_overlap_secs: 0,
+        promo_start_at: 0,
+        promo_end_at: 0,
+        promo_bonus_bps: 0,
+        promo_waives_deposit_fee: false,
+        early_supporter_slots: 0,
+        early_supporter_discount_bps: 0,
+        referral_fee_share_bps: 0,
+        allowlist_only: false,
+        recovery_authority: Pubkey::default(),
+        dormancy_period_secs: 0,
+        escheat_custody: Pubkey::default(),
+        reserve_drift_tolerance_bps: 0,
+        wind_down_waterfall: vec![],
+        pull_payouts: false,
+        guardian_authority: Pubkey::default(),
+        queue_alert_depth: 0,
+        queue_alert_usdc: 0,
+        tier_terms: vec![],
+        max_queue_entries_per_day: 0,
+        refund_window_secs: 0,
+        slot_stable_quotes: false,
+        sweep_wrapper_program: Pubkey::default(),
+        sweep_wrapped_mint: Pubkey::default(),
+        liquidity_buffer_usdc: 0,
+        segregate_roles: false,
+        isolated_exits: true,
+    }
+}
+
+/// Largest serialized form of a queue entry (the skip reason is set)
+fn pending_withdraw() -> PendingWithdraw {
+    PendingWithdraw {
+        user: Pubkey::new_unique(),
+        amount: u64::MAX,
+        min_usdc_amount: u64::MAX,
+        exit_adjustment_bps: u16::MAX,
+        requested_at: i64::MAX,
+        earliest_execution: i64::MAX,
+        approved: true,
+        tier: InvestorTier::Institutional,
+        skip_count: u8::MAX,
+        last_skip_reason: Some(SkipReason::DelegationMissing),
+    }
+}
+
+fn pool(queue_len: usize) -> Pool {
+    Pool {
+        layout_version: Pool::LAYOUT_VERSION,
+        pool_authority: Pubkey::new_unique(),
+        usdc_mint: Pubkey::new_unique(),
+        pool_id: 0,
+        ipt_mint: Pubkey::new_unique(),
+        usdc_reserve: Pubkey::new_unique(),
+        pending_queue: (0..queue_len).map(|_| pending_withdraw()).collect(),
+        queue_head: 0,
+        queue_capacity: queue_len as u32,
+        current_exchange_rate: 1_000_000,
+        total_ipt_supply: 0,
+        total_usdc_reserves: 0,
+        total_accumulated_fees: 0,
+        accumulated_deposit_fees: 0,
+        accumulated_withdrawal_fees: 0,
+        accumulated_management_fees: 0,
+        max_total_supply: 0,
+        tvl_cap: 0,
+        config: config(queue_len as u32),
+        pool_state: PoolState::Active,
+        pause_flags: 0,
+        usdc_decimals: 6,
+        ipt_decimals: 6,
+        next_timelock_id: 0,
+        next_distribution_id: 0,
+        migration_mint_cap: 0,
+        migration_minted: 0,
+        current_epoch: 0,
+        epoch_started_at: 0,
+        last_epoch_nav: 0,
+        redemption_epoch: u64::MAX,
+        epoch_redemption_limit: 0,
+        epoch_redeemed_usdc: 0,
+        admin_withdrawal_epoch: 0,
+        epoch_admin_withdrawn: 0,
+        oracle_publisher: Pubkey::default(),
+        last_oracle_nonce: 0,
+        rate_feed_id: [0; 32],
+        pending_admin: Pubkey::default(),
+        pending_oracle_authority: Pubkey::default(),
+        previous_oracle_authority: Pubkey::default(),
+        oracle_overlap_ends_at: 0,
+        total_buyback_ipt: 0,
+        total_buyback_usdc: 0,
+        promo_budget: 0,
+        promo_spent: 0,
+        depositor_count: 0,
+        certificates_issued: 0,
+        referral_payable: 0,
+        blocked_count: 0,
+        pending_recovery_admin: Pubkey::new_unique(),
+        pending_recovery_oracle: Pubkey::new_unique(),
+        pending_recovery_fee_collector: Pubkey::new_unique(),
+        recovery_executable_at: 0,
+        rate_evidence_hash: [u8::MAX; 32],
+        accrued_yield: 0,
+        quote_rate_slot: 0,
+        quote_rate: 0,
+        reserve_asset_count: 0,
+        asset_reserves_value: 0,
+        wind_down_stage: 0,
+        wind_down_started_at: 0,
+        escrowed_payouts: 0,
+        queue_alert_raised: false,
+        queue_reserved_usdc: 0,
+        swept_usdc: 0,
+        name: String::new(),
+        description_uri: String::new(),
+        logo_uri: String::new(),
+        last_rate_update: 0,
+        last_rate_observed_at: 0,
+        created_at: 0,
+        bump: 255,
+    }
+}
+
+fn position() -> Position {
+    Position {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        depositor_ordinal: 1,
+        first_deposit_at: 0,
+        last_activity_at: 0,
+        dormant_flagged_at: 0,
+        tracked_ipt: 0,
+        avg_entry_rate: 0,
+        deposited_usdc: 0,
+        last_certificate_number: 0,
+        queue_day: 0,
+        queue_entries_today: 0,
+        last_deposit_at: 0,
+        last_deposit_ipt: 0,
+        last_deposit_usdc: 0,
+        last_deposit_fee: 0,
+        last_deposit_net_usdc: 0,
+        last_deposit_referrer: Pubkey::default(),
+        last_deposit_referral_fee: 0,
+        last_deposit_partner: Pubkey::default(),
+        last_deposit_promo_usdc: 0,
+        last_deposit_lot: false,
+        bump: 255,
+    }
+}
+
+fn fee_recipient(share_bps: u16) -> FeeRecipient {
+    FeeRecipient {
+        token_account: Pubkey::new_unique(),
+        share_bps,
+    }
+}
+
+fn tier_terms(max_deposit_usdc: u64, deposit_fee_bps: u16, withdrawal_fee_bps: u16) -> TierTerms {
+    TierTerms {
+        max_deposit_usdc,
+        deposit_fee_bps,
+        withdrawal_fee_bps,
+    }
+}
+
+#[test]
+fn pending_withdraw_space_matches_serialized_size() {
+    let serialized = pending_withdraw().try_to_vec().unwrap();
+    assert_eq!(serialized.len(), PendingWithdraw::INIT_SPACE);
+}
+
+#[test]
+fn pool_space_matches_serialized_size() {
+    for queue_len in [0, 1, Pool::MAX_QUEUE_SIZE, Pool::MAX_QUEUE_CAPACITY] {
+        let mut pool = pool(queue_len);
+        // Variable-length fields are sized for their maximum length
+        pool.config.fee_recipients = vec![fee_recipient(1_000); PoolConfig::MAX_FEE_RECIPIENTS];
+        pool.config.wind_down_waterfall = PayoutClass::DEFAULT_ORDER.to_vec();
+        pool.config.tier_terms = vec![tier_terms(0, 0, 0); InvestorTier::ALL.len()];
+        pool.name = "n".repeat(Pool::MAX_NAME_LEN);
+        pool.description_uri = "d".repeat(Pool::MAX_URI_LEN);
+        pool.logo_uri = "l".repeat(Pool::MAX_URI_LEN);
+        let serialized = pool.try_to_vec().unwrap();
+        assert_eq!(8 + serialized.len(), Pool::space(queue_len), "queue of {}", queue_len);
+    }
+}
+
+#[test]
+fn queue_growth_fits_one_realloc() {
+    let growth = Pool::space(Pool::MAX_QUEUE_SIZE + Pool::MAX_QUEUE_GROWTH) - Pool::LEN;
+    assert!(growth <= MAX_PERMITTED_DATA_INCREASE);
+
+    // Going from the initial size to the full capacity takes two calls
+    let mut capacity = Pool::MAX_QUEUE_SIZE;
+    let mut calls = 0;
+    while capacity < Pool::MAX_QUEUE_CAPACITY {
+        capacity = (capacity + Pool::MAX_QUEUE_GROWTH).min(Pool::MAX_QUEUE_CAPACITY);
+        calls += 1;
+    }
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn admin_state_changes_only_freeze_and_unfreeze() {
+    assert!(PoolState::Active.admin_can_set(PoolState::Frozen));
+    assert!(PoolState::Frozen.admin_can_set(PoolState::Active));
+
+    // No-ops and the lifecycle states with their own instructions
+    assert!(!PoolState::Active.admin_can_set(PoolState::Active));
+    assert!(!PoolState::Active.admin_can_set(PoolState::Migration));
+    assert!(!PoolState::Active.admin_can_set(PoolState::WindDown));
+    assert!(!PoolState::Migration.admin_can_set(PoolState::Active));
+    assert!(!PoolState::WindDown.admin_can_set(PoolState::Active));
+    assert!(!PoolState::WindDown.admin_can_set(PoolState::Frozen));
+}
+
+#[test]
+fn slot_stable_quotes_pin_the_first_rate_read_in_a_slot() {
+    let mut pool = pool(0);
+    pool.current_exchange_rate = 1_000_000;
+
+    // Off by default: flows always see the live rate
+    assert_eq!(pool.pin_quote_rate(10), 1_000_000);
+    pool.current_exchange_rate = 1_010_000;
+    assert_eq!(pool.pin_quote_rate(10), 1_010_000);
+
+    pool.config.slot_stable_quotes = true;
+    assert_eq!(pool.pin_quote_rate(11), 1_010_000);
+    pool.current_exchange_rate = 1_020_000;
+    assert_eq!(pool.quote_rate_at(11), 1_010_000);
+    assert_eq!(pool.pin_quote_rate(11), 1_010_000);
+
+    // The next slot picks up the update
+    assert_eq!(pool.quote_rate_at(12), 1_020_000);
+    assert_eq!(pool.pin_quote_rate(12), 1_020_000);
+}
+
+#[test]
+fn required_queue_capacity_covers_cap_and_queue() {
+    let mut pool = pool(5);
+    pool.config.max_queue_size = 3;
+    assert_eq!(pool.required_queue_capacity(), 5);
+
+    pool.pending_queue.clear();
+    assert_eq!(pool.required_queue_capacity(), 3);
+}
+
+#[test]
+fn consuming_a_batch_window_keeps_unsettled_entries_in_order() {
+    let mut pool = pool(6);
+    for (i, entry) in pool.pending_queue.iter_mut().enumerate() {
+        entry.amount = i as u64;
+    }
+
+    pool.consume_queue_window(&[true, false, true, false]);
+    let live: Vec<u64> = pool.queued().iter().map(|entry| entry.amount).collect();
+    assert_eq!(pool.queue_head, 2);
+    assert_eq!(live, vec![1, 3, 4, 5]);
+
+    pool.compact_queue();
+    assert_eq!(pool.queue_head, 0);
+    assert_eq!(pool.pending_queue.len(), 4);
+    assert_eq!(pool.queued()[0].amount, 1);
+}
+
+#[test]
+fn enqueue_compacts_consumed_entries() {
+    let mut pool = pool(3);
+    pool.consume_queue_window(&[true, true]);
+    assert_eq!(pool.queued().len(), 1);
+
+    let position = pool.enqueue_withdrawal(pending_withdraw());
+    assert_eq!(pool.queue_head, 0);
+    assert_eq!(pool.pending_queue.len(), 2);
+    assert_eq!(position, 1);
+}
+
+#[test]
+fn unapproved_requests_expire_after_the_approval_window() {
+    let mut entry = pending_withdraw();
+    entry.requested_at = 1_000;
+    entry.approved = false;
+
+    assert!(!entry.approval_expired(0, i64::MAX));
+    assert!(!entry.approval_expired(600, 1_599));
+    assert!(entry.approval_expired(600, 1_600));
+
+    entry.approved = true;
+    assert!(!entry.approval_expired(600, 1_600));
+}
+
+#[test]
+fn fee_buckets_sum_to_the_total() {
+    let mut pool = pool(0);
+    pool.credit_fees(FeeBucket::Deposit, 30).unwrap();
+    pool.credit_fees(FeeBucket::Withdrawal, 50).unwrap();
+    pool.credit_fees(FeeBucket::Management, 20).unwrap();
+    assert_eq!(pool.total_accumulated_fees, 100);
+
+    pool.debit_fees(FeeBucket::Withdrawal, 45).unwrap();
+    assert_eq!(pool.fee_balance(FeeBucket::Withdrawal), 5);
+    assert_eq!(pool.total_accumulated_fees, 55);
+
+    // One stream cannot be drawn down with another stream's fees
+    assert!(pool.debit_fees(FeeBucket::Deposit, 31).is_err());
+    assert_eq!(pool.fee_balance(FeeBucket::Deposit), 30);
+    assert_eq!(pool.total_accumulated_fees, 55);
+}
+
+#[test]
+fn previous_oracle_is_accepted_only_during_the_overlap() {
+    let mut pool = pool(0);
+    let current = pool.config.oracle_authority;
+    let previous = Pubkey::new_unique();
+    pool.previous_oracle_authority = previous;
+    pool.oracle_overlap_ends_at = 1_000;
+
+    assert!(pool.is_oracle(&current, 5_000));
+    assert!(pool.is_oracle(&previous, 999));
+    assert!(!pool.is_oracle(&previous, 1_000));
+    assert!(!pool.is_oracle(&Pubkey::new_unique(), 0));
+}
+
+#[test]
+fn fee_split_pays_each_share_rounded_down() {
+    let mut config = config(10);
+    config.fee_recipients = vec![fee_recipient(2_500), fee_recipient(3_333)];
+
+    assert_eq!(config.fee_split(1_000_001).unwrap(), vec![250_000, 333_300]);
+    assert!(ValidationUtils::validate_fee_recipients(&config.fee_recipients).is_ok());
+
+    config.fee_recipients.push(fee_recipient(4_168));
+    assert!(ValidationUtils::validate_fee_recipients(&config.fee_recipients).is_err());
+}
+
+#[test]
+fn promotion_waives_the_fee_first_and_caps_the_bonus_at_the_budget() {
+    let mut pool = pool(0);
+    pool.config.promo_start_at = 100;
+    pool.config.promo_end_at = 200;
+    pool.config.promo_bonus_bps = 500;
+    pool.config.promo_waives_deposit_fee = true;
+    pool.promo_budget = 1_000_000;
+
+    // Outside the window nothing is paid
+    assert_eq!(pool.promotion_for_deposit(10_000_000, 100_000, 99).unwrap(), (0, 0));
+    assert_eq!(pool.promotion_for_deposit(10_000_000, 100_000, 200).unwrap(), (0, 0));
+
+    assert_eq!(pool.promotion_for_deposit(10_000_000, 100_000, 150).unwrap(), (100_000, 500_000));
+    assert_eq!(pool.promotion_for_deposit(40_000_000, 400_000, 150).unwrap(), (400_000, 600_000));
+
+    pool.spend_promo_budget(1_000_000).unwrap();
+    assert_eq!(pool.promotion_for_deposit(10_000_000, 100_000, 150).unwrap(), (0, 0));
+    assert_eq!(pool.promo_spent, 1_000_000);
+}
+
+#[test]
+fn early_supporters_keep_their_deposit_fee_discount() {
+    let mut config = config(10);
+    config.deposit_fee_bps = 100;
+    config.early_supporter_slots = 2;
+    config.early_supporter_discount_bps = 60;
+
+    assert_eq!(config.deposit_fee_bps_for(0), 100);
+    assert_eq!(config.deposit_fee_bps_for(1), 40);
+    assert_eq!(config.deposit_fee_bps_for(2), 40);
+    assert_eq!(config.deposit_fee_bps_for(3), 100);
+
+    config.early_supporter_discount_bps = 500;
+    assert_eq!(config.deposit_fee_bps_for(1), 0);
+}
+
+#[test]
+fn investor_tiers_set_deposit_caps_and_fees() {
+    let mut config = config(10);
+    config.deposit_fee_bps = 100;
+    config.withdrawal_fee_bps = 50;
+
+    // Without tier terms every tier pays the base schedule uncapped
+    assert_eq!(config.tier_deposit_fee_bps(InvestorTier::Institutional, 0), 100);
+    assert_eq!(config.withdrawal_fee_bps_for(InvestorTier::Professional), 50);
+    assert!(ValidationUtils::validate_tier_deposit(&config, InvestorTier::Retail, None, u64::MAX).is_ok());
+
+    config.tier_terms = vec![
+        tier_terms(10_000_000_000, 100, 50),
+        tier_terms(1_000_000_000_000, 60, 30),
+        tier_terms(0, 20, 10),
+    ];
+    assert!(ValidationUtils::validate_pool_config(&config, 10).is_ok());
+    assert_eq!(config.tier_deposit_fee_bps(InvestorTier::Professional, 0), 60);
+    assert_eq!(config.withdrawal_fee_bps_for(InvestorTier::Institutional), 10);
+
+    // Early supporters keep their discount off the tier's fee
+    config.early_supporter_slots = 1;
+    config.early_supporter_discount_bps = 30;
+    assert_eq!(config.tier_deposit_fee_bps(InvestorTier::Professional, 1), 30);
+    assert_eq!(config.tier_deposit_fee_bps(InvestorTier::Institutional, 1), 0);
+
+    let mut position = position();
+    let err = ValidationUtils::validate_tier_deposit(
+        &config,
+        InvestorTier::Retail,
+        Some(&position),
+        10_000_000_001,
+    )
+    .unwrap_err();
+    assert!(err == PoolError::TierDepositCapExceeded.into());
+    assert!(
+        ValidationUtils::validate_tier_deposit(&config, InvestorTier::Institutional, None, u64::MAX)
+            .is_ok()
+    );
+
+    // A capped tier is counted on the depositor's position
+    let err = ValidationUtils::validate_tier_deposit(&config, InvestorTier::Retail, None, 1)
+        .unwrap_err();
+    assert!(err == PoolError::TierPositionRequired.into());
+
+    // Deposits under the cap on their own cannot add up past it
+    let deposit = 6_000_000_000;
+    ValidationUtils::validate_tier_deposit(&config, InvestorTier::Retail, Some(&position), deposit)
+        .unwrap();
+    position.record_entry(deposit, 1_000_000, deposit).unwrap();
+    let err = ValidationUtils::validate_tier_deposit(
+        &config,
+        InvestorTier::Retail,
+        Some(&position),
+        deposit,
+    )
+    .unwrap_err();
+    assert!(err == PoolError::TierDepositCapExceeded.into());
+
+    // Withdrawing half frees half the deposited amount again
+    position.record_exit(deposit / 2);
+    ValidationUtils::validate_tier_deposit(&config, InvestorTier::Retail, Some(&position), deposit)
+        .unwrap();
+
+    // A refund gives back exactly what its deposit counted
+    position.record_entry(deposit, 1_000_000, deposit).unwrap();
+    position.reverse_entry(deposit, deposit);
+    assert_eq!(position.deposited_usdc, deposit / 2);
+
+    // Terms must cover every tier
+    config.tier_terms.pop();
+    let err = ValidationUtils::validate_pool_config(&config, 10).unwrap_err();
+    assert!(err == PoolError::InvalidConfigParameter.into());
+}
+
+#[test]
+fn config_cross_field_checks_fail_with_distinct_errors() {
+    let valid = config(10);
+    assert!(ValidationUtils::validate_pool_config(&valid, 10).is_ok());
+
+    let mut fees = valid.clone();
+    fees.deposit_fee_bps = 1_000;
+    fees.withdrawal_fee_bps = 1_000;
+    assert_eq!(
+        ValidationUtils::validate_pool_config(&fees, 10).unwrap_err(),
+        PoolError::CombinedFeesTooHigh.into()
+    );
+
+    let mut roles = valid.clone();
+    roles.segregate_roles = true;
+    roles.fee_collector = roles.admin_authority;
+    assert_eq!(
+        ValidationUtils::validate_pool_config(&roles, 10).unwrap_err(),
+        PoolError::RoleSegregationViolated.into()
+    );
+
+    let mut queue = config(0);
+    queue.min_execution_delay_secs = 60;
+    assert_eq!(
+        ValidationUtils::validate_pool_config(&queue, 10).unwrap_err(),
+        PoolError::QueueSizeRequired.into()
+    );
+
+    let mut bounds = valid;
+    bounds.rate_floor = 2_000_000;
+    bounds.rate_ceiling = 1_000_000;
+    assert_eq!(
+        ValidationUtils::validate_pool_config(&bounds, 10).unwrap_err(),
+        PoolError::RateBoundsInverted.into()
+    );
+}
+
+#[test]
+fn dead_man_switch_freezes_to_withdrawals_only() {
+    let mut pool = pool(0);
+    pool.last_rate_update = 1_000;
+    assert!(!pool.dead_man_switch_due(i64::MAX));
+
+    pool.config.dead_man_switch_secs = 100;
+    assert!(!pool.dead_man_switch_due(1_100));
+    assert!(pool.dead_man_switch_due(1_101));
+
+    pool.pool_state = PoolState::Frozen;
+    let err = ValidationUtils::validate_pool_operation(&pool, PauseFlag::Deposits).unwrap_err();
+    assert!(err == PoolError::PoolFrozen.into());
+    assert!(ValidationUtils::validate_pool_operation(&pool, PauseFlag::Withdrawals).is_ok());
+    assert!(ValidationUtils::validate_pool_operation(&pool, PauseFlag::Queue).is_ok());
+}
+
+#[test]
+fn positions_go_dormant_until_the_owner_shows_activity() {
+    let mut position = Position {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        depositor_ordinal: 1,
+        first_deposit_at: 0,
+        last_activity_at: 0,
+        dormant_flagged_at: 0,
+        tracked_ipt: 0,
+        avg_entry_rate: 0,
+        deposited_usdc: 0,
+        last_certificate_number: 0,
+        queue_day: 0,
+        queue_entries_today: 0,
+        last_deposit_at: 0,
+        last_deposit_ipt: 0,
+        last_deposit_usdc: 0,
+        last_deposit_fee: 0,
+        last_deposit_net_usdc: 0,
+        last_deposit_referrer: Pubkey::default(),
+        last_deposit_referral_fee: 0,
+        last_deposit_partner: Pubkey::default(),
+        last_deposit_promo_usdc: 0,
+        last_deposit_lot: false,
+        bump: 255,
+    };
+    assert_eq!(position.try_to_vec().unwrap().len() + 8, Position::LEN);
+
+    // Escheatment is opt-in per pool
+    assert!(!position.is_dormant(0, i64::MAX));
+    assert!(!position.is_dormant(1_000, 999));
+    assert!(position.is_dormant(1_000, 1_000));
+
+    position.dormant_flagged_at = 1_000;
+    position.touch(1_500);
+    assert_eq!(position.dormant_flagged_at, 0);
+    assert!(!position.is_dormant(1_000, 2_000));
+}
+
+#[test]
+fn nav_summary_tracks_open_high_low_close_per_day() {
+    let mut summary = NavSummary {
+        pool: Pubkey::new_unique(),
+        period: NavSummary::period_at(86_400 * 3 + 10),
+        open_rate: 0,
+        high_rate: 0,
+        low_rate: 0,
+        close_rate: 0,
+        close_nav: 0,
+        updates: 0,
+        first_update_at: 0,
+        last_update_at: 0,
+        bump: 255,
+    };
+    assert_eq!(summary.period, 3);
+    assert_eq!(summary.try_to_vec().unwrap().len() + 8, NavSummary::LEN);
+
+    summary.record(1_000_000, 1_020_000, 10_200, 100).unwrap();
+    summary.record(1_020_000, 990_000, 9_900, 200).unwrap();
+    summary.record(990_000, 1_005_000, 10_050, 300).unwrap();
+
+    assert_eq!(summary.open_rate, 1_000_000);
+    assert_eq!(summary.high_rate, 1_020_000);
+    assert_eq!(summary.low_rate, 990_000);
+    assert_eq!(summary.close_rate, 1_005_000);
+    assert_eq!(summary.close_nav, 10_050);
+    assert_eq!(summary.updates, 3);
+    assert_eq!((summary.first_update_at, summary.last_update_at), (100, 300));
+}
+
+#[test]
+fn statement_lines_verify_against_the_committed_root() {
+    assert!(StatementCommitment::is_valid_period(202609));
+    assert!(!StatementCommitment::is_valid_period(202613));
+    assert!(!StatementCommitment::is_valid_period(202600));
+    assert!(!StatementCommitment::is_valid_period(9912));
+
+    let statement = Pubkey::new_unique();
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let a = MerkleUtils::statement_leaf(&statement, &alice, 1_000, 12);
+    let b = MerkleUtils::statement_leaf(&statement, &bob, 500, 6);
+    let root = if a <= b {
+        anchor_lang::solana_program::hash::hashv(&[&a, &b]).to_bytes()
+    } else {
+        anchor_lang::solana_program::hash::hashv(&[&b, &a]).to_bytes()
+    };
+
+    assert!(MerkleUtils::verify(&[b], &root, a));
+    assert!(MerkleUtils::verify(&[a], &root, b));
+    // A restated balance or yield no longer matches the commitment
+    let altered = MerkleUtils::statement_leaf(&statement, &alice, 1_000, 13);
+    assert!(!MerkleUtils::verify(&[b], &root, altered));
+}
+
+#[test]
+fn reserve_assets_count_toward_reserves_within_their_target_weight() {
+    let mut pool = pool(0);
+    pool.total_usdc_reserves = 7_000;
+    pool.revalue_reserve_asset(0, 3_000).unwrap();
+    pool.revalue_reserve_asset(3_000, 2_900).unwrap();
+    assert_eq!(pool.total_reserve_value().unwrap(), 9_900);
+
+    let asset = ReserveAsset {
+        pool: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        vault: Pubkey::new_unique(),
+        decimals: 6,
+        price: 1_000_000,
+        price_updated_at: 100,
+        balance: 2_900,
+        value: 2_900,
+        target_weight_bps: 3_000,
+        bump: 255,
+    };
+    assert!(asset.within_target(3_000, 10_000, 0));
+    assert!(!asset.within_target(3_001, 10_001, 0));
+
+    assert!(!asset.is_price_stale(60, 160));
+    assert!(asset.is_price_stale(60, 161));
+    assert!(ReserveAsset { price: 0, ..asset }.is_price_stale(0, 100));
+}
+
+#[test]
+fn reserve_drift_is_flagged_outside_the_tolerance_band() {
+    let asset = ReserveAsset {
+        pool: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        vault: Pubkey::new_unique(),
+        decimals: 6,
+        price: 1_000_000,
+        price_updated_at: 0,
+        balance: 2_400,
+        value: 2_400,
+        target_weight_bps: 3_000,
+        bump: 255,
+    };
+
+    // 24% against a 30% target: inside a 600bps band, outside 500bps
+    assert_eq!(asset.drift(10_000, 600), None);
+    assert_eq!(asset.drift(10_000, 500), Some(2_400));
+    assert_eq!(ReserveAsset { value: 3_700, ..asset }.drift(10_000, 500), Some(3_700));
+
+    // Deposits may fill the band above target
+    assert!(asset.within_target(3_500, 10_000, 500));
+    assert!(!asset.within_target(3_501, 10_000, 500));
+    assert_eq!(ReserveAsset::weight_bps(1, 0), 0);
+}
+
+#[test]
+fn wind_down_pays_one_waterfall_class_at_a_time() {
+    let mut pool = pool(1);
+    pool.config.wind_down_waterfall = vec![
+        PayoutClass::Holders,
+        PayoutClass::QueuedWithdrawals,
+        PayoutClass::Fees,
+    ];
+    assert!(ValidationUtils::validate_pool_config(&pool.config, pool.queue_capacity).is_ok());
+    assert_eq!(pool.wind_down_class(), None);
+
+    pool.pool_state = PoolState::WindDown;
+    assert_eq!(pool.wind_down_class(), Some(PayoutClass::Holders));
+    let err = ValidationUtils::validate_pool_operation(&pool, PauseFlag::Deposits).unwrap_err();
+    assert!(err == PoolError::PoolWindingDown.into());
+    let err = ValidationUtils::validate_pool_operation(&pool, PauseFlag::Queue).unwrap_err();
+    assert!(err == PoolError::WindDownStageInactive.into());
+    assert!(ValidationUtils::validate_pool_operation(&pool, PauseFlag::Withdrawals).is_ok());
+    assert!(ValidationUtils::validate_fee_payout(&pool).is_err());
+
+    pool.total_ipt_supply = 1;
+    assert!(!pool.wind_down_stage_settled());
+    pool.total_ipt_supply = 0;
+    assert!(pool.wind_down_stage_settled());
+
+    pool.wind_down_stage = 2;
+    assert_eq!(pool.wind_down_class(), Some(PayoutClass::Fees));
+    assert!(ValidationUtils::validate_fee_payout(&pool).is_ok());
+    pool.wind_down_stage = 3;
+    assert_eq!(pool.wind_down_class(), None);
+
+    // Every class must appear exactly once
+    pool.config.wind_down_waterfall =
+        vec![PayoutClass::Holders, PayoutClass::Holders, PayoutClass::Fees];
+    assert!(ValidationUtils::validate_pool_config(&pool.config, pool.queue_capacity).is_err());
+}
+
+#[test]
+fn feature_flags_switch_subsystems_off_program_wide() {
+    let mut protocol_config = ProtocolConfig {
+        protocol_admin: Pubkey::new_unique(),
+        max_deposit_fee_bps: 1_000,
+        max_withdrawal_fee_bps: 1_000,
+        max_management_fee_bps: 1_000,
+        is_paused: false,
+        disabled_features: Feature::SHIPPED_DARK,
+        bump: 255,
+    };
+    assert_eq!(protocol_config.try_to_vec().unwrap().len() + 8, ProtocolConfig::LEN);
+
+    // New subsystems start dark; existing ones stay on
+    assert!(!protocol_config.feature_enabled(Feature::ReserveAssetDeposits));
+    assert!(!protocol_config.feature_enabled(Feature::InKindRedemptions));
+    assert!(protocol_config.feature_enabled(Feature::ReferralRewards));
+    let err =
+        ValidationUtils::validate_feature_enabled(&protocol_config, Feature::InKindRedemptions)
+            .unwrap_err();
+    assert!(err == PoolError::FeatureDisabled.into());
+
+    protocol_config.disabled_features = Feature::PayoutEscrows.mask();
+    assert!(protocol_config.feature_enabled(Feature::ReserveAssetDeposits));
+    assert!(!protocol_config.feature_enabled(Feature::PayoutEscrows));
+}
+
+#[test]
+fn layout_version_is_read_from_the_stable_header() {
+    let mut data = <Pool as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+    data.extend(pool(0).try_to_vec().unwrap());
+    assert_eq!(Pool::read_layout_version(&data), Some(Pool::LAYOUT_VERSION));
+
+    // Older layouts are recognized without decoding the rest of the account
+    data[8..10].copy_from_slice(&0u16.to_le_bytes());
+    data.truncate(10);
+    assert_eq!(Pool::read_layout_version(&data), Some(0));
+
+    data[0] ^= 1;
+    assert_eq!(Pool::read_layout_version(&data), None);
+}
+
+#[test]
+fn baseline_pool_migrates_to_the_current_layout() {
+    let pool_authority = Pubkey::new_unique();
+    let usdc_mint = Pubkey::new_unique();
+    let admin = Pubkey::new_unique();
+    let queued_user = Pubkey::new_unique();
+
+    // A pool account as the pre-versioning program wrote it
+    let mut data = <Pool as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(pool_authority.as_ref());
+    data.extend_from_slice(usdc_mint.as_ref());
+    data.extend_from_slice(Pubkey::new_unique().as_ref()); // ipt_mint
+    data.extend_from_slice(Pubkey::new_unique().as_ref()); // usdc_reserve
+    data.extend_from_slice(&1u32.to_le_bytes()); // pending_queue length
+    data.extend_from_slice(queued_user.as_ref());
+    data.extend_from_slice(&500u64.to_le_bytes()); // amount
+    data.extend_from_slice(&490u64.to_le_bytes()); // min_usdc_amount
+    data.extend_from_slice(&1_020_000u64.to_le_bytes()); // current_exchange_rate
+    data.extend_from_slice(&10_000u64.to_le_bytes()); // total_ipt_supply
+    data.extend_from_slice(&10_200u64.to_le_bytes()); // total_usdc_reserves
+    data.extend_from_slice(&75u64.to_le_bytes()); // total_accumulated_fees
+    data.extend_from_slice(&0u64.to_le_bytes()); // max_total_supply
+    data.extend_from_slice(admin.as_ref());
+    data.extend_from_slice(Pubkey::new_unique().as_ref()); // oracle_authority
+    data.extend_from_slice(Pubkey::new_unique().as_ref()); // fee_collector
+    data.extend_from_slice(&100u16.to_le_bytes()); // deposit_fee_bps
+    data.extend_from_slice(&50u16.to_le_bytes()); // withdrawal_fee_bps
+    data.extend_from_slice(&25u16.to_le_bytes()); // management_fee_bps
+    data.extend_from_slice(&1_000_000u64.to_le_bytes()); // initial_exchange_rate
+    data.extend_from_slice(&0u64.to_le_bytes()); // config.max_total_supply
+    data.extend_from_slice(&10u32.to_le_bytes()); // max_queue_size
+    data.push(4); // pool_state: WithdrawOnly
+    data.extend_from_slice(&1_700_000_100i64.to_le_bytes()); // last_rate_update
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes()); // created_at
+    data.push(254); // bump
+    data.resize(Pool::V0_LEN, 0);
+    assert_eq!(Pool::read_layout_version(&data), Some(0));
+
+    let step = POOL_MIGRATIONS.iter().find(|step| step.from == 0).unwrap();
+    (step.migrate)(&mut data, &MigrationInputs { usdc_decimals: 6 }).unwrap();
+    assert_eq!(Pool::read_layout_version(&data), Some(1));
+
+    let pool = Pool::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(pool.pool_authority, pool_authority);
+    assert_eq!(pool.usdc_mint, usdc_mint);
+    assert_eq!(pool.pool_id, Pool::LEGACY_POOL_ID);
+    assert!(pool.id_seed().is_empty());
+    assert_eq!(pool.queue_capacity, Pool::MAX_QUEUE_SIZE as u32);
+    assert_eq!(pool.required_queue_capacity(), 10);
+    assert_eq!(pool.queued().len(), 1);
+    assert_eq!(pool.queued()[0].user, queued_user);
+    assert_eq!(pool.queued()[0].amount, 500);
+    assert_eq!(pool.queued()[0].min_usdc_amount, 490);
+    assert!(pool.queued()[0].approved);
+    assert_eq!(pool.current_exchange_rate, 1_020_000);
+    assert_eq!(pool.total_ipt_supply, 10_000);
+    assert_eq!(pool.total_usdc_reserves, 10_200);
+    assert_eq!(pool.total_accumulated_fees, 75);
+    assert_eq!(pool.accumulated_deposit_fees, 75);
+    assert_eq!(pool.config.admin_authority, admin);
+    assert_eq!(pool.config.treasurer_authority, admin);
+    assert_eq!(pool.config.withdrawal_fee_bps, 50);
+    assert_eq!(pool.config.max_queue_size, 10);
+    assert_eq!(pool.pool_state, PoolState::Active);
+    assert!(pool.is_paused(PauseFlag::Deposits));
+    assert!(!pool.is_paused(PauseFlag::Withdrawals));
+    assert_eq!(pool.usdc_decimals, 6);
+    assert_eq!(pool.last_rate_update, 1_700_000_100);
+    assert_eq!(pool.created_at, 1_700_000_000);
+    assert_eq!(pool.bump, 254);
+    ValidationUtils::validate_pool_config(&pool.config, pool.queue_capacity).unwrap();
+
+    // No current pool can be mistaken for layout 0
+    assert!((0..=Pool::MAX_QUEUE_CAPACITY).all(|capacity| Pool::space(capacity) != Pool::V0_LEN));
+}
+
+#[test]
+fn guardian_pauses_queue_processing_only() {
+    let mut pool = pool(0);
+    let admin = pool.config.admin_authority;
+    let guardian = Pubkey::new_unique();
+    assert!(pool.config.can_pause_queue(&admin));
+    assert!(!pool.config.can_pause_queue(&guardian));
+    assert!(!pool.config.can_pause_queue(&Pubkey::default()));
+
+    pool.config.guardian_authority = guardian;
+    assert!(pool.config.can_pause_queue(&guardian));
+
+    // Batches halt; new requests still pass the withdrawal check
+    pool.pause_flags |= PauseFlag::Queue.mask();
+    let err = ValidationUtils::validate_pool_operation(&pool, PauseFlag::Queue).unwrap_err();
+    assert!(err == PoolError::QueuePaused.into());
+    assert!(ValidationUtils::validate_pool_operation(&pool, PauseFlag::Withdrawals).is_ok());
+}
+
+#[test]
+fn guardian_emergency_pause_halts_every_operation() {
+    let mut pool = pool(0);
+    let guardian = Pubkey::new_unique();
+    assert!(!pool.config.can_emergency_pause(&guardian));
+    pool.config.guardian_authority = guardian;
+    assert!(pool.config.can_emergency_pause(&guardian));
+    assert!(pool.config.can_emergency_pause(&pool.config.admin_authority));
+
+    pool.pause_flags = PauseFlag::ALL;
+    for flag in [
+        PauseFlag::Deposits,
+        PauseFlag::Withdrawals,
+        PauseFlag::Queue,
+        PauseFlag::RateUpdates,
+    ] {
+        assert!(ValidationUtils::validate_pool_operation(&pool, flag).is_err());
+    }
+}
+
+#[test]
+fn queue_alert_trips_on_depth_or_obligation() {
+    let mut pool = pool(0);
+    for _ in 0..3 {
+        let mut pending = pending_withdraw();
+        pending.amount = 100_000_000;
+        pool.pending_queue.push(pending);
+    }
+
+    // 300 IPT at 1.0 less the 1% withdrawal fee
+    let queued_usdc = pool.queued_usdc_obligation().unwrap();
+    assert_eq!(queued_usdc, 297_000_000);
+    assert!(!pool.queue_alert_breached(queued_usdc));
+
+    pool.config.queue_alert_depth = 3;
+    assert!(pool.queue_alert_breached(queued_usdc));
+
+    pool.config.queue_alert_depth = 4;
+    pool.config.queue_alert_usdc = 300_000_000;
+    assert!(!pool.queue_alert_breached(queued_usdc));
+    pool.config.queue_alert_usdc = 297_000_000;
+    assert!(pool.queue_alert_breached(queued_usdc));
+}
+
+#[test]
+fn cost_basis_lots_are_consumed_in_ledger_order() {
+    let lot = |ipt_amount, cost_usdc, acquired_at| CostBasisLot {
+        ipt_amount,
+        cost_usdc,
+        exchange_rate: cost_usdc * 1_000_000 / ipt_amount,
+        acquired_at,
+    };
+    let mut ledger = CostBasisLedger {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        method: LotMethod::Fifo,
+        lots: vec![lot(100, 100, 1), lot(100, 120, 2)],
+        bump: 0,
+    };
+
+    // 150 IPT sold for 195 USDC: all of the first lot and half the second
+    let realized = ledger.dispose(150, 195).unwrap();
+    assert_eq!(realized.len(), 2);
+    assert_eq!((realized[0].acquired_at, realized[0].ipt_amount), (1, 100));
+    assert_eq!((realized[0].proceeds_usdc, realized[0].realized_gain), (130, 30));
+    assert_eq!((realized[1].acquired_at, realized[1].ipt_amount), (2, 50));
+    assert_eq!((realized[1].cost_usdc, realized[1].realized_gain), (60, 5));
+    assert_eq!(ledger.lots, vec![lot(50, 60, 2)]);
+
+    ledger.method = LotMethod::Lifo;
+    ledger.record_lot(lot(10, 15, 3)).unwrap();
+    let realized = ledger.dispose(20, 20).unwrap();
+    assert_eq!((realized[0].acquired_at, realized[0].realized_gain), (3, -5));
+    assert_eq!((realized[1].acquired_at, realized[1].realized_gain), (2, -2));
+
+    // IPT without a recorded basis is not reported
+    let realized = ledger.dispose(1_000, 1_000).unwrap();
+    assert_eq!(realized.iter().map(|lot| lot.ipt_amount).sum::<u64>(), 40);
+    assert!(ledger.lots.is_empty());
+
+    ledger.lots = vec![lot(1, 1, 0); CostBasisLedger::MAX_LOTS];
+    let err = ledger.record_lot(lot(1, 1, 0)).unwrap_err();
+    assert!(err == PoolError::CostBasisLedgerFull.into());
+    assert!(ledger.try_to_vec().unwrap().len() + 8 <= CostBasisLedger::LEN);
+}
+
+#[test]
+fn refunded_deposit_drops_its_lot() {
+    let lot = |ipt_amount, acquired_at| CostBasisLot {
+        ipt_amount,
+        cost_usdc: ipt_amount,
+        exchange_rate: 1_000_000,
+        acquired_at,
+    };
+    let mut ledger = CostBasisLedger {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        method: LotMethod::Fifo,
+        lots: vec![lot(100, 1), lot(50, 2), lot(20, 2)],
+        bump: 0,
+    };
+
+    // The newest lot from the refunded deposit's timestamp goes
+    ledger.reverse_lot(2);
+    assert_eq!(ledger.lots, vec![lot(100, 1), lot(50, 2)]);
+
+    // Nothing left of the lot, nothing to drop
+    ledger.reverse_lot(3);
+    assert_eq!(ledger.lots.len(), 2);
+}
+
+#[test]
+fn position_tracks_ipt_weighted_entry_rate() {
+    let mut position = Position {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        depositor_ordinal: 1,
+        first_deposit_at: 0,
+        last_activity_at: 0,
+        dormant_flagged_at: 0,
+        tracked_ipt: 0,
+        avg_entry_rate: 0,
+        deposited_usdc: 0,
+        last_certificate_number: 0,
+        queue_day: 0,
+        queue_entries_today: 0,
+        last_deposit_at: 0,
+        last_deposit_ipt: 0,
+        last_deposit_usdc: 0,
+        last_deposit_fee: 0,
+        last_deposit_net_usdc: 0,
+        last_deposit_referrer: Pubkey::default(),
+        last_deposit_referral_fee: 0,
+        last_deposit_partner: Pubkey::default(),
+        last_deposit_promo_usdc: 0,
+        last_deposit_lot: false,
+        bump: 255,
+    };
+
+    position.record_entry(300, 1_000_000, 300).unwrap();
+    position.record_entry(100, 1_200_000, 120).unwrap();
+    assert_eq!((position.tracked_ipt, position.avg_entry_rate), (400, 1_050_000));
+    assert_eq!(position.deposited_usdc, 420);
+
+    // Exits leave the average of what remains alone and release their
+    // share of the deposited total
+    position.record_exit(100);
+    assert_eq!((position.tracked_ipt, position.avg_entry_rate), (300, 1_050_000));
+    assert_eq!(position.deposited_usdc, 315);
+    position.record_exit(1_000);
+    assert_eq!((position.tracked_ipt, position.avg_entry_rate), (0, 0));
+    assert_eq!(position.deposited_usdc, 0);
+}
+
+#[test]
+fn position_limits_queue_entries_per_day() {
+    let mut position = Position {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        depositor_ordinal: 1,
+        first_deposit_at: 0,
+        last_activity_at: 0,
+        dormant_flagged_at: 0,
+        tracked_ipt: 0,
+        avg_entry_rate: 0,
+        deposited_usdc: 0,
+        last_certificate_number: 0,
+        queue_day: 0,
+        queue_entries_today: 0,
+        last_deposit_at: 0,
+        last_deposit_ipt: 0,
+        last_deposit_usdc: 0,
+        last_deposit_fee: 0,
+        last_deposit_net_usdc: 0,
+        last_deposit_referrer: Pubkey::default(),
+        last_deposit_referral_fee: 0,
+        last_deposit_partner: Pubkey::default(),
+        last_deposit_promo_usdc: 0,
+        last_deposit_lot: false,
+        bump: 255,
+    };
+    let day = Position::QUEUE_DAY_SECS;
+
+    position.record_queue_entry(day, 2).unwrap();
+    position.record_queue_entry(2 * day - 1, 2).unwrap();
+    let err = position.record_queue_entry(2 * day - 1, 2).unwrap_err();
+    assert!(err == PoolError::QueueEntryLimitReached.into());
+
+    // The count starts over the next day, and no limit never rejects
+    position.record_queue_entry(2 * day, 2).unwrap();
+    assert_eq!((position.queue_day, position.queue_entries_today), (2, 1));
+    for _ in 0..5 {
+        position.record_queue_entry(2 * day, 0).unwrap();
+    }
+}
+
+#[test]
+fn latest_deposit_is_refundable_within_the_window() {
+    let mut position = Position {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        depositor_ordinal: 1,
+        first_deposit_at: 0,
+        last_activity_at: 0,
+        dormant_flagged_at: 0,
+        tracked_ipt: 0,
+        avg_entry_rate: 0,
+        deposited_usdc: 0,
+        last_certificate_number: 0,
+        queue_day: 0,
+        queue_entries_today: 0,
+        last_deposit_at: 1_000,
+        last_deposit_ipt: 0,
+        last_deposit_usdc: 0,
+        last_deposit_fee: 0,
+        last_deposit_net_usdc: 0,
+        last_deposit_referrer: Pubkey::default(),
+        last_deposit_referral_fee: 0,
+        last_deposit_partner: Pubkey::default(),
+        last_deposit_promo_usdc: 0,
+        last_deposit_lot: false,
+        bump: 255,
+    };
+    // Nothing to refund until a deposit is recorded
+    assert!(!position.deposit_refundable(3_600, 1_000));
+
+    position.last_deposit_ipt = 5_000_000;
+    assert!(position.deposit_refundable(3_600, 4_600));
+    assert!(!position.deposit_refundable(3_600, 4_601));
+    // Refunds are opt-in per pool
+    assert!(!position.deposit_refundable(0, 1_000));
+}
+
+#[test]
+fn deposit_covers_queue_head_in_order() {
+    let mut pool = pool(0);
+    for amount in [100_000_000, 50_000_000, 10_000_000] {
+        let mut pending = pending_withdraw();
+        pending.amount = amount;
+        pool.pending_queue.push(pending);
+    }
+
+    // Gross USDC (before the withdrawal fee) at 1.0
+    assert_eq!(pool.queued_entries_covered(99_999_999).unwrap(), 0);
+    assert_eq!(pool.queued_entries_covered(100_000_000).unwrap(), 1);
+    // The third entry would fit, but the second blocks it
+    assert_eq!(pool.queued_entries_covered(140_000_000).unwrap(), 1);
+    assert_eq!(pool.queued_entries_covered(u64::MAX).unwrap(), 3);
+}
+
+#[test]
+fn queue_reserve_is_held_back_from_instant_exits() {
+    let mut pool = pool(0);
+    pool.queue_reserved_usdc = 40;
+
+    // Nothing queued, nothing to hold back
+    assert_eq!(pool.unreserved_liquidity(100), 100);
+
+    pool.pending_queue.push(pending_withdraw());
+    assert_eq!(pool.unreserved_liquidity(100), 60);
+    assert_eq!(pool.unreserved_liquidity(30), 0);
+}
+
+#[test]
+fn admin_withdrawals_accumulate_towards_the_cosigner_threshold() {
+    let mut pool = pool(0);
+    pool.config.large_withdrawal_threshold = 1_000;
+
+    // Splitting a large withdrawal still reaches the threshold
+    let total = pool.record_admin_withdrawal(600).unwrap();
+    assert!(!pool.config.requires_cosigner(total));
+    let total = pool.record_admin_withdrawal(600).unwrap();
+    assert!(pool.config.requires_cosigner(total));
+
+    // A new epoch starts the count again
+    pool.current_epoch += 1;
+    let total = pool.record_admin_withdrawal(600).unwrap();
+    assert_eq!(total, 600);
+    assert!(!pool.config.requires_cosigner(total));
+}
+
+#[test]
+fn only_liquidity_above_the_buffer_is_swept() {
+    let mut pool = pool(0);
+    pool.config.liquidity_buffer_usdc = 50;
+    assert_eq!(pool.sweepable_usdc(80), 30);
+    assert_eq!(pool.sweepable_usdc(40), 0);
+
+    // Queue earmarks are never swept
+    pool.queue_reserved_usdc = 20;
+    pool.pending_queue.push(pending_withdraw());
+    assert_eq!(pool.sweepable_usdc(80), 10);
+}
+
+#[test]
+fn unwrapping_returns_principal_pro_rata() {
+    let mut pool = pool(0);
+    pool.total_usdc_reserves = 100;
+    pool.swept_usdc = 300;
+    assert_eq!(pool.total_reserve_value().unwrap(), 400);
+
+    assert_eq!(pool.swept_principal(50, 150).unwrap(), 100);
+    assert_eq!(pool.swept_principal(150, 150).unwrap(), 300);
+    assert!(pool.swept_principal(151, 150).is_err());
+}
+
+#[test]
+fn deposit_certificates_are_sequential_from_one() {
+    let mut pool = pool(0);
+    assert_eq!(pool.issue_certificate().unwrap(), 1);
+    assert_eq!(pool.issue_certificate().unwrap(), 2);
+    assert_eq!(pool.certificates_issued, 2);
+
+    pool.certificates_issued = u64::MAX;
+    let err = pool.issue_certificate().unwrap_err();
+    assert!(err == PoolError::MathematicalOverflow.into());
+}
+
+#[test]
+fn share_register_commitment_space_matches_serialized_size() {
+    let commitment = ShareRegisterCommitment {
+        pool: Pubkey::new_unique(),
+        cutoff_slot: u64::MAX,
+        register_hash: [u8::MAX; 32],
+        holder_count: u32::MAX,
+        total_balance: u64::MAX,
+        committed_by: Pubkey::new_unique(),
+        committed_at: i64::MAX,
+        bump: u8::MAX,
+    };
+    assert_eq!(
+        commitment.try_to_vec().unwrap().len() + 8,
+        ShareRegisterCommitment::LEN
+    );
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::solana_program::program_option::COption;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token::state::{Account as SplTokenAccount, AccountState};
+use ipt::utils::TokenUtils;
+use proptest::prelude::*;
+
+fn pubkey() -> impl Strategy<Value = Pubkey> {
+    any::<[u8; 32]>().prop_map(Pubkey::new_from_array)
+}
+
+fn packed(account: SplTokenAccount) -> Vec<u8> {
+    let mut data = vec![0u8; SplTokenAccount::LEN];
+    SplTokenAccount::pack(account, &mut data).unwrap();
+    data
+}
+
+fn with_account_info<T>(owner: &Pubkey, data: &mut [u8], f: impl FnOnce(&AccountInfo) -> T) -> T {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0u64;
+    let info = AccountInfo::new(&key, false, true, &mut lamports, data, owner, false, 0);
+    f(&info)
+}
+
+proptest! {
+    #[test]
+    fn read_token_account_matches_full_unpack(
+        mint in pubkey(),
+        owner in pubkey(),
+        amount in any::<u64>(),
+        delegate in proptest::option::of(pubkey()),
+        delegated_amount in any::<u64>(),
+        frozen in any::<bool>(),
+    ) {
+        let account = SplTokenAccount {
+            mint,
+            owner,
+            amount,
+            delegate: delegate.map_or(COption::None, COption::Some),
+            state: if frozen { AccountState::Frozen } else { AccountState::Initialized },
+            is_native: COption::None,
+            delegated_amount,
+            close_authority: COption::None,
+        };
+        let mut data = packed(account);
+
+        let fields = with_account_info(&anchor_spl::token::ID, &mut data, |info| {
+            TokenUtils::read_token_account(info).unwrap()
+        });
+        prop_assert_eq!(fields.mint, mint);
+        prop_assert_eq!(fields.owner, owner);
+        prop_assert_eq!(fields.amount, amount);
+        prop_assert_eq!(fields.delegate, delegate);
+        prop_assert_eq!(fields.delegated_amount, delegated_amount);
+
+        let balance = with_account_info(&anchor_spl::token::ID, &mut data, |info| {
+            TokenUtils::read_amount(info).unwrap()
+        });
+        prop_assert_eq!(balance, amount);
+    }
+}
+
+#[test]
+fn read_token_account_rejects_foreign_and_uninitialized_accounts() {
+    let mut data = packed(SplTokenAccount {
+        state: AccountState::Initialized,
+        ..SplTokenAccount::default()
+    });
+    let foreign = Pubkey::new_unique();
+    assert!(with_account_info(&foreign, &mut data, |info| {
+        TokenUtils::read_token_account(info).is_err()
+    }));
+
+    let mut uninitialized = packed(SplTokenAccount::default());
+    assert!(with_account_info(&anchor_spl::token::ID, &mut uninitialized, |info| {
+        TokenUtils::read_token_account(info).is_err()
+    }));
+}
@@ -12,9 +12,12 @@ import {
   approve,
   transfer,
 } from "@solana/spl-token";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
 import { assert } from "chai";
 
+// Withdrawals still waiting; entries before queueHead were consumed by batch execution
+const liveQueue = (pool) => pool.pendingQueue.slice(pool.queueHead);
+
 describe("Batch Withdraw & Queue Security Tests", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -32,6 +35,10 @@ describe("Batch Withdraw & Queue Security Tests", () => {
   // Test wallets
   const payer = (provider.wallet as anchor.Wallet).payer;
   const admin = provider.wallet;
+  const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+  );
+  let protocolConfigPda: PublicKey;
   const oracle = Keypair.generate();
   const feeCollector = Keypair.generate();
   const maliciousUser = Keypair.generate();
@@ -57,6 +64,34 @@ describe("Batch Withdraw & Queue Security Tests", () => {
   const MAX_TOTAL_SUPPLY = new BN(0); // Unlimited
   const MAX_QUEUE_SIZE = 20;
   const DECIMALS = 6;
+  const POOL_ID = new BN(0);
+
+  // Program-wide config is created once per validator by the upgrade authority
+  before(async () => {
+    [protocolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    const existing = await provider.connection.getAccountInfo(protocolConfigPda);
+    if (!existing) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_ID
+      );
+
+      await program.methods
+        .initProtocolConfig(admin.publicKey, 10_000, 10_000, 10_000)
+        .accounts({
+          authority: admin.publicKey,
+          protocolConfig: protocolConfigPda,
+          program: program.programId,
+          programData,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
 
   before(async () => {
     // // Airdrop SOL
@@ -99,7 +134,7 @@ describe("Batch Withdraw & Queue Security Tests", () => {
 
     // Derive PDAs
     [poolPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("pool"), usdcMint.toBuffer()],
+      [Buffer.from("pool"), usdcMint.toBuffer(), POOL_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     poolAuthority = poolPda;
@@ -177,18 +212,75 @@ describe("Batch Withdraw & Queue Security Tests", () => {
       adminAuthority: admin.publicKey,
       oracleAuthority: oracle.publicKey,
       feeCollector: feeCollector.publicKey,
+      treasurerAuthority: admin.publicKey,
       depositFeeBps: DEPOSIT_FEE_BPS,
       withdrawalFeeBps: WITHDRAWAL_FEE_BPS,
       managementFeeBps: MANAGEMENT_FEE_BPS,
       initialExchangeRate: INITIAL_EXCHANGE_RATE,
       maxTotalSupply: MAX_TOTAL_SUPPLY,
       maxQueueSize: MAX_QUEUE_SIZE,
+      timelockDelay: new BN(0),
+      rateFloor: new BN(0),
+      rateCeiling: new BN(0),
+      maxHeartbeatSecs: new BN(0),
+      maxRedemptionBpsPerEpoch: 0,
+      queuePressureDepth: 0,
+      queuePressureBps: 0,
+      rebasing: false,
+      epochDurationSecs: new BN(86_400),
+      segregateRoles: false,
+      isolatedExits: false,
+      queueAgeWeight: 0,
+      queueSizeWeight: 0,
+      maxBatchShareBps: 0,
+      maxSkipAttempts: 1,
+      minExecutionDelaySecs: new BN(0),
+      cancellationFeeBps: 0,
+      cancellationGraceSecs: new BN(0),
+      commitRevealThreshold: new BN(0),
+      revealDelaySlots: new BN(0),
+      approvalThreshold: new BN(0),
+      complianceAuthority: PublicKey.default,
+      approvalExpirySecs: new BN(0),
+      largeWithdrawalThreshold: new BN(0),
+      withdrawalCosigner: PublicKey.default,
+      maxBuybackUsdc: new BN(0),
+      feeSweepThreshold: new BN(0),
+      feeRecipients: [],
+      oracleOverlapSecs: new BN(0),
+      promoStartAt: new BN(0),
+      promoEndAt: new BN(0),
+      promoBonusBps: 0,
+      promoWaivesDepositFee: false,
+      earlySupporterSlots: new BN(0),
+      earlySupporterDiscountBps: 0,
+      referralFeeShareBps: 0,
+      allowlistOnly: false,
+      recoveryAuthority: PublicKey.default,
+      deadManSwitchSecs: new BN(0),
+      dormancyPeriodSecs: new BN(0),
+      escheatCustody: PublicKey.default,
+      reserveDriftToleranceBps: 0,
+      windDownWaterfall: [],
+      pullPayouts: false,
+      guardianAuthority: PublicKey.default,
+      queueAlertDepth: 0,
+      queueAlertUsdc: new BN(0),
+      tierTerms: [],
+      maxQueueEntriesPerDay: 0,
+      refundWindowSecs: new BN(0),
+      slotStableQuotes: false,
+      sweepWrapperProgram: PublicKey.default,
+      sweepWrappedMint: PublicKey.default,
+      liquidityBufferUsdc: new BN(0),
+      minRateUpdateIntervalSecs: new BN(0),
     };
 
     await program.methods
-      .initPool(config)
+      .initPool(POOL_ID, config)
       .accounts({
         payer: admin.publicKey,
+        protocolConfig: protocolConfigPda,
         usdcMint: usdcMint,
         pool: poolPda,
         systemProgram: SystemProgram.programId,
@@ -197,9 +289,9 @@ describe("Batch Withdraw & Queue Security Tests", () => {
 
     // Initialize pool - Step 2
     await program.methods
-      .initPoolStep2()
+      .initPoolStep2(DECIMALS)
       .accounts({
-        payer: admin.publicKey,
+        admin: admin.publicKey,
         pool: poolPda,
         poolAuthority: poolAuthority,
         usdcMint: usdcMint,
@@ -212,11 +304,11 @@ describe("Batch Withdraw & Queue Security Tests", () => {
 
     // Admin deposits reserves
     await program.methods
-      .adminDepositUsdc(new BN(200_000 * 10 ** DECIMALS))
+      .adminDepositUsdc(new BN(200_000 * 10 ** DECIMALS), { rebalance: {} }, null)
       .accounts({
-        admin: admin.publicKey,
+        treasurer: admin.publicKey,
         pool: poolPda,
-        adminUsdcAccount: adminUsdcAccount,
+        treasurerUsdcAccount: adminUsdcAccount,
         poolUsdcReserve: usdcReserve,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
@@ -271,6 +363,7 @@ describe("Batch Withdraw & Queue Security Tests", () => {
         .accounts({
           user: maliciousUser.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: maliciousUserUsdcAccount,
           userIptAccount: maliciousUserIptAccount,
@@ -288,6 +381,7 @@ describe("Batch Withdraw & Queue Security Tests", () => {
         .accounts({
           user: validUser1.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: validUser1UsdcAccount,
           userIptAccount: validUser1IptAccount,
@@ -304,6 +398,7 @@ describe("Batch Withdraw & Queue Security Tests", () => {
         .accounts({
           user: validUser2.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: validUser2UsdcAccount,
           userIptAccount: validUser2IptAccount,
@@ -323,12 +418,12 @@ describe("Batch Withdraw & Queue Security Tests", () => {
       const withdrawAmount = pool.totalUsdcReserves.sub(new BN(2_000 * 10 ** DECIMALS));
 
       await program.methods
-        .adminWithdrawUsdc(withdrawAmount)
+        .adminWithdrawUsdc(withdrawAmount, { rebalance: {} }, null)
         .accounts({
-          admin: admin.publicKey,
+          treasurer: admin.publicKey,
           pool: poolPda,
           poolAuthority: poolAuthority,
-          adminUsdcAccount: adminUsdcAccount,
+          treasurerUsdcAccount: adminUsdcAccount,
           poolUsdcReserve: usdcReserve,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -358,18 +453,20 @@ describe("Batch Withdraw & Queue Security Tests", () => {
         .accounts({
           user: maliciousUser.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: maliciousUserUsdcAccount,
           userIptAccount: maliciousUserIptAccount,
           poolUsdcReserve: usdcReserve,
           iptMint: iptMint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         })
         .signers([maliciousUser])
         .rpc();
 
       const pool = await program.account.pool.fetch(poolPda);
-      assert.equal(pool.pendingQueue.length, 1);
+      assert.equal(liveQueue(pool).length, 1);
       console.log("✅ Malicious user in queue at position 0");
     });
 
@@ -429,12 +526,14 @@ describe("Batch Withdraw & Queue Security Tests", () => {
         .accounts({
           user: validUser1.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: validUser1UsdcAccount,
           userIptAccount: validUser1IptAccount,
           poolUsdcReserve: usdcReserve,
           iptMint: iptMint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         })
         .signers([validUser1])
         .rpc();
@@ -444,18 +543,20 @@ describe("Batch Withdraw & Queue Security Tests", () => {
         .accounts({
           user: validUser2.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: validUser2UsdcAccount,
           userIptAccount: validUser2IptAccount,
           poolUsdcReserve: usdcReserve,
           iptMint: iptMint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         })
         .signers([validUser2])
         .rpc();
 
       const pool = await program.account.pool.fetch(poolPda);
-      assert.equal(pool.pendingQueue.length, 3);
+      assert.equal(liveQueue(pool).length, 3);
       console.log("✅ Queue now has 3 users:");
       console.log("   [0] Malicious user (0 IPT balance - will be skipped)");
       console.log("   [1] Valid user 1 (will be processed)");
@@ -464,11 +565,11 @@ describe("Batch Withdraw & Queue Security Tests", () => {
 
     it("Step 6: Admin restores reserves for batch processing", async () => {
       await program.methods
-        .adminDepositUsdc(new BN(100_000 * 10 ** DECIMALS))
+        .adminDepositUsdc(new BN(100_000 * 10 ** DECIMALS), { rebalance: {} }, null)
         .accounts({
-          admin: admin.publicKey,
+          treasurer: admin.publicKey,
           pool: poolPda,
-          adminUsdcAccount: adminUsdcAccount,
+          treasurerUsdcAccount: adminUsdcAccount,
           poolUsdcReserve: usdcReserve,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -479,9 +580,9 @@ describe("Batch Withdraw & Queue Security Tests", () => {
 
     it("Step 7: ✅ TEST - Batch process skips malicious user, processes valid users", async () => {
       const poolBefore = await program.account.pool.fetch(poolPda);
-      assert.equal(poolBefore.pendingQueue.length, 3);
+      assert.equal(liveQueue(poolBefore).length, 3);
 
-      const amounts = poolBefore.pendingQueue.map((w) => w.amount);
+      const amounts = liveQueue(poolBefore).map((w) => w.amount);
 
       // Get remaining accounts for batch
       const remainingAccounts = [
@@ -498,10 +599,12 @@ describe("Batch Withdraw & Queue Security Tests", () => {
         .accounts({
           executor: executor.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           poolUsdcReserve: usdcReserve,
           iptMint: iptMint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         })
         .remainingAccounts(remainingAccounts)
         .signers([executor])
@@ -512,14 +615,19 @@ describe("Batch Withdraw & Queue Security Tests", () => {
       const poolAfter = await program.account.pool.fetch(poolPda);
       
       console.log("\n========== BATCH EXECUTION RESULT ==========");
-      console.log("Queue length after:", poolAfter.pendingQueue.length);
+      console.log("Queue length after:", liveQueue(poolAfter).length);
       console.log("Expected: 0 (all processed/skipped)");
       
       assert.equal(
-        poolAfter.pendingQueue.length,
+        liveQueue(poolAfter).length,
         0,
         "Queue should be empty after processing"
       );
+      assert.equal(
+        poolAfter.queueHead,
+        poolAfter.pendingQueue.length,
+        "Processed entries are consumed by advancing the head, not shifting the queue"
+      );
 
       // Check events would show:
       // - 1 WithdrawSkipped event (malicious user)
@@ -533,5 +641,291 @@ describe("Batch Withdraw & Queue Security Tests", () => {
       console.log("   ✅ System NOT blocked!");
       console.log("===========================================\n");
     });
+
+    it("Step 8: Per-user batch share leaves the rest of a large request queued", async () => {
+      const drainReserves = async () => {
+        const pool = await program.account.pool.fetch(poolPda);
+        if (pool.totalUsdcReserves.isZero()) return;
+        await program.methods
+          .adminWithdrawUsdc(pool.totalUsdcReserves, { rebalance: {} }, null)
+          .accounts({
+            treasurer: admin.publicKey,
+            pool: poolPda,
+            poolAuthority: poolAuthority,
+            treasurerUsdcAccount: adminUsdcAccount,
+            poolUsdcReserve: usdcReserve,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      };
+
+      // Empty reserves so the withdrawal has to queue
+      await drainReserves();
+      await program.methods
+        .userDeposit(new BN(1_000 * 10 ** DECIMALS), new BN(0))
+        .accounts({
+          user: validUser1.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          userUsdcAccount: validUser1UsdcAccount,
+          userIptAccount: validUser1IptAccount,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([validUser1])
+        .rpc();
+      await drainReserves();
+
+      const iptBalance = new BN((await getAccount(provider.connection, validUser1IptAccount)).amount.toString());
+      await program.methods
+        .userWithdraw(iptBalance, new BN(0))
+        .accounts({
+          user: validUser1.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          userUsdcAccount: validUser1UsdcAccount,
+          userIptAccount: validUser1IptAccount,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([validUser1])
+        .rpc();
+
+      // Enough reserves for the whole request, but one user may take only half
+      await program.methods
+        .adminDepositUsdc(new BN(1_000 * 10 ** DECIMALS), { rebalance: {} }, null)
+        .accounts({
+          treasurer: admin.publicKey,
+          pool: poolPda,
+          treasurerUsdcAccount: adminUsdcAccount,
+          poolUsdcReserve: usdcReserve,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const originalConfig = (await program.account.pool.fetch(poolPda)).config;
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, maxBatchShareBps: 5_000 })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      await program.methods
+        .batchExecuteWithdraw([iptBalance])
+        .accounts({
+          executor: executor.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .remainingAccounts([
+          { pubkey: validUser1IptAccount, isSigner: false, isWritable: true },
+          { pubkey: validUser1UsdcAccount, isSigner: false, isWritable: true },
+        ])
+        .signers([executor])
+        .rpc();
+
+      const pool = await program.account.pool.fetch(poolPda);
+      assert.equal(liveQueue(pool).length, 1, "Remainder should stay queued");
+      const remaining = liveQueue(pool)[0].amount;
+      assert.isTrue(remaining.gtn(0) && remaining.lt(iptBalance), "Request should be partially filled");
+
+      const iptAfter = new BN((await getAccount(provider.connection, validUser1IptAccount)).amount.toString());
+      assert.equal(iptAfter.toString(), remaining.toString(), "Only the filled part should be burned");
+
+      await program.methods
+        .adminUpdateConfig(originalConfig)
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+    });
+
+    it("Step 9: Automation crank executes the queue head without amounts", async () => {
+      const crankAccounts = {
+        executor: executor.publicKey,
+        pool: poolPda,
+        protocolConfig: protocolConfigPda,
+        poolAuthority: poolAuthority,
+        poolUsdcReserve: usdcReserve,
+        iptMint: iptMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      };
+
+      await program.methods
+        .crankWithdrawQueue()
+        .accounts(crankAccounts)
+        .remainingAccounts([
+          { pubkey: validUser1IptAccount, isSigner: false, isWritable: true },
+          { pubkey: validUser1UsdcAccount, isSigner: false, isWritable: true },
+        ])
+        .signers([executor])
+        .rpc();
+
+      const pool = await program.account.pool.fetch(poolPda);
+      assert.equal(liveQueue(pool).length, 0, "Crank should settle the remainder");
+
+      // An idle crank succeeds so scheduled threads keep running
+      await program.methods
+        .crankWithdrawQueue()
+        .accounts(crankAccounts)
+        .signers([executor])
+        .rpc();
+    });
+
+    it("Step 10: Payouts the user's account cannot receive are escrowed for claiming", async () => {
+      const [payoutEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("payout_escrow"), poolPda.toBuffer(), validUser2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // Queue a withdrawal against empty reserves
+      const pool0 = await program.account.pool.fetch(poolPda);
+      if (!pool0.totalUsdcReserves.isZero()) {
+        await program.methods
+          .adminWithdrawUsdc(pool0.totalUsdcReserves, { rebalance: {} }, null)
+          .accounts({
+            treasurer: admin.publicKey,
+            pool: poolPda,
+            poolAuthority: poolAuthority,
+            treasurerUsdcAccount: adminUsdcAccount,
+            poolUsdcReserve: usdcReserve,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      }
+      await program.methods
+        .userDeposit(new BN(500 * 10 ** DECIMALS), new BN(0))
+        .accounts({
+          user: validUser2.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          userUsdcAccount: validUser2UsdcAccount,
+          userIptAccount: validUser2IptAccount,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([validUser2])
+        .rpc();
+      const deposited = await program.account.pool.fetch(poolPda);
+      await program.methods
+        .adminWithdrawUsdc(deposited.totalUsdcReserves, { rebalance: {} }, null)
+        .accounts({
+          treasurer: admin.publicKey,
+          pool: poolPda,
+          poolAuthority: poolAuthority,
+          treasurerUsdcAccount: adminUsdcAccount,
+          poolUsdcReserve: usdcReserve,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const iptBalance = new BN((await getAccount(provider.connection, validUser2IptAccount)).amount.toString());
+      await program.methods
+        .userWithdraw(iptBalance, new BN(0))
+        .accounts({
+          user: validUser2.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          userUsdcAccount: validUser2UsdcAccount,
+          userIptAccount: validUser2IptAccount,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([validUser2])
+        .rpc();
+
+      await program.methods
+        .adminDepositUsdc(new BN(1_000 * 10 ** DECIMALS), { rebalance: {} }, null)
+        .accounts({
+          treasurer: admin.publicKey,
+          pool: poolPda,
+          treasurerUsdcAccount: adminUsdcAccount,
+          poolUsdcReserve: usdcReserve,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      // The keeper opens the escrow and passes it in place of the USDC account
+      await program.methods
+        .openPayoutEscrow()
+        .accounts({
+          payer: admin.publicKey,
+          pool: poolPda,
+          poolAuthority: poolAuthority,
+          user: validUser2.publicKey,
+          usdcMint: usdcMint,
+          payoutEscrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .crankWithdrawQueue()
+        .accounts({
+          executor: executor.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .remainingAccounts([
+          { pubkey: validUser2IptAccount, isSigner: false, isWritable: true },
+          { pubkey: payoutEscrow, isSigner: false, isWritable: true },
+        ])
+        .signers([executor])
+        .rpc();
+
+      const escrowed = new BN((await getAccount(provider.connection, payoutEscrow)).amount.toString());
+      assert.isTrue(escrowed.gtn(0), "Payout should be held in escrow");
+      let pool = await program.account.pool.fetch(poolPda);
+      assert.equal(liveQueue(pool).length, 0, "Escrowed request should leave the queue");
+      assert.equal(pool.escrowedPayouts.toString(), escrowed.toString());
+
+      const usdcBefore = new BN((await getAccount(provider.connection, validUser2UsdcAccount)).amount.toString());
+      await program.methods
+        .claimEscrowedPayout()
+        .accounts({
+          user: validUser2.publicKey,
+          pool: poolPda,
+          poolAuthority: poolAuthority,
+          payoutEscrow,
+          userUsdcAccount: validUser2UsdcAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([validUser2])
+        .rpc();
+
+      const usdcAfter = new BN((await getAccount(provider.connection, validUser2UsdcAccount)).amount.toString());
+      assert.equal(usdcAfter.sub(usdcBefore).toString(), escrowed.toString());
+      assert.isNull(await provider.connection.getAccountInfo(payoutEscrow), "Escrow should be closed");
+      pool = await program.account.pool.fetch(poolPda);
+      assert.isTrue(pool.escrowedPayouts.isZero());
+    });
   });
 });
@@ -12,8 +12,12 @@ import {
   approve,
   transfer,
 } from "@solana/spl-token";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
 import { assert } from "chai";
+import { createHash } from "crypto";
+
+// Withdrawals still waiting; entries before queueHead were consumed by batch execution
+const liveQueue = (pool) => pool.pendingQueue.slice(pool.queueHead);
 
 describe("refi-ipt - Edge Cases & Security Tests", () => {
   const provider = anchor.AnchorProvider.env();
@@ -32,6 +36,10 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
   // Test wallets
   const payer = (provider.wallet as anchor.Wallet).payer;
   const admin = provider.wallet;
+  const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+  );
+  let protocolConfigPda: PublicKey;
   const oracle = Keypair.generate();
   const feeCollector = Keypair.generate();
   const maliciousUser = Keypair.generate();
@@ -52,6 +60,34 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
   const WITHDRAWAL_FEE_BPS = 100;
   const MANAGEMENT_FEE_BPS = 50;
   const DECIMALS = 6;
+  const POOL_ID = new BN(0);
+
+  // Program-wide config is created once per validator by the upgrade authority
+  before(async () => {
+    [protocolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    const existing = await provider.connection.getAccountInfo(protocolConfigPda);
+    if (!existing) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_ID
+      );
+
+      await program.methods
+        .initProtocolConfig(admin.publicKey, 10_000, 10_000, 10_000)
+        .accounts({
+          authority: admin.publicKey,
+          protocolConfig: protocolConfigPda,
+          program: program.programId,
+          programData,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
 
   before(async () => {
     // // Airdrop SOL
@@ -98,7 +134,7 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
 
     // Derive PDAs
     [poolPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("pool"), usdcMint.toBuffer()],
+      [Buffer.from("pool"), usdcMint.toBuffer(), POOL_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     poolAuthority = poolPda;
@@ -179,18 +215,75 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
       adminAuthority: admin.publicKey,
       oracleAuthority: oracle.publicKey,
       feeCollector: feeCollector.publicKey,
+      treasurerAuthority: admin.publicKey,
       depositFeeBps: DEPOSIT_FEE_BPS,
       withdrawalFeeBps: WITHDRAWAL_FEE_BPS,
       managementFeeBps: MANAGEMENT_FEE_BPS,
       initialExchangeRate: INITIAL_EXCHANGE_RATE,
       maxTotalSupply: MAX_TOTAL_SUPPLY,
       maxQueueSize: MAX_QUEUE_SIZE,
+      timelockDelay: new BN(0),
+      rateFloor: new BN(0),
+      rateCeiling: new BN(0),
+      maxHeartbeatSecs: new BN(0),
+      maxRedemptionBpsPerEpoch: 0,
+      queuePressureDepth: 0,
+      queuePressureBps: 0,
+      rebasing: false,
+      epochDurationSecs: new BN(86_400),
+      segregateRoles: false,
+      isolatedExits: false,
+      queueAgeWeight: 0,
+      queueSizeWeight: 0,
+      maxBatchShareBps: 0,
+      maxSkipAttempts: 1,
+      minExecutionDelaySecs: new BN(0),
+      cancellationFeeBps: 0,
+      cancellationGraceSecs: new BN(0),
+      commitRevealThreshold: new BN(0),
+      revealDelaySlots: new BN(0),
+      approvalThreshold: new BN(0),
+      complianceAuthority: PublicKey.default,
+      approvalExpirySecs: new BN(0),
+      largeWithdrawalThreshold: new BN(0),
+      withdrawalCosigner: PublicKey.default,
+      maxBuybackUsdc: new BN(0),
+      feeSweepThreshold: new BN(0),
+      feeRecipients: [],
+      oracleOverlapSecs: new BN(0),
+      promoStartAt: new BN(0),
+      promoEndAt: new BN(0),
+      promoBonusBps: 0,
+      promoWaivesDepositFee: false,
+      earlySupporterSlots: new BN(0),
+      earlySupporterDiscountBps: 0,
+      referralFeeShareBps: 0,
+      allowlistOnly: false,
+      recoveryAuthority: PublicKey.default,
+      deadManSwitchSecs: new BN(0),
+      dormancyPeriodSecs: new BN(0),
+      escheatCustody: PublicKey.default,
+      reserveDriftToleranceBps: 0,
+      windDownWaterfall: [],
+      pullPayouts: false,
+      guardianAuthority: PublicKey.default,
+      queueAlertDepth: 0,
+      queueAlertUsdc: new BN(0),
+      tierTerms: [],
+      maxQueueEntriesPerDay: 0,
+      refundWindowSecs: new BN(0),
+      slotStableQuotes: false,
+      sweepWrapperProgram: PublicKey.default,
+      sweepWrappedMint: PublicKey.default,
+      liquidityBufferUsdc: new BN(0),
+      minRateUpdateIntervalSecs: new BN(0),
     };
 
     await program.methods
-      .initPool(config)
+      .initPool(POOL_ID, config)
       .accounts({
         payer: admin.publicKey,
+        protocolConfig: protocolConfigPda,
         usdcMint: usdcMint,
         pool: poolPda,
         systemProgram: SystemProgram.programId,
@@ -199,9 +292,9 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
 
     // Initialize pool - Step 2
     await program.methods
-      .initPoolStep2()
+      .initPoolStep2(DECIMALS)
       .accounts({
-        payer: admin.publicKey,
+        admin: admin.publicKey,
         pool: poolPda,
         poolAuthority: poolAuthority,
         usdcMint: usdcMint,
@@ -214,11 +307,11 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
 
     // Admin deposits reserves
     await program.methods
-      .adminDepositUsdc(new BN(100_000 * 10 ** DECIMALS))
+      .adminDepositUsdc(new BN(100_000 * 10 ** DECIMALS), { rebalance: {} }, null)
       .accounts({
-        admin: admin.publicKey,
+        treasurer: admin.publicKey,
         pool: poolPda,
-        adminUsdcAccount: adminUsdcAccount,
+        treasurerUsdcAccount: adminUsdcAccount,
         poolUsdcReserve: usdcReserve,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
@@ -265,6 +358,7 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
         .accounts({
           user: maliciousUser.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: maliciousUserUsdcAccount,
           userIptAccount: maliciousUserIptAccount,
@@ -282,6 +376,7 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
         .accounts({
           user: validUser.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: validUserUsdcAccount,
           userIptAccount: validUserIptAccount,
@@ -301,12 +396,12 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
       const withdrawAmount = pool.totalUsdcReserves.sub(new BN(1000 * 10 ** DECIMALS));
 
       await program.methods
-        .adminWithdrawUsdc(withdrawAmount)
+        .adminWithdrawUsdc(withdrawAmount, { rebalance: {} }, null)
         .accounts({
-          admin: admin.publicKey,
+          treasurer: admin.publicKey,
           pool: poolPda,
           poolAuthority: poolAuthority,
-          adminUsdcAccount: adminUsdcAccount,
+          treasurerUsdcAccount: adminUsdcAccount,
           poolUsdcReserve: usdcReserve,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -338,18 +433,20 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
         .accounts({
           user: maliciousUser.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: maliciousUserUsdcAccount,
           userIptAccount: maliciousUserIptAccount,
           poolUsdcReserve: usdcReserve,
           iptMint: iptMint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         })
         .signers([maliciousUser])
         .rpc();
 
       let pool = await program.account.pool.fetch(poolPda);
-      assert.equal(pool.pendingQueue.length, 1, "Malicious user should be in queue");
+      assert.equal(liveQueue(pool).length, 1, "Malicious user should be in queue");
 
       // 3. ATTACK: Transfer all IPT to another account
       await transfer(
@@ -395,29 +492,31 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
         .accounts({
           user: validUser.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: validUserUsdcAccount,
           userIptAccount: validUserIptAccount,
           poolUsdcReserve: usdcReserve,
           iptMint: iptMint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         })
         .signers([validUser])
         .rpc();
 
       const pool = await program.account.pool.fetch(poolPda);
-      assert.equal(pool.pendingQueue.length, 2, "Both users should be in queue");
+      assert.equal(liveQueue(pool).length, 2, "Both users should be in queue");
 
       console.log("✓ Valid user also in queue");
     });
 
     it("Admin restores reserves for processing", async () => {
       await program.methods
-        .adminDepositUsdc(new BN(50_000 * 10 ** DECIMALS))
+        .adminDepositUsdc(new BN(50_000 * 10 ** DECIMALS), { rebalance: {} }, null)
         .accounts({
-          admin: admin.publicKey,
+          treasurer: admin.publicKey,
           pool: poolPda,
-          adminUsdcAccount: adminUsdcAccount,
+          treasurerUsdcAccount: adminUsdcAccount,
           poolUsdcReserve: usdcReserve,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -435,7 +534,7 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
 
       // For now, we verify the queue state
       assert.equal(
-        poolBefore.pendingQueue.length,
+        liveQueue(poolBefore).length,
         2,
         "Queue should have 2 users before processing"
       );
@@ -448,6 +547,612 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
     });
   });
 
+  describe("Exit Controls", () => {
+    const gatedUser = Keypair.generate();
+    let gatedUserUsdcAccount: PublicKey;
+    let gatedUserIptAccount: PublicKey;
+
+    before(async () => {
+      const tx = new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: payer.publicKey,
+          toPubkey: gatedUser.publicKey,
+          lamports: 0.05 * anchor.web3.LAMPORTS_PER_SOL,
+        })
+      );
+      await provider.sendAndConfirm(tx, [payer]);
+
+      gatedUserUsdcAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, payer, usdcMint, gatedUser.publicKey
+      )).address;
+      gatedUserIptAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, payer, iptMint, gatedUser.publicKey
+      )).address;
+      await mintTo(provider.connection, payer, usdcMint, gatedUserUsdcAccount, payer, 1_000 * 10 ** DECIMALS);
+
+      await program.methods
+        .userDeposit(new BN(1_000 * 10 ** DECIMALS), new BN(0))
+        .accounts({
+          user: gatedUser.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          userUsdcAccount: gatedUserUsdcAccount,
+          userIptAccount: gatedUserIptAccount,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([gatedUser])
+        .rpc();
+    });
+
+    it("Withdrawals beyond the epoch gate are queued despite ample reserves", async () => {
+      const originalConfig = (await program.account.pool.fetch(poolPda)).config;
+
+      // 0.01% of NAV per epoch
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, maxRedemptionBpsPerEpoch: 1 })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      const usdcBefore = (await getAccount(provider.connection, gatedUserUsdcAccount)).amount;
+
+      await program.methods
+        .userWithdraw(new BN(100 * 10 ** DECIMALS), new BN(0))
+        .accounts({
+          user: gatedUser.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          userUsdcAccount: gatedUserUsdcAccount,
+          userIptAccount: gatedUserIptAccount,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([gatedUser])
+        .rpc();
+
+      const pool = await program.account.pool.fetch(poolPda);
+      const usdcAfter = (await getAccount(provider.connection, gatedUserUsdcAccount)).amount;
+      assert.equal(usdcAfter.toString(), usdcBefore.toString(), "No USDC should leave past the gate");
+      assert.isTrue(
+        liveQueue(pool).some((entry) => entry.user.equals(gatedUser.publicKey)),
+        "Gated withdrawal should be queued"
+      );
+
+      await program.methods
+        .adminUpdateConfig(originalConfig)
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+    });
+
+    it("Withdrawals into a deep queue pay the exit adjustment", async () => {
+      const poolBefore = await program.account.pool.fetch(poolPda);
+      const originalConfig = poolBefore.config;
+      assert.isAbove(liveQueue(poolBefore).length, 1, "Earlier tests leave a queue behind");
+
+      // 5% exit adjustment once more than one withdrawal is waiting
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, queuePressureDepth: 1, queuePressureBps: 500 })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      const iptAmount = new BN(100 * 10 ** DECIMALS);
+      const gross = iptAmount.mul(poolBefore.currentExchangeRate).div(new BN(1_000_000));
+      const fee = gross.muln(WITHDRAWAL_FEE_BPS).divn(10_000);
+      const adjustment = gross.muln(500).divn(10_000);
+      const usdcBefore = (await getAccount(provider.connection, gatedUserUsdcAccount)).amount;
+
+      await program.methods
+        .userWithdraw(iptAmount, new BN(0))
+        .accounts({
+          user: gatedUser.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          userUsdcAccount: gatedUserUsdcAccount,
+          userIptAccount: gatedUserIptAccount,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([gatedUser])
+        .rpc();
+
+      const usdcAfter = (await getAccount(provider.connection, gatedUserUsdcAccount)).amount;
+      assert.equal(
+        (usdcAfter - usdcBefore).toString(),
+        gross.sub(fee).sub(adjustment).toString(),
+        "Payout should carry the exit adjustment"
+      );
+
+      await program.methods
+        .adminUpdateConfig(originalConfig)
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+    });
+
+    it("Early cancellation burns the cancellation fee", async () => {
+      const poolBefore = await program.account.pool.fetch(poolPda);
+      const originalConfig = poolBefore.config;
+      const queued = liveQueue(poolBefore).find((e) => e.user.equals(gatedUser.publicKey));
+      assert.isDefined(queued, "The gated withdrawal is still queued");
+
+      // 1%, waived only after a day in the queue
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, cancellationFeeBps: 100, cancellationGraceSecs: new BN(86_400) })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      const iptBefore = (await getAccount(provider.connection, gatedUserIptAccount)).amount;
+
+      await program.methods
+        .cancelWithdrawalRequest()
+        .accounts({
+          user: gatedUser.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          userIptAccount: gatedUserIptAccount,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([gatedUser])
+        .rpc();
+
+      const fee = queued.amount.divn(100);
+      const iptAfter = (await getAccount(provider.connection, gatedUserIptAccount)).amount;
+      assert.equal((iptBefore - iptAfter).toString(), fee.toString(), "Fee is burned from the user");
+
+      const poolAfter = await program.account.pool.fetch(poolPda);
+      assert.equal(poolBefore.totalIptSupply.sub(poolAfter.totalIptSupply).toString(), fee.toString());
+      assert.isTrue(poolAfter.totalAccumulatedFees.gt(poolBefore.totalAccumulatedFees), "Fee value is booked to the pool");
+      assert.isFalse(liveQueue(poolAfter).some((e) => e.user.equals(gatedUser.publicKey)));
+
+      await program.methods
+        .adminUpdateConfig(originalConfig)
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+    });
+
+    it("A review window queues every withdrawal until it elapses", async () => {
+      const originalConfig = (await program.account.pool.fetch(poolPda)).config;
+
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, minExecutionDelaySecs: new BN(3600) })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      const usdcBefore = (await getAccount(provider.connection, gatedUserUsdcAccount)).amount;
+
+      await program.methods
+        .userWithdraw(new BN(10 * 10 ** DECIMALS), new BN(0))
+        .accounts({
+          user: gatedUser.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          poolAuthority: poolAuthority,
+          userUsdcAccount: gatedUserUsdcAccount,
+          userIptAccount: gatedUserIptAccount,
+          poolUsdcReserve: usdcReserve,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([gatedUser])
+        .rpc();
+
+      const usdcAfter = (await getAccount(provider.connection, gatedUserUsdcAccount)).amount;
+      assert.equal(usdcAfter.toString(), usdcBefore.toString(), "Nothing is paid inside the review window");
+
+      const pool = await program.account.pool.fetch(poolPda);
+      const entry = liveQueue(pool).find((e) => e.user.equals(gatedUser.publicKey));
+      assert.isDefined(entry, "Withdrawal should be queued despite ample reserves");
+      assert.equal(entry.earliestExecution.sub(entry.requestedAt).toNumber(), 3600);
+
+      await program.methods
+        .adminUpdateConfig(originalConfig)
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+    });
+
+    it("Shrinking a queued request keeps its place in line", async () => {
+      const poolBefore = await program.account.pool.fetch(poolPda);
+      const positionBefore = liveQueue(poolBefore).findIndex((e) => e.user.equals(gatedUser.publicKey));
+      assert.isAtLeast(positionBefore, 0, "The review-window request is still queued");
+      const amountBefore = liveQueue(poolBefore)[positionBefore].amount;
+      const cancelAmount = new BN(4 * 10 ** DECIMALS);
+
+      await program.methods
+        .shrinkWithdrawalRequest(cancelAmount)
+        .accounts({
+          user: gatedUser.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+          userIptAccount: gatedUserIptAccount,
+          iptMint: iptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([gatedUser])
+        .rpc();
+
+      const poolAfter = await program.account.pool.fetch(poolPda);
+      const positionAfter = liveQueue(poolAfter).findIndex((e) => e.user.equals(gatedUser.publicKey));
+      assert.equal(positionAfter, positionBefore, "Queue position should be kept");
+      assert.equal(
+        liveQueue(poolAfter)[positionAfter].amount.toString(),
+        amountBefore.sub(cancelAmount).toString()
+      );
+
+      // Shrinking to nothing is a full cancellation, which has its own instruction
+      try {
+        await program.methods
+          .shrinkWithdrawalRequest(amountBefore.sub(cancelAmount))
+          .accounts({
+            user: gatedUser.publicKey,
+            pool: poolPda,
+            protocolConfig: protocolConfigPda,
+            userIptAccount: gatedUserIptAccount,
+            iptMint: iptMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([gatedUser])
+          .rpc();
+        assert.fail("Should have thrown InvalidAmount");
+      } catch (err: any) {
+        assert.include(err.toString(), "InvalidAmount");
+      }
+    });
+
+    it("Size-weighted priority serves small requests first", async () => {
+      const originalConfig = (await program.account.pool.fetch(poolPda)).config;
+
+      // One whole IPT outweighs ~11 days of waiting
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, queueAgeWeight: 1, queueSizeWeight: 1_000_000 })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      const wholeIpt = (entry) => entry.amount.div(new BN(10 ** DECIMALS)).toNumber();
+      let pool = await program.account.pool.fetch(poolPda);
+      assert.isAbove(liveQueue(pool).length, 1, "Earlier tests leave a queue behind");
+      for (let i = 1; i < liveQueue(pool).length; i++) {
+        assert.isAtMost(
+          wholeIpt(liveQueue(pool)[i - 1]),
+          wholeIpt(liveQueue(pool)[i]),
+          "Queue should be ordered smallest first"
+        );
+      }
+
+      // Back to FIFO: request time decides again
+      await program.methods
+        .adminUpdateConfig(originalConfig)
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      pool = await program.account.pool.fetch(poolPda);
+      for (let i = 1; i < liveQueue(pool).length; i++) {
+        assert.isTrue(
+          liveQueue(pool)[i - 1].requestedAt.lte(liveQueue(pool)[i].requestedAt),
+          "Queue should be ordered oldest first"
+        );
+      }
+    });
+
+    it("Isolated exits reject withdrawals composed with other instructions", async () => {
+      const originalConfig = (await program.account.pool.fetch(poolPda)).config;
+
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, isolatedExits: true })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .userWithdraw(new BN(10 * 10 ** DECIMALS), new BN(0))
+          .accounts({
+            user: gatedUser.publicKey,
+            pool: poolPda,
+            protocolConfig: protocolConfigPda,
+            poolAuthority: poolAuthority,
+            userUsdcAccount: gatedUserUsdcAccount,
+            userIptAccount: gatedUserIptAccount,
+            poolUsdcReserve: usdcReserve,
+            iptMint: iptMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .preInstructions([
+            SystemProgram.transfer({
+              fromPubkey: gatedUser.publicKey,
+              toPubkey: payer.publicKey,
+              lamports: 1,
+            }),
+          ])
+          .signers([gatedUser])
+          .rpc();
+        assert.fail("Composed withdrawal should be rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "TransactionShapeRejected");
+      }
+
+      await program.methods
+        .adminUpdateConfig(originalConfig)
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+    });
+
+    it("Large withdrawals must be committed and revealed after the slot delay", async () => {
+      const originalConfig = (await program.account.pool.fetch(poolPda)).config;
+      const amount = new BN(10 * 10 ** DECIMALS);
+      const salt = Keypair.generate().publicKey.toBuffer();
+      const commitment = createHash("sha256")
+        .update(
+          Buffer.concat([
+            poolPda.toBuffer(),
+            gatedUser.publicKey.toBuffer(),
+            amount.toArrayLike(Buffer, "le", 8),
+            new BN(0).toArrayLike(Buffer, "le", 8),
+            salt,
+          ])
+        )
+        .digest();
+      const [withdrawalCommitment] = PublicKey.findProgramAddressSync(
+        [Buffer.from("withdrawal_commitment"), poolPda.toBuffer(), gatedUser.publicKey.toBuffer()],
+        program.programId
+      );
+      const withdrawAccounts = {
+        user: gatedUser.publicKey,
+        pool: poolPda,
+        protocolConfig: protocolConfigPda,
+        poolAuthority: poolAuthority,
+        userUsdcAccount: gatedUserUsdcAccount,
+        userIptAccount: gatedUserIptAccount,
+        poolUsdcReserve: usdcReserve,
+        iptMint: iptMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      };
+
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, commitRevealThreshold: amount, revealDelaySlots: new BN(1_000) })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .userWithdraw(amount, new BN(0))
+          .accounts(withdrawAccounts)
+          .signers([gatedUser])
+          .rpc();
+        assert.fail("Uncommitted large withdrawal should be rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "WithdrawalCommitmentRequired");
+      }
+
+      await program.methods
+        .commitWithdrawal(Array.from(commitment))
+        .accounts({
+          user: gatedUser.publicKey,
+          pool: poolPda,
+          withdrawalCommitment,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([gatedUser])
+        .rpc();
+
+      const stored = await program.account.withdrawalCommitment.fetch(withdrawalCommitment);
+      assert.deepEqual(stored.commitment, Array.from(commitment));
+
+      try {
+        await program.methods
+          .revealWithdrawal(amount.add(new BN(1)), new BN(0), Array.from(salt))
+          .accounts({ withdraw: withdrawAccounts, withdrawalCommitment })
+          .signers([gatedUser])
+          .rpc();
+        assert.fail("Mismatched reveal should be rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "CommitmentMismatch");
+      }
+
+      try {
+        await program.methods
+          .revealWithdrawal(amount, new BN(0), Array.from(salt))
+          .accounts({ withdraw: withdrawAccounts, withdrawalCommitment })
+          .signers([gatedUser])
+          .rpc();
+        assert.fail("Reveal before the delay should be rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "RevealTooEarly");
+      }
+
+      await program.methods
+        .adminUpdateConfig(originalConfig)
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+    });
+
+    it("Only the compliance authority approves, and only requests that need it", async () => {
+      const pool = await program.account.pool.fetch(poolPda);
+      const originalConfig = pool.config;
+      const compliance = Keypair.generate();
+      const entry = liveQueue(pool).find((e) => e.user.equals(gatedUser.publicKey));
+      assert.isDefined(entry, "The gated withdrawal is still queued");
+      assert.isTrue(entry.approved, "Requests queued below the threshold need no approval");
+
+      const scheduleAndSetComplianceAuthority = async (authority: PublicKey) => {
+        const { nextTimelockId } = await program.account.pool.fetch(poolPda);
+        const [timelockOperation] = PublicKey.findProgramAddressSync(
+          [Buffer.from("timelock"), poolPda.toBuffer(), nextTimelockId.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        await program.methods
+          .scheduleAdminAction({ setRoleAuthority: { role: { compliance: {} }, authority } })
+          .accounts({
+            admin: admin.publicKey,
+            pool: poolPda,
+            timelockOperation,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        await program.methods
+          .setRoleAuthority()
+          .accounts({
+            admin: admin.publicKey,
+            pool: poolPda,
+            timelockOperation,
+          })
+          .rpc();
+      };
+      await scheduleAndSetComplianceAuthority(compliance.publicKey);
+
+      await program.methods
+        .adminUpdateConfig({
+          ...originalConfig,
+          approvalThreshold: new BN(1),
+          complianceAuthority: compliance.publicKey,
+          approvalExpirySecs: new BN(86_400),
+          largeWithdrawalThreshold: new BN(0),
+          withdrawalCosigner: PublicKey.default,
+          maxBuybackUsdc: new BN(0),
+          feeSweepThreshold: new BN(0),
+          feeRecipients: [],
+          oracleOverlapSecs: new BN(0),
+          promoStartAt: new BN(0),
+          promoEndAt: new BN(0),
+          promoBonusBps: 0,
+          promoWaivesDepositFee: false,
+          earlySupporterSlots: new BN(0),
+          earlySupporterDiscountBps: 0,
+          referralFeeShareBps: 0,
+          allowlistOnly: false,
+          recoveryAuthority: PublicKey.default,
+          deadManSwitchSecs: new BN(0),
+          dormancyPeriodSecs: new BN(0),
+          escheatCustody: PublicKey.default,
+          reserveDriftToleranceBps: 0,
+          windDownWaterfall: [],
+          pullPayouts: false,
+          guardianAuthority: PublicKey.default,
+          queueAlertDepth: 0,
+          queueAlertUsdc: new BN(0),
+          tierTerms: [],
+          maxQueueEntriesPerDay: 0,
+          refundWindowSecs: new BN(0),
+          slotStableQuotes: false,
+          sweepWrapperProgram: PublicKey.default,
+          sweepWrappedMint: PublicKey.default,
+          liquidityBufferUsdc: new BN(0),
+          minRateUpdateIntervalSecs: new BN(0),
+        })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .approveWithdrawalRequest(gatedUser.publicKey)
+          .accounts({
+            complianceAuthority: gatedUser.publicKey,
+            pool: poolPda,
+          })
+          .signers([gatedUser])
+          .rpc();
+        assert.fail("Approval by anyone else should be rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "UnauthorizedComplianceAuthority");
+      }
+
+      try {
+        await program.methods
+          .approveWithdrawalRequest(gatedUser.publicKey)
+          .accounts({
+            complianceAuthority: compliance.publicKey,
+            pool: poolPda,
+          })
+          .signers([compliance])
+          .rpc();
+        assert.fail("Approving an already cleared request should be rejected");
+      } catch (err: any) {
+        assert.include(err.toString(), "ApprovalNotRequired");
+      }
+
+      // The threshold comes down before the compliance key can go
+      await program.methods
+        .adminUpdateConfig({ ...originalConfig, complianceAuthority: compliance.publicKey })
+        .accounts({
+          admin: admin.publicKey,
+          pool: poolPda,
+          protocolConfig: protocolConfigPda,
+        })
+        .rpc();
+      await scheduleAndSetComplianceAuthority(originalConfig.complianceAuthority);
+    });
+  });
+
   describe("Edge Case: Partial Balance Transfer", () => {
     it("User transfers PART of their IPT after queuing", async () => {
       // This test would verify that the system handles cases where
@@ -469,9 +1174,9 @@ describe("refi-ipt - Edge Cases & Security Tests", () => {
       const pool = await program.account.pool.fetch(poolPda);
 
       console.log("\n========== QUEUE SECURITY TEST ==========");
-      console.log("Pending queue length:", pool.pendingQueue.length);
+      console.log("Pending queue length:", liveQueue(pool).length);
       console.log("Queue entries:");
-      pool.pendingQueue.forEach((entry, idx) => {
+      liveQueue(pool).forEach((entry, idx) => {
         console.log(`  [${idx}] User: ${entry.user.toString()}, Amount: ${entry.amount.toString()}`);
       });
       console.log("=========================================\n");
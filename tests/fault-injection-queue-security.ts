@@ -13,9 +13,12 @@ import {
   transfer,
   revoke,
 } from "@solana/spl-token";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
 import { assert } from "chai";
 
+// Withdrawals still waiting; entries before queueHead were consumed by batch execution
+const liveQueue = (pool) => pool.pendingQueue.slice(pool.queueHead);
+
 describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -33,6 +36,10 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
   // Wallets
   const payer = (provider.wallet as anchor.Wallet).payer;
   const admin = provider.wallet;
+  const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+  );
+  let protocolConfigPda: PublicKey;
   const oracle = Keypair.generate();
   const feeCollector = Keypair.generate();
   const executor = Keypair.generate();
@@ -56,6 +63,7 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
   let victim3IptAccount: PublicKey;
 
   const DECIMALS = 6;
+  const POOL_ID = new BN(0);
   const INITIAL_EXCHANGE_RATE = new BN(1_034_200);
 
   // Test results
@@ -83,6 +91,33 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
     return err.toString().substring(0, 150);
   }
 
+  // Program-wide config is created once per validator by the upgrade authority
+  before(async () => {
+    [protocolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    const existing = await provider.connection.getAccountInfo(protocolConfigPda);
+    if (!existing) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_ID
+      );
+
+      await program.methods
+        .initProtocolConfig(admin.publicKey, 10_000, 10_000, 10_000)
+        .accounts({
+          authority: admin.publicKey,
+          protocolConfig: protocolConfigPda,
+          program: program.programId,
+          programData,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
   before(async () => {
     console.log("\n🔧 Setting up Queue Security Test Environment...\n");
 
@@ -115,7 +150,7 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
 
     // Derive PDAs
     [poolPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("pool"), usdcMint.toBuffer()],
+      [Buffer.from("pool"), usdcMint.toBuffer(), POOL_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     poolAuthority = poolPda;
@@ -163,18 +198,75 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       adminAuthority: admin.publicKey,
       oracleAuthority: oracle.publicKey,
       feeCollector: feeCollector.publicKey,
+      treasurerAuthority: admin.publicKey,
       depositFeeBps: 100,
       withdrawalFeeBps: 100,
       managementFeeBps: 50,
       initialExchangeRate: INITIAL_EXCHANGE_RATE,
       maxTotalSupply: new BN(0),
       maxQueueSize: 20,
+      timelockDelay: new BN(0),
+      rateFloor: new BN(0),
+      rateCeiling: new BN(0),
+      maxHeartbeatSecs: new BN(0),
+      maxRedemptionBpsPerEpoch: 0,
+      queuePressureDepth: 0,
+      queuePressureBps: 0,
+      rebasing: false,
+      epochDurationSecs: new BN(86_400),
+      segregateRoles: false,
+      isolatedExits: false,
+      queueAgeWeight: 0,
+      queueSizeWeight: 0,
+      maxBatchShareBps: 0,
+      maxSkipAttempts: 1,
+      minExecutionDelaySecs: new BN(0),
+      cancellationFeeBps: 0,
+      cancellationGraceSecs: new BN(0),
+      commitRevealThreshold: new BN(0),
+      revealDelaySlots: new BN(0),
+      approvalThreshold: new BN(0),
+      complianceAuthority: PublicKey.default,
+      approvalExpirySecs: new BN(0),
+      largeWithdrawalThreshold: new BN(0),
+      withdrawalCosigner: PublicKey.default,
+      maxBuybackUsdc: new BN(0),
+      feeSweepThreshold: new BN(0),
+      feeRecipients: [],
+      oracleOverlapSecs: new BN(0),
+      promoStartAt: new BN(0),
+      promoEndAt: new BN(0),
+      promoBonusBps: 0,
+      promoWaivesDepositFee: false,
+      earlySupporterSlots: new BN(0),
+      earlySupporterDiscountBps: 0,
+      referralFeeShareBps: 0,
+      allowlistOnly: false,
+      recoveryAuthority: PublicKey.default,
+      deadManSwitchSecs: new BN(0),
+      dormancyPeriodSecs: new BN(0),
+      escheatCustody: PublicKey.default,
+      reserveDriftToleranceBps: 0,
+      windDownWaterfall: [],
+      pullPayouts: false,
+      guardianAuthority: PublicKey.default,
+      queueAlertDepth: 0,
+      queueAlertUsdc: new BN(0),
+      tierTerms: [],
+      maxQueueEntriesPerDay: 0,
+      refundWindowSecs: new BN(0),
+      slotStableQuotes: false,
+      sweepWrapperProgram: PublicKey.default,
+      sweepWrappedMint: PublicKey.default,
+      liquidityBufferUsdc: new BN(0),
+      minRateUpdateIntervalSecs: new BN(0),
     };
 
     await program.methods
-      .initPool(config)
+      .initPool(POOL_ID, config)
       .accounts({
         payer: admin.publicKey,
+        protocolConfig: protocolConfigPda,
         usdcMint: usdcMint,
         pool: poolPda,
         systemProgram: SystemProgram.programId,
@@ -182,9 +274,9 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       .rpc();
 
     await program.methods
-      .initPoolStep2()
+      .initPoolStep2(DECIMALS)
       .accounts({
-        payer: admin.publicKey,
+        admin: admin.publicKey,
         pool: poolPda,
         poolAuthority: poolAuthority,
         usdcMint: usdcMint,
@@ -220,11 +312,11 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
 
     // Admin deposits initial reserves
     await program.methods
-      .adminDepositUsdc(new BN(200_000 * 10 ** DECIMALS))
+      .adminDepositUsdc(new BN(200_000 * 10 ** DECIMALS), { rebalance: {} }, null)
       .accounts({
-        admin: admin.publicKey,
+        treasurer: admin.publicKey,
         pool: poolPda,
-        adminUsdcAccount: adminUsdcAccount,
+        treasurerUsdcAccount: adminUsdcAccount,
         poolUsdcReserve: usdcReserve,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
@@ -246,6 +338,7 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
         .accounts({
           user: user.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: usdcAcc,
           userIptAccount: iptAcc,
@@ -272,12 +365,12 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       const reserveToWithdraw = pool.totalUsdcReserves.sub(new BN(1000 * 10 ** DECIMALS));
       
       await program.methods
-        .adminWithdrawUsdc(reserveToWithdraw)
+        .adminWithdrawUsdc(reserveToWithdraw, { rebalance: {} }, null)
         .accounts({
-          admin: admin.publicKey,
+          treasurer: admin.publicKey,
           pool: poolPda,
           poolAuthority: poolAuthority,
-          adminUsdcAccount: adminUsdcAccount,
+          treasurerUsdcAccount: adminUsdcAccount,
           poolUsdcReserve: usdcReserve,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -312,21 +405,23 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
         .accounts({
           user: attacker.publicKey,
           pool: poolPda,
+          protocolConfig: protocolConfigPda,
           poolAuthority: poolAuthority,
           userUsdcAccount: attackerUsdcAccount,
           userIptAccount: attackerIptAccount,
           poolUsdcReserve: usdcReserve,
           iptMint: iptMint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         })
         .signers([attacker])
         .rpc();
       
       const poolAfter = await program.account.pool.fetch(poolPda);
       
-      if (poolAfter.pendingQueue.length > 0) {
+      if (liveQueue(poolAfter).length > 0) {
         console.log(`✅ ${testId}: Attacker added to queue at position 0`);
-        console.log(`   Queue length: ${poolAfter.pendingQueue.length}`);
+        console.log(`   Queue length: ${liveQueue(poolAfter).length}`);
         recordResult(testId, "Malicious user queues withdrawal request", "PASS", `Queue position: 0`);
       } else {
         console.log(`⚠️ ${testId}: Withdrawal was instant (reserves available)`);
@@ -394,12 +489,14 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
             .accounts({
               user: user.publicKey,
               pool: poolPda,
+              protocolConfig: protocolConfigPda,
               poolAuthority: poolAuthority,
               userUsdcAccount: usdcAcc,
               userIptAccount: iptAcc,
               poolUsdcReserve: usdcReserve,
               iptMint: iptMint,
               tokenProgram: TOKEN_PROGRAM_ID,
+              instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
             })
             .signers([user])
             .rpc();
@@ -408,24 +505,24 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       
       const poolAfter = await program.account.pool.fetch(poolPda);
       console.log(`✅ ${testId}: Victims added to queue`);
-      console.log(`   Queue length: ${poolAfter.pendingQueue.length}`);
+      console.log(`   Queue length: ${liveQueue(poolAfter).length}`);
       console.log(`   Queue state:`);
-      poolAfter.pendingQueue.forEach((entry, idx) => {
+      liveQueue(poolAfter).forEach((entry, idx) => {
         console.log(`     [${idx}] User: ${entry.user.toString().substring(0, 8)}..., Amount: ${entry.amount.toString()}`);
       });
       
-      recordResult(testId, "Valid users queue withdrawals behind attacker", "PASS", `Queue length: ${poolAfter.pendingQueue.length}`);
+      recordResult(testId, "Valid users queue withdrawals behind attacker", "PASS", `Queue length: ${liveQueue(poolAfter).length}`);
     });
 
     it("SEC-05: Admin restores reserves for batch processing", async () => {
       const testId = "SEC-05";
       
       await program.methods
-        .adminDepositUsdc(new BN(100_000 * 10 ** DECIMALS))
+        .adminDepositUsdc(new BN(100_000 * 10 ** DECIMALS), { rebalance: {} }, null)
         .accounts({
-          admin: admin.publicKey,
+          treasurer: admin.publicKey,
           pool: poolPda,
-          adminUsdcAccount: adminUsdcAccount,
+          treasurerUsdcAccount: adminUsdcAccount,
           poolUsdcReserve: usdcReserve,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -439,7 +536,7 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       const testId = "SEC-06";
       
       const poolBefore = await program.account.pool.fetch(poolPda);
-      const queueLengthBefore = poolBefore.pendingQueue.length;
+      const queueLengthBefore = liveQueue(poolBefore).length;
       
       if (queueLengthBefore === 0) {
         console.log(`⚠️ ${testId}: Queue is empty, skipping test`);
@@ -451,12 +548,12 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       console.log(`   Queue before: ${queueLengthBefore} entries`);
       
       // Get amounts from queue
-      const amounts = poolBefore.pendingQueue.map(w => w.amount);
+      const amounts = liveQueue(poolBefore).map(w => w.amount);
       
       // Build remaining accounts array (2 accounts per user: IPT, USDC)
       const remainingAccounts: anchor.web3.AccountMeta[] = [];
       
-      for (const pending of poolBefore.pendingQueue) {
+      for (const pending of liveQueue(poolBefore)) {
         // Find the corresponding accounts
         let userIptAcc: PublicKey;
         let userUsdcAcc: PublicKey;
@@ -486,10 +583,12 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
           .accounts({
             executor: executor.publicKey,
             pool: poolPda,
+            protocolConfig: protocolConfigPda,
             poolAuthority: poolAuthority,
             poolUsdcReserve: usdcReserve,
             iptMint: iptMint,
             tokenProgram: TOKEN_PROGRAM_ID,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
           })
           .remainingAccounts(remainingAccounts)
           .signers([executor])
@@ -498,7 +597,7 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
         console.log(`   ✅ Batch tx: ${tx}`);
         
         const poolAfter = await program.account.pool.fetch(poolPda);
-        const queueLengthAfter = poolAfter.pendingQueue.length;
+        const queueLengthAfter = liveQueue(poolAfter).length;
         
         console.log(`   Queue after: ${queueLengthAfter} entries`);
         
@@ -540,12 +639,12 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
         if (pool.totalUsdcReserves.gt(new BN(1000 * 10 ** DECIMALS))) {
           const toWithdraw = pool.totalUsdcReserves.sub(new BN(500 * 10 ** DECIMALS));
           await program.methods
-            .adminWithdrawUsdc(toWithdraw)
+            .adminWithdrawUsdc(toWithdraw, { rebalance: {} }, null)
             .accounts({
-              admin: admin.publicKey,
+              treasurer: admin.publicKey,
               pool: poolPda,
               poolAuthority: poolAuthority,
-              adminUsdcAccount: adminUsdcAccount,
+              treasurerUsdcAccount: adminUsdcAccount,
               poolUsdcReserve: usdcReserve,
               tokenProgram: TOKEN_PROGRAM_ID,
             })
@@ -568,12 +667,14 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
             .accounts({
               user: victim3.publicKey,
               pool: poolPda,
+              protocolConfig: protocolConfigPda,
               poolAuthority: poolAuthority,
               userUsdcAccount: victim3UsdcAccount,
               userIptAccount: victim3IptAccount,
               poolUsdcReserve: usdcReserve,
               iptMint: iptMint,
               tokenProgram: TOKEN_PROGRAM_ID,
+              instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
             })
             .signers([victim3])
             .rpc();
@@ -588,7 +689,7 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
           
           console.log(`✅ ${testId}: User queued then revoked delegation`);
           console.log(`   When batch processes, this user should be SKIPPED`);
-          console.log(`   with NotDelegated or InsufficientDelegation error`);
+          console.log(`   as "IPT not delegated to pool" instead of failing the batch`);
           
           recordResult(testId, "User revokes delegation after queuing", "PASS", "Delegation revoked after queuing");
           
@@ -612,7 +713,7 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       
       // Check if any victim is already in queue
       const pool = await program.account.pool.fetch(poolPda);
-      const queuedUsers = pool.pendingQueue.map(w => w.user.toString());
+      const queuedUsers = liveQueue(pool).map(w => w.user.toString());
       
       // Find a user that's in the queue
       let targetUser: Keypair | null = null;
@@ -661,12 +762,14 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
           .accounts({
             user: targetUser.publicKey,
             pool: poolPda,
+            protocolConfig: protocolConfigPda,
             poolAuthority: poolAuthority,
             userUsdcAccount: targetUsdcAcc!,
             userIptAccount: targetIptAcc!,
             poolUsdcReserve: usdcReserve,
             iptMint: iptMint,
             tokenProgram: TOKEN_PROGRAM_ID,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
           })
           .signers([targetUser])
           .rpc();
@@ -691,7 +794,7 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       const expectedError = "EmptyWithdrawalBatch";
       
       const pool = await program.account.pool.fetch(poolPda);
-      const queueLength = pool.pendingQueue.length;
+      const queueLength = liveQueue(pool).length;
       
       // Create amounts array larger than queue
       const amounts = Array(queueLength + 5).fill(new BN(100));
@@ -702,10 +805,12 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
           .accounts({
             executor: executor.publicKey,
             pool: poolPda,
+            protocolConfig: protocolConfigPda,
             poolAuthority: poolAuthority,
             poolUsdcReserve: usdcReserve,
             iptMint: iptMint,
             tokenProgram: TOKEN_PROGRAM_ID,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
           })
           .signers([executor])
           .rpc();
@@ -730,12 +835,12 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
       
       const pool = await program.account.pool.fetch(poolPda);
       
-      if (pool.pendingQueue.length === 0) {
+      if (liveQueue(pool).length === 0) {
         recordResult(testId, "Fails when remaining accounts count is invalid", "SKIP", "Empty queue");
         return;
       }
       
-      const amounts = [pool.pendingQueue[0].amount];
+      const amounts = [liveQueue(pool)[0].amount];
       
       // Provide only 1 account instead of 2
       const remainingAccounts = [
@@ -749,10 +854,12 @@ describe("🔴 FAULT-INJECTION: Queue Security & Batch Operations", () => {
           .accounts({
             executor: executor.publicKey,
             pool: poolPda,
+            protocolConfig: protocolConfigPda,
             poolAuthority: poolAuthority,
             poolUsdcReserve: usdcReserve,
             iptMint: iptMint,
             tokenProgram: TOKEN_PROGRAM_ID,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
           })
           .remainingAccounts(remainingAccounts)
           .signers([executor])